@@ -1,22 +1,65 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use darling::{ast, util, FromDeriveInput, FromField};
 use proc_macro2::Ident;
-use quote::{format_ident, quote};
-use syn::{parse_macro_input, DeriveInput, Type};
+use quote::{format_ident, quote, quote_spanned};
+use syn::{parse_macro_input, spanned::Spanned, DeriveInput, Type};
 
 enum FilterType<'a> {
     Number(&'a Ident, Option<String>),
     String(&'a Ident, Option<String>),
+    Dynamic(&'a Ident, Option<String>),
+    Nullable(&'a Ident, Option<String>),
+    In(&'a Ident, Option<String>),
+    Array(&'a Ident, Option<String>),
+    Search(&'a Ident, Option<String>),
     None(&'a Ident, Option<String>),
 }
 
+impl<'a> FilterType<'a> {
+    /// The field and its `alias`, whatever its filter type.
+    fn ident_and_alias(&self) -> (&'a Ident, Option<&str>) {
+        match self {
+            FilterType::Number(ident, alias)
+            | FilterType::String(ident, alias)
+            | FilterType::Dynamic(ident, alias)
+            | FilterType::Nullable(ident, alias)
+            | FilterType::In(ident, alias)
+            | FilterType::Array(ident, alias)
+            | FilterType::Search(ident, alias)
+            | FilterType::None(ident, alias) => (ident, alias.as_deref()),
+        }
+    }
+
+    /// The path of the filter type the field is parsed with.
+    fn path(&self) -> proc_macro2::TokenStream {
+        match self {
+            FilterType::Number(..) => quote!(filtrum::NumberFilters),
+            FilterType::String(..) => quote!(filtrum::StringFilters),
+            FilterType::Dynamic(..) => quote!(filtrum::DynamicFilters),
+            FilterType::Nullable(..) => quote!(filtrum::NullableFilter),
+            FilterType::In(..) => quote!(filtrum::InFilters),
+            FilterType::Array(..) => quote!(filtrum::ArrayFilters),
+            FilterType::Search(..) => quote!(filtrum::SearchFilter),
+            FilterType::None(..) => quote!(filtrum::EqualFilter),
+        }
+    }
+}
+
 #[derive(Debug, FromDeriveInput)]
 #[darling(attributes(filtrum), supports(struct_named))]
 struct MacroArgs {
     data: ast::Data<util::Ignored, FieldMacroArgs>,
     #[darling(default)]
     table: Option<String>,
+    #[darling(default)]
+    tiebreak: Option<String>,
+    #[darling(default)]
+    order_by: Option<String>,
+    #[darling(default)]
+    getters: bool,
+    #[darling(default)]
+    allow_reserved: bool,
 }
 
 #[derive(Debug, FromField)]
@@ -31,30 +74,293 @@ struct FieldMacroArgs {
     alias: Option<String>,
     #[darling(default)]
     skip: bool,
+    #[darling(default)]
+    default_value: Option<String>,
+    #[darling(default)]
+    search: Option<util::PathList>,
+    #[darling(default)]
+    transform: Option<String>,
+    #[darling(default)]
+    kind: Option<String>,
+    #[darling(default)]
+    allowed_ops: Option<String>,
+}
+
+/// The filter types `kind` accepts, with the name of each.
+const KINDS: [(&str, &str); 8] = [
+    ("number", "NumberFilters"),
+    ("string", "StringFilters"),
+    ("dynamic", "DynamicFilters"),
+    ("nullable", "NullableFilter"),
+    ("in", "InFilters"),
+    ("array", "ArrayFilters"),
+    ("search", "SearchFilter"),
+    ("equal", "EqualFilter"),
+];
+
+/// Returns the filter type named by `kind`, ignoring case.
+fn kind_type(kind: &str) -> Option<&'static str> {
+    KINDS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(kind))
+        .map(|(_, ty)| *ty)
+}
+
+/// Returns the filter type a field is parsed as, e.g. `NumberFilters`: the one named by
+/// its `kind`, or else the last segment of its type (or of the `T` of an `Option<T>`),
+/// which also covers paths like `filtrum::NumberFilters<i32>`.
+fn filter_type_name(field: &FieldMacroArgs) -> Option<String> {
+    if let Some(kind) = &field.kind {
+        return kind_type(kind).map(|x| x.to_string());
+    }
+
+    let Type::Path(type_path) = option_inner(&field.ty).unwrap_or(&field.ty) else {
+        return None;
+    };
+
+    type_path.path.segments.last().map(|x| x.ident.to_string())
 }
 
-fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+/// Types a field is sometimes declared with by mistake, which are values rather than
+/// filters. Any other type is parsed as an `EqualFilter`, as it may be an alias of one.
+const PLAIN_TYPES: [&str; 23] = [
+    "bool", "char", "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128",
+    "usize", "f32", "f64", "str", "String", "Vec", "HashMap", "HashSet", "BTreeMap", "BTreeSet",
+];
+
+/// The default `ParseOptions::reserved` names and the keyset `cursor`, with what a
+/// request sets through each. `FromQueryFilter` reads these parameters besides the
+/// fields, so a field named after one is filtered on whatever the client paginates by.
+const RESERVED: [(&str, &str); 4] = [
+    ("limit", "the page size"),
+    ("skip", "the offset"),
+    ("order_by", "the sort"),
+    ("cursor", "the keyset cursor"),
+];
+
+/// Returns `true` if `field` is parsed as a `SearchFilter`.
+fn is_search_filter(field: &FieldMacroArgs) -> bool {
+    filter_type_name(field).as_deref() == Some("SearchFilter")
+}
+
+/// Returns the `T` in `Option<T>`, or `None` if `ty` is not an `Option`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+
+    match &segment.arguments {
+        syn::PathArguments::AngleBracketed(args) => match args.args.first()? {
+            syn::GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// The functions `transform` accepts, with the `filtrum::sqlx::Transform` variant of
+/// each.
+const TRANSFORMS: [(&str, &str); 4] = [
+    ("LOWER", "Lower"),
+    ("UPPER", "Upper"),
+    ("TRIM", "Trim"),
+    ("DATE", "Date"),
+];
+
+/// Returns the `filtrum::sqlx::Transform` variant named by `transform`, ignoring case.
+fn transform_variant(transform: &str) -> Option<Ident> {
+    TRANSFORMS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(transform))
+        .map(|(_, variant)| format_ident!("{}", variant))
+}
+
+/// Splits an `allowed_ops` list like `"eq, starts_with"` into its operator keys. An
+/// empty list allows no operator at all.
+fn allowed_ops(ops: &str) -> Vec<&str> {
+    match ops.trim() {
+        "" => Vec::new(),
+        ops => ops.split(',').map(str::trim).collect(),
+    }
+}
+
+/// The keys `filtrum::Op` parses into an operator of its own, short spellings included;
+/// any other key is an `Op::Other` that no filter handles.
+const OPERATORS: [&str; 36] = [
+    "eq",
+    "ne",
+    "nseq",
+    "gt",
+    "lt",
+    "gte",
+    "lte",
+    "between",
+    "range",
+    "like",
+    "l",
+    "not_like",
+    "nl",
+    "ilike",
+    "starts_with",
+    "sw",
+    "ends_with",
+    "ew",
+    "contains",
+    "c",
+    "regex",
+    "iregex",
+    "similar",
+    "sim",
+    "contains_unaccent",
+    "len_gt",
+    "len_lt",
+    "len_eq",
+    "in",
+    "not_in",
+    "nin",
+    "array_contains",
+    "fts",
+    "asc",
+    "desc",
+    "rand",
+];
+
+/// Returns `true` if `op` is the key of an operator, e.g. `gte` or `!starts_with`.
+fn is_operator_key(op: &str) -> bool {
+    OPERATORS.contains(&op.strip_prefix('!').unwrap_or(op))
+}
+
+/// Rejects attributes that contradict each other or can't produce a valid column, like
+/// `skip` with `alias`, or an empty `table`.
+fn check_attributes(name: &Ident, data: &MacroArgs) -> darling::Result<()> {
+    let mut errors = darling::Error::accumulator();
+
+    if data.table.as_deref() == Some("") {
+        errors.push(darling::Error::custom("`table` can't be empty").with_span(name));
+    }
+
+    for field in data.data.as_ref().take_struct().unwrap().fields {
+        let error = |msg: String| darling::Error::custom(msg).with_span(&field.ident);
+
+        for (attr, value) in [("table", &field.table), ("alias", &field.alias)] {
+            if value.as_deref() == Some("") {
+                errors.push(error(format!("`{attr}` can't be empty")));
+            }
+        }
+
+        let transform = field.transform.as_deref();
+        if let Some(transform) = transform.filter(|x| transform_variant(x).is_none()) {
+            let names = TRANSFORMS.map(|(name, _)| name).join(", ");
+            errors.push(error(format!(
+                "unknown `transform` `{transform}`, expected one of {names}"
+            )));
+        }
+
+        let kind = field.kind.as_deref();
+        if let Some(kind) = kind.filter(|x| kind_type(x).is_none()) {
+            let names = KINDS.map(|(name, _)| name).join(", ");
+            errors.push(error(format!(
+                "unknown `kind` `{kind}`, expected one of {names}"
+            )));
+        }
+
+        let ops = field
+            .allowed_ops
+            .as_deref()
+            .map(allowed_ops)
+            .unwrap_or_default();
+        if let Some(op) = ops.iter().find(|x| !is_operator_key(x)) {
+            let op = match *op {
+                "" => "an empty operator".to_string(),
+                op => format!("`{op}`, which isn't a known operator"),
+            };
+            errors.push(error(format!(
+                "`allowed_ops` has {op}; list keys like `eq, starts_with, !eq` separated by \
+                 commas, or leave it empty to allow none"
+            )));
+        }
+
+        if !field.skip {
+            if filter_type_name(field).is_some_and(|x| PLAIN_TYPES.contains(&x.as_str())) {
+                // the value type, without the `Option` and the spaces `quote!` adds
+                let ty = option_inner(&field.ty).unwrap_or(&field.ty);
+                let ty = quote!(#ty).to_string().replace(' ', "");
+                errors.push(
+                    darling::Error::custom(format!(
+                        "`{ty}` isn't a filter type; wrap it in one, e.g. `EqualFilter<{ty}>`, \
+                         or add `#[filtrum(skip)]`"
+                    ))
+                    .with_span(&field.ty),
+                );
+            }
+
+            let key = field.ident.as_ref().map(|x| x.to_string());
+            let reserved = key.and_then(|key| {
+                RESERVED
+                    .iter()
+                    .find(|(name, _)| !data.allow_reserved && *name == key)
+            });
+            if let Some((key, sets)) = reserved {
+                // the cursor parameter has a fixed name, unlike the `ParseOptions::reserved` ones
+                let unless = match *key {
+                    "cursor" => "the filter is never paginated with a `Cursor`",
+                    _ => "the filter is parsed with other `ParseOptions::reserved` names",
+                };
+                errors.push(error(format!(
+                    "`{key}` is a reserved parameter, so `{key}=` sets {sets} and filters this \
+                     field on the same value; rename the field, and keep the column with \
+                     `table` and `alias` (an `alias` only applies with a `table`) or \
+                     `ApplyOptions::columns`, or add `#[filtrum(allow_reserved)]` if {unless}"
+                )));
+            }
+
+            continue;
+        }
+
+        // a skipped field is never parsed or filtered on, so these would do nothing
+        let set = [
+            ("table", field.table.is_some()),
+            ("alias", field.alias.is_some()),
+            ("default_value", field.default_value.is_some()),
+            ("search", field.search.is_some()),
+            ("transform", field.transform.is_some()),
+            ("kind", field.kind.is_some()),
+            ("allowed_ops", field.allowed_ops.is_some()),
+        ];
+        for (attr, _) in set.into_iter().filter(|(_, set)| *set) {
+            errors.push(error(format!(
+                "`skip` can't be combined with `{attr}`, a skipped field is never filtered on"
+            )));
+        }
+    }
+
+    errors.finish()
+}
+
+fn expand_from_query_filter(input: &DeriveInput) -> darling::Result<proc_macro2::TokenStream> {
     let name = &input.ident;
 
-    let data = MacroArgs::from_derive_input(&input)?;
+    let data = MacroArgs::from_derive_input(input)?;
+    check_attributes(name, &data)?;
 
+    // a field-level table takes precedence over the struct-level one
     let custom_table = data
         .data
         .as_ref()
         .map_struct_fields(|x| {
-            if x.table.is_some() && x.ident.is_some() {
-                Some((
-                    x.ident.clone().unwrap().to_string(),
-                    x.table.clone().unwrap(),
-                ))
-            } else {
-                None
-            }
+            let table = x.table.as_ref().or(data.table.as_ref())?;
+
+            Some((x.ident.as_ref()?.to_string(), table.clone()))
         })
         .take_struct()
         .unwrap()
         .into_iter()
-        .filter_map(|x| x)
+        .flatten()
         .collect::<HashMap<String, String>>();
 
     let skipped_fields = data
@@ -70,7 +376,7 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
         .take_struct()
         .unwrap()
         .into_iter()
-        .filter_map(|x| x)
+        .flatten()
         .map(|x| {
             quote! {
                 #x: Default::default()
@@ -78,6 +384,71 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
         })
         .collect::<Vec<_>>();
 
+    // fields declared as `Option<...Filters>`, which hold `None` when nothing was parsed
+    let optional_fields = data
+        .data
+        .as_ref()
+        .map_struct_fields(|x| {
+            if !x.skip && option_inner(&x.ty).is_some() {
+                x.ident.as_ref().map(|x| x.to_string())
+            } else {
+                None
+            }
+        })
+        .take_struct()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<HashSet<String>>();
+
+    // the columns of each `SearchFilter` field, from `search(name, email)`
+    let mut search_columns = HashMap::new();
+    for field in data.data.as_ref().take_struct().unwrap().fields {
+        if field.skip {
+            continue;
+        }
+
+        match (&field.search, is_search_filter(field)) {
+            (None, false) => {}
+            (Some(columns), true) if !columns.is_empty() => {
+                let columns = columns
+                    .iter()
+                    .map(|x| quote! { stringify!(#x) })
+                    .collect::<Vec<_>>();
+                search_columns.insert(field.ident.as_ref().unwrap().to_string(), columns);
+            }
+            (Some(_), false) => {
+                return Err(
+                    darling::Error::custom("`search` needs a `SearchFilter` field")
+                        .with_span(&field.ident),
+                );
+            }
+            (_, true) => {
+                return Err(darling::Error::custom(
+                    "a `SearchFilter` field needs its columns, e.g. `#[filtrum(search(name, email))]`",
+                )
+                .with_span(&field.ident));
+            }
+        }
+    }
+
+    // fields with a `default_value`, which is parsed when the query has no such field
+    let default_values = data
+        .data
+        .as_ref()
+        .map_struct_fields(|x| {
+            if x.skip {
+                return None;
+            }
+
+            Some((x.ident.as_ref()?.to_string(), x.default_value.clone()?))
+        })
+        .take_struct()
+        .unwrap()
+        .into_iter()
+        .flatten()
+        .collect::<HashMap<String, String>>();
+
     let fields = data
         .data
         .as_ref()
@@ -94,7 +465,6 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
         .filter_map(|x| if let Some(x) = x { Some(x) } else { None })
         .filter_map(|f| {
             let name = &f.ident;
-            let ty = &f.ty;
             let alias = &f.alias;
 
             if name.is_none() && alias.is_none() {
@@ -102,32 +472,38 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
             }
 
             let alias = alias.as_ref().map(|x| x.to_string());
+            let ident = filter_type_name(f)?;
 
-            // if type is Option<NumberFilter<T>> then
-            // return (name, ty)
-
-            if let Type::Path(type_path) = ty {
-                if let Some(segment) = type_path.path.segments.last() {
-                    let ident = &segment.ident;
-                    if ident == "NumberFilters" {
-                        return Some(FilterType::Number(name.as_ref().unwrap(), alias));
-                    }
-                    if ident == "StringFilters" {
-                        return Some(FilterType::String(name.as_ref().unwrap(), alias));
-                    }
-
-                    return Some(FilterType::None(name.as_ref().unwrap(), alias));
-                }
+            if ident == "NumberFilters" {
+                return Some(FilterType::Number(name.as_ref().unwrap(), alias));
+            }
+            if ident == "StringFilters" {
+                return Some(FilterType::String(name.as_ref().unwrap(), alias));
+            }
+            if ident == "DynamicFilters" {
+                return Some(FilterType::Dynamic(name.as_ref().unwrap(), alias));
+            }
+            if ident == "NullableFilter" {
+                return Some(FilterType::Nullable(name.as_ref().unwrap(), alias));
+            }
+            if ident == "InFilters" {
+                return Some(FilterType::In(name.as_ref().unwrap(), alias));
+            }
+            if ident == "ArrayFilters" {
+                return Some(FilterType::Array(name.as_ref().unwrap(), alias));
+            }
+            if ident == "SearchFilter" {
+                return Some(FilterType::Search(name.as_ref().unwrap(), alias));
             }
 
-            None
+            Some(FilterType::None(name.as_ref().unwrap(), alias))
         })
         .collect::<Vec<_>>();
 
     fn create_search_id(
         table: &str,
         ident: &Ident,
-        alias: &Option<String>,
+        alias: Option<&str>,
         place: impl FnOnce() -> proc_macro2::TokenStream,
     ) -> proc_macro2::TokenStream {
         let var_name = format_ident!("{}", ident);
@@ -145,55 +521,66 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
         }
     }
 
-    let fields_as_filters = fields.iter().filter_map(|f| {
-            let f = match f {
-                FilterType::Number(ident, alias) => {
-                    let var_name = format_ident!("{}", ident);
-
-                    if let Some(table) = custom_table.get(&ident.to_string()) {
-                        create_search_id(table, ident, alias, || quote! {
-                            let #var_name = filtrum::NumberFilters::from_id_value(search_id, s)?;
-                        })
-                    } else {
-                        quote! {
-                            let #var_name = filtrum::NumberFilters::from_str(stringify!(#var_name), s)?;
-                        }
-                    }
+    let fields_as_filters = fields.iter().map(|f| {
+            let (ident, alias) = f.ident_and_alias();
+            let var_name = format_ident!("{}", ident);
+            let path = f.path();
 
+            // a `SearchFilter` matches the columns listed in its `search` attribute
+            let on_columns = match f {
+                FilterType::Search(..) => {
+                    let columns = &search_columns[&ident.to_string()];
+                    quote!(.on_columns(&[#(#columns),*]))
                 }
-                FilterType::String(ident, alias) => {
-                    let var_name = format_ident!("{}", ident);
-
-                    if let Some(table) = custom_table.get(&ident.to_string()) {
-                        create_search_id(table, ident, alias, || quote! {
-                            let #var_name = filtrum::StringFilters::from_id_value(search_id, s)?;
-                        })
-                    } else {
-                        quote! {
-                            let #var_name = filtrum::StringFilters::from_str(stringify!(#var_name), s)?;
-                        }
-                    }
+                _ => quote!(),
+            };
+
+            let parsed = if let Some(table) = custom_table.get(&ident.to_string()) {
+                create_search_id(table, ident, alias, || quote! {
+                    let #var_name = #path::from_id_value_with(search_id, s, parse_options)?#on_columns;
+                })
+            } else {
+                quote! {
+                    let #var_name = #path::from_id_value_with(stringify!(#var_name).to_string().into(), s, parse_options)?#on_columns;
                 }
-                FilterType::None(ident, alias) => {
-                    let var_name = format_ident!("{}", ident);
-
-                    if let Some(table) = custom_table.get(&ident.to_string()) {
-                        create_search_id(table, ident, alias, || quote! {
-                            let #var_name = filtrum::EqualFilter::from_id_value(search_id, s)?;
-                        })
-                    } else {
-                        quote! {
-                            let #var_name = filtrum::EqualFilter::from_str(stringify!(#var_name), s)?;
-                        }
-                    }
+            };
+
+            // a field the client left out is parsed from the pair `(field, <default>)`
+            // instead, so the default is taken as it is, even with an `&` or `=`; one it
+            // cleared (`field=`) is still there, so it stays empty.
+            let parsed = if let Some(default) = default_values.get(&ident.to_string()) {
+                quote! {
+                    let #ident = {
+                        let default_pair = [(
+                            ::std::borrow::Cow::Borrowed(stringify!(#ident)),
+                            ::std::borrow::Cow::Borrowed(#default),
+                        )];
+                        let s = if filtrum::common::has_field(s, stringify!(#ident)) {
+                            s
+                        } else {
+                            filtrum::QueryParams::Pairs(&default_pair)
+                        };
+
+                        #parsed
+                        #ident
+                    };
                 }
+            } else {
+                parsed
             };
 
-            Some(f)
+            if optional_fields.contains(&ident.to_string()) {
+                quote! {
+                    #parsed
+                    let #ident = if #ident.is_empty() { None } else { Some(#ident) };
+                }
+            } else {
+                parsed
+            }
         })
         .collect::<Vec<_>>();
 
-    let impl_with_filter_id = if let Some(table) = data.table {
+    let impl_with_filter_id = if let Some(table) = &data.table {
         quote! {
             Some(#table)
         }
@@ -201,41 +588,300 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
         quote! { None }
     };
 
+    let impl_default_tiebreak = if let Some(tiebreak) = &data.tiebreak {
+        quote! {
+            Some(#tiebreak)
+        }
+    } else {
+        quote! { None }
+    };
+
+    // `order_by = "-created_at"` uses the query shorthand: a leading `-` is descending
+    let impl_default_order_by = match data.order_by.as_deref() {
+        Some(order_by) => {
+            let (column, desc) = match order_by.strip_prefix('-') {
+                Some(column) => (column, true),
+                None => (order_by, false),
+            };
+
+            if column.is_empty() {
+                return Err(darling::Error::custom("`order_by` needs a column name"));
+            }
+
+            if desc {
+                quote! { Some(filtrum::OrderBy::Desc(#column.to_string().into())) }
+            } else {
+                quote! { Some(filtrum::OrderBy::Asc(#column.to_string().into())) }
+            }
+        }
+        None => quote! { None },
+    };
+
     let field_names = fields
         .iter()
+        .map(|f| {
+            let ident = f.ident_and_alias().0;
+            quote! { #ident }
+        })
+        .collect::<Vec<_>>();
+
+    let mut defaults = default_values.iter().collect::<Vec<_>>();
+    defaults.sort();
+    let (default_fields, default_field_values): (Vec<_>, Vec<_>) = defaults.into_iter().unzip();
+
+    // a client sort on a field with a table renders its column the way its filters do,
+    // with the field's own table and its `alias`
+    let sort_columns = fields.iter().filter_map(|f| {
+        let (ident, alias) = f.ident_and_alias();
+        let table = custom_table.get(&ident.to_string())?;
+
+        let id = match alias {
+            Some(alias) => quote! {
+                filtrum::FilterId::WithPrefixAndAlias(#table.to_string(), stringify!(#ident).to_string(), #alias.to_string())
+            },
+            None => quote! {
+                filtrum::FilterId::WithPrefix(#table.to_string(), stringify!(#ident).to_string())
+            },
+        };
+
+        Some(quote! { stringify!(#ident) => Some(#id), })
+    });
+
+    // the operators are parsed once, as an `Op` can't be built in a `static`
+    let allowed_ops_arms = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
         .filter_map(|f| {
-            let r = match f {
-                FilterType::Number(f, _) => {
-                    quote! {
-                        #f
-                    }
-                }
-                FilterType::String(f, _) => {
-                    quote! {
-                        #f
-                    }
+            let ident = f.ident.as_ref()?;
+            let ops = allowed_ops(f.allowed_ops.as_deref()?);
+
+            Some(quote! {
+                stringify!(#ident) => {
+                    static OPS: std::sync::LazyLock<Vec<filtrum::Op>> =
+                        std::sync::LazyLock::new(|| vec![#(filtrum::Op::from(#ops)),*]);
+                    Some(OPS.as_slice())
                 }
-                FilterType::None(f, _) => {
-                    quote! {
-                        #f
+            })
+        });
+
+    let getters = if data.getters {
+        data.data
+            .as_ref()
+            .take_struct()
+            .unwrap()
+            .fields
+            .into_iter()
+            .filter(|f| !f.skip)
+            .filter_map(|f| {
+                let ident = f.ident.as_ref()?;
+                let ty = &f.ty;
+
+                Some(quote! {
+                    pub fn #ident(&self) -> &#ty {
+                        &self.#ident
                     }
-                }
+                })
+            })
+            .collect::<Vec<_>>()
+    } else {
+        Vec::new()
+    };
+
+    let impl_getters = if getters.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            #[automatically_derived]
+            impl #name {
+                #(#getters)*
+            }
+        }
+    };
+
+    // every field type is one of the filters, or an `Option` of one, which all list
+    // their operators
+    let (schema_idents, schema_tys): (Vec<_>, Vec<_>) = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| Some((f.ident.as_ref()?, &f.ty)))
+        .unzip();
+
+    // each field is parsed with the caller's options, checked against the operators
+    // `allowed_ops` lists
+    let parse_options = if fields_as_filters.is_empty() {
+        quote! { let _ = (s, options); }
+    } else {
+        quote! {
+            let parse_options = &filtrum::ParseOptions {
+                allowed_ops: filtrum::AllowedOps::of::<Self>(),
+                ..options.clone()
             };
+        }
+    };
 
-            Some(r)
-        })
-        .collect::<Vec<_>>();
+    let all_fields = skipped_fields.iter().chain(field_names.iter());
 
     let impl_into_cond = quote! {
         impl filtrum::WithFilterId for #name {
             fn filter_id() -> Option<&'static str> {
                 #impl_with_filter_id
             }
+
+            fn default_tiebreak() -> Option<&'static str> {
+                #impl_default_tiebreak
+            }
+
+            fn default_order_by() -> Option<filtrum::OrderBy> {
+                #impl_default_order_by
+            }
+
+            fn sortable_columns() -> Option<&'static [&'static str]> {
+                Some(&[#(stringify!(#field_names)),*])
+            }
+
+            fn default_values() -> &'static [(&'static str, &'static str)] {
+                &[#((#default_fields, #default_field_values)),*]
+            }
+
+            fn sort_column(field: &str) -> Option<filtrum::FilterId> {
+                match field {
+                    #(#sort_columns)*
+                    _ => None,
+                }
+            }
+
+            fn allowed_ops(field: &str) -> Option<&'static [filtrum::Op]> {
+                match field {
+                    #(#allowed_ops_arms)*
+                    _ => None,
+                }
+            }
+
+            fn filter_schema() -> Vec<filtrum::FieldSchema> {
+                vec![#(filtrum::FieldSchema::of::<#schema_tys>(stringify!(#schema_idents))),*]
+            }
+
+            fn from_str_with<'a>(
+                s: impl Into<filtrum::QueryParams<'a>>,
+                options: &filtrum::ParseOptions,
+            ) -> Result<Self, filtrum::FilterParseError> {
+                let s: filtrum::QueryParams<'a> = s.into();
+                #parse_options
+                #(#fields_as_filters)*
+                Ok(Self {
+                    #(#all_fields),*
+                })
+            }
         }
 
     };
 
-    let all_fields = skipped_fields.iter().chain(field_names.iter());
+    // every field type is one of the filters, or an `Option` of one, which all merge
+    let impl_merge = quote! {
+        #[automatically_derived]
+        impl filtrum::Merge for #name {
+            fn merge(&mut self, other: Self) {
+                #(filtrum::Merge::merge(&mut self.#field_names, other.#field_names);)*
+            }
+        }
+    };
+
+    let impl_clear_field = quote! {
+        #[automatically_derived]
+        impl filtrum::ClearField for #name {
+            fn clear_field(&mut self, field: &str) -> bool {
+                match field {
+                    #(stringify!(#field_names) => self.#field_names = Default::default(),)*
+                    _ => return false,
+                }
+
+                true
+            }
+        }
+    };
+
+    // each call is spanned to its field's type, so a field that isn't a `Filter` fails
+    // to compile right there
+    let filter_fields = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            Some(quote_spanned! {f.ty.span()=>
+                (stringify!(#ident), filtrum::Filter::conditions(&self.#ident))
+            })
+        });
+
+    let filter_shapes = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            Some(quote_spanned! {f.ty.span()=>
+                (stringify!(#ident), filtrum::Filter::condition_shapes(&self.#ident))
+            })
+        });
+
+    let impl_filter_fields = quote! {
+        #[automatically_derived]
+        impl filtrum::FilterFields for #name {
+            fn filter_fields(&self) -> Vec<(&'static str, Vec<(filtrum::Op, filtrum::FilterValue)>)> {
+                vec![#(#filter_fields),*]
+            }
+
+            fn filter_shapes(&self) -> Vec<(&'static str, Vec<String>)> {
+                vec![#(#filter_shapes),*]
+            }
+        }
+    };
+
+    let visit_fields = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            Some(quote_spanned! {f.ty.span()=>
+                filtrum::VisitConditions::visit_conditions(&self.#ident, visit);
+            })
+        });
+
+    let impl_visit_conditions = quote! {
+        #[automatically_derived]
+        impl filtrum::VisitConditions for #name {
+            fn visit_conditions(&self, visit: &mut dyn FnMut(String, filtrum::Op, filtrum::FilterValue)) {
+                #(#visit_fields)*
+            }
+        }
+    };
+
+    let impl_sqlx_filter = expand_sqlx_filter(name, &data);
+
+    let impl_sea_query_filter = expand_sea_query_filter(name, &data);
 
     Ok(quote! {
         #[automatically_derived]
@@ -243,22 +889,172 @@ fn expand_from_query_filter(input: &DeriveInput) -> syn::Result<proc_macro2::Tok
             type Err = filtrum::FilterParseError;
 
             fn from_str(s: &str) -> Result<Self, Self::Err> {
-                #(#fields_as_filters)*
-                Ok(Self {
-                    #(#all_fields),*
-                })
+                <Self as filtrum::WithFilterId>::from_str_with(s, &Default::default())
             }
         }
 
         #impl_into_cond
+
+        #impl_merge
+
+        #impl_clear_field
+
+        #impl_filter_fields
+
+        #impl_visit_conditions
+
+        #impl_getters
+
+        #impl_sqlx_filter
+
+        #impl_sea_query_filter
     })
 }
 
+/// Generates a `SqlxFilter` impl that applies every non-skipped field in declaration
+/// order, joined with the conjunction from the options. Each field type is bounded in
+/// the where clause, so the impl only exists for the databases all of them support.
+#[cfg(feature = "sqlx")]
+fn expand_sqlx_filter(name: &Ident, data: &MacroArgs) -> proc_macro2::TokenStream {
+    let fields = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| Some((f.ident.as_ref()?, &f.ty)))
+        .collect::<Vec<_>>();
+
+    let idents = fields.iter().map(|(ident, _)| ident);
+    let tys = fields.iter().map(|(_, ty)| ty);
+    let count = fields.len();
+
+    // `transform` attributes fill in the fields the options don't already transform
+    let transforms = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            let variant = transform_variant(f.transform.as_deref()?)?;
+
+            Some(quote! {
+                if options.transforms.get(stringify!(#ident)).is_none() {
+                    transforms = transforms.with(stringify!(#ident), filtrum::sqlx::Transform::#variant);
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    let with_transforms = if transforms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            let mut transforms = options.transforms.clone();
+            #(#transforms)*
+            let options = &filtrum::sqlx::ApplyOptions {
+                transforms,
+                ..options.clone()
+            };
+        }
+    };
+
+    // the fields `options.fields` allows, with the options their transforms apply to
+    let filters = quote! {
+        #with_transforms
+        let filters: [(&str, &dyn filtrum::sqlx::SqlxFilter<DB>); #count] =
+            [#((stringify!(#idents), &self.#idents)),*];
+        let filters = filters
+            .into_iter()
+            .filter(|(field, _)| options.fields.allows(field))
+            .map(|(_, filter)| filter)
+            .collect::<Vec<_>>();
+    };
+
+    quote! {
+        #[automatically_derived]
+        impl<DB: filtrum::sqlx::Database> filtrum::sqlx::SqlxFilter<DB> for #name
+        where
+            #(#tys: filtrum::sqlx::SqlxFilter<DB>,)*
+        {
+            fn apply<'a>(&self, qb: &mut filtrum::sqlx::QueryBuilder<'a, DB>) {
+                self.apply_with(qb, &filtrum::sqlx::ApplyOptions::default());
+            }
+
+            fn apply_with<'a>(
+                &self,
+                qb: &mut filtrum::sqlx::QueryBuilder<'a, DB>,
+                options: &filtrum::sqlx::ApplyOptions,
+            ) {
+                #filters
+                filtrum::sqlx::apply_all(qb, &filters, options);
+            }
+
+            fn push_group<'a>(
+                &self,
+                qb: &mut filtrum::sqlx::QueryBuilder<'a, DB>,
+                separator: &str,
+                options: &filtrum::sqlx::ApplyOptions,
+            ) -> bool {
+                #filters
+                filtrum::sqlx::push_group_all(qb, &filters, separator, options)
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "sqlx"))]
+fn expand_sqlx_filter(_name: &Ident, _data: &MacroArgs) -> proc_macro2::TokenStream {
+    quote! {}
+}
+
+/// Generates a `SeaQueryFilter` impl that ANDs the conditions of every non-skipped
+/// field. A field type without the trait fails to compile at that field.
+#[cfg(feature = "sea-query")]
+fn expand_sea_query_filter(name: &Ident, data: &MacroArgs) -> proc_macro2::TokenStream {
+    let filters = data
+        .data
+        .as_ref()
+        .take_struct()
+        .unwrap()
+        .fields
+        .into_iter()
+        .filter(|f| !f.skip)
+        .filter_map(|f| {
+            let ident = f.ident.as_ref()?;
+            Some(quote_spanned! {f.ty.span()=>
+                &self.#ident as &dyn filtrum::sea_query::SeaQueryFilter
+            })
+        });
+
+    quote! {
+        #[automatically_derived]
+        impl filtrum::sea_query::SeaQueryFilter for #name {
+            fn condition(&self) -> filtrum::sea_query::Condition {
+                filtrum::sea_query::all_of(&[#(#filters),*])
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "sea-query"))]
+fn expand_sea_query_filter(_name: &Ident, _data: &MacroArgs) -> proc_macro2::TokenStream {
+    quote! {}
+}
+
 #[proc_macro_derive(Filterable, attributes(filtrum))]
 pub fn derive_filterable(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    let toks = expand_from_query_filter(&input).unwrap_or_else(|e| e.to_compile_error());
+    // darling errors carry the span of the offending attribute, and `write_errors`
+    // reports all of them at once instead of just the first.
+    let toks = expand_from_query_filter(&input).unwrap_or_else(|e| e.write_errors());
 
     toks.into()
 }
@@ -0,0 +1,219 @@
+use std::str::FromStr;
+
+use crate::{
+    common::{from_str_with, split_list, FromParam, QueryParams},
+    errors::{BoxError, FilterParseError},
+    filter::{visit_filter, Filter, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    in_filter::ListOperand,
+    op::Op,
+    options::ParseOptions,
+};
+
+/// A containment condition on an array column.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArrayFilter<T> {
+    /// The array holds every one of the values (`column @> ?` on Postgres). Query param:
+    /// `field[contains]=a,b`, `field[array_contains]=a,b` or `field=a,b` (inferred).
+    Contains(Vec<T>),
+}
+
+impl<T> ArrayFilter<T> {
+    /// Returns the operator of this filter, e.g. [`Op::Contains`].
+    pub fn operator(&self) -> Op {
+        match self {
+            ArrayFilter::Contains(_) => Op::Contains,
+        }
+    }
+
+    /// Returns the values of the filter.
+    pub fn values(&self) -> &[T] {
+        match self {
+            ArrayFilter::Contains(v) => v,
+        }
+    }
+}
+
+impl<T> FromParam<ListOperand<T>> for ArrayFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        let values = split_list(value)
+            .iter()
+            .map(|x| {
+                if x.is_empty() {
+                    return Err(FilterParseError::Value(None));
+                }
+
+                x.parse().map_err(FilterParseError::value)
+            })
+            .collect::<Result<_, _>>()?;
+
+        match op {
+            Op::Eq | Op::Contains | Op::ArrayContains => Ok(ArrayFilter::Contains(values)),
+            _ => Err(FilterParseError::UnknownFilter),
+        }
+    }
+}
+
+/// A collection of containment filters applied to an array column, e.g. a Postgres
+/// `text[]` of tags.
+///
+/// Unlike the `contains` of [`StringFilters`](crate::StringFilters), which is a `LIKE`
+/// on text, `tags[contains]=rust,web` matches rows whose array holds both values. With
+/// SQLx it renders `tags @> ?` on Postgres only, binding the values as one array.
+///
+/// Only array columns are matched: the values are bound as a Postgres array, which a
+/// JSONB column can't be compared with. Filter a JSONB column with a hand-written
+/// condition instead, e.g. `data @> CAST(? AS jsonb)` through
+/// `FromQueryFilter::and_raw`.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::array_filter::{ArrayFilter, ArrayFilters};
+///
+/// let tags = ArrayFilters::<String>::from_str("tags", "tags[contains]=rust,web").unwrap();
+///
+/// assert_eq!(
+///     tags.0,
+///     vec![ArrayFilter::Contains(vec!["rust".to_string(), "web".to_string()])]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct ArrayFilters<T>(pub Vec<ArrayFilter<T>>, pub Option<FilterId>);
+
+impl<T> ArrayFilters<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    /// Parses containment filters from a query string for a specific search ID.
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    /// Parses containment filters from a query string for a specific `FilterId`.
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Parses containment filters from a query string for a specific `FilterId`, using
+    /// `options`.
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        from_str_with::<ListOperand<T>, _>(search_id.id(), value, options)
+            .map(|x| Self(x, Some(search_id)))
+    }
+}
+
+impl<T> ArrayFilters<T> {
+    /// Returns `true` if no filters were parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the `FilterId` the filters apply to.
+    pub fn with_id(self, search_id: FilterId) -> Self {
+        Self(self.0, Some(search_id))
+    }
+}
+
+impl<T> Filter for ArrayFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        ArrayFilters::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.0
+            .iter()
+            .map(|x| {
+                let values = x.values().iter().map(|x| x.to_filter_value()).collect();
+                (x.operator(), FilterValue::List(values))
+            })
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for ArrayFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
+
+impl<T> Merge for ArrayFilters<T> {
+    fn merge(&mut self, other: Self) {
+        self.0
+            .retain(|x| !other.0.iter().any(|y| y.operator() == x.operator()));
+        self.0.extend(other.0);
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_array_filter_parsing() {
+        let f = ArrayFilters::<String>::from_str("tags", "tags[contains]=rust,web&x=1").unwrap();
+        assert_eq!(
+            f.0,
+            vec![ArrayFilter::Contains(vec![
+                "rust".to_string(),
+                "web".to_string()
+            ])]
+        );
+
+        let f = ArrayFilters::<i32>::from_str("ids", "ids=7&ids[ARRAY_CONTAINS]=8,9").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                ArrayFilter::Contains(vec![7]),
+                ArrayFilter::Contains(vec![8, 9])
+            ]
+        );
+        assert_eq!(
+            f.conditions()[0],
+            (Op::Contains, FilterValue::List(vec![FilterValue::Int(7)]))
+        );
+
+        assert!(matches!(
+            ArrayFilters::<i32>::from_str("ids", "ids[contains]=1,x"),
+            Err(FilterParseError::Value(Some(_)))
+        ));
+        assert!(matches!(
+            ArrayFilters::<i32>::from_str("ids", "ids[like]=1"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+}
@@ -1,18 +1,124 @@
-use std::str::FromStr;
+use std::{
+    borrow::Cow,
+    convert::Infallible,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    str::FromStr,
+};
 
 use axum::{
-    extract::FromRequestParts,
-    http::{request::Parts, StatusCode},
+    body::Bytes,
+    extract::{rejection::BytesRejection, FromRequest, FromRequestParts, RawQuery, Request},
+    http::{header, request::Parts, Extensions, StatusCode},
     response::{IntoResponse, Response},
 };
 
-use crate::{common::WithFilterId, errors::FilterParseError, query_filter::FromQueryFilter};
+use crate::{
+    common::{decode_query, QueryParams, WithFilterId},
+    errors::FilterParseError,
+    limit::Limit,
+    options::ParseOptions,
+    query_filter::FromQueryFilter,
+};
 
 pub struct FilterRejection(pub FilterParseError);
 
 impl IntoResponse for FilterRejection {
     fn into_response(self) -> Response {
-        (StatusCode::BAD_REQUEST, self.0.to_string()).into_response()
+        (StatusCode::from(&self.0), self.0.to_string()).into_response()
+    }
+}
+
+/// The status the extractors reject with, for handlers that parse filters themselves:
+/// `415 Unsupported Media Type` for a form body of the wrong type, the status of axum's
+/// rejection for a body that couldn't be read, and `400 Bad Request` for everything wrong
+/// with the query itself.
+///
+/// ```rust,ignore
+/// let filter = FromQueryFilter::<UserFilter>::from_str(query)
+///     .map_err(|e| (StatusCode::from(&e), e.to_string()))?;
+/// ```
+impl From<&FilterParseError> for StatusCode {
+    fn from(err: &FilterParseError) -> Self {
+        match err {
+            FilterParseError::ContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            FilterParseError::Body(err) => err
+                .downcast_ref::<BytesRejection>()
+                .map_or(StatusCode::BAD_REQUEST, BytesRejection::status),
+            FilterParseError::FilterStructure
+            | FilterParseError::Value(_)
+            | FilterParseError::UnknownFilter
+            | FilterParseError::Duplicate
+            | FilterParseError::UnknownField(_)
+            | FilterParseError::InvalidCursor
+            | FilterParseError::SkipTooLarge(_) => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// The parsing and pagination policy shared by every filter extractor of an app: how
+/// queries are parsed, including the names of the pagination parameters, and the
+/// default and largest `limit`.
+///
+/// Added to the router as an `Extension`, it applies to `FromQueryFilter<T>`,
+/// [`Lenient`] and [`FilterForm`] whatever the router's state. Without one they parse
+/// with the defaults.
+///
+/// ```rust,ignore
+/// let config = FilterConfig {
+///     options: ParseOptions {
+///         reserved: ReservedParams { limit: "size", skip: "offset", ..Default::default() },
+///         ..Default::default()
+///     },
+///     default_limit: Some(20),
+///     max_limit: Some(100),
+/// };
+///
+/// let app = Router::new()
+///     .route("/users", get(list_users))
+///     .layer(Extension(config));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FilterConfig {
+    /// The options every query is parsed with: the sort and pagination parameters, and
+    /// the inner filter through [`WithFilterId::from_str_with`], which a derived filter
+    /// parses every field with, aliases and empty values included.
+    pub options: ParseOptions,
+    /// The limit of a query without one, or `None` to leave it unset.
+    pub default_limit: Option<u64>,
+    /// The largest limit; a larger one is lowered to it. `None` for no maximum.
+    pub max_limit: Option<u64>,
+}
+
+impl FilterConfig {
+    /// Parses `query` with [`FilterConfig::options`], then fills in the default limit
+    /// and lowers it to the maximum.
+    pub fn parse<'a, T>(
+        &self,
+        query: impl Into<QueryParams<'a>>,
+    ) -> Result<FromQueryFilter<T>, FilterParseError>
+    where
+        T: FromStr<Err = FilterParseError> + WithFilterId + Default,
+    {
+        let filter = FromQueryFilter::from_str_with(query, &self.options)?;
+
+        Ok(FromQueryFilter {
+            limit: self.limit(filter.limit),
+            ..filter
+        })
+    }
+
+    /// Returns `limit`, or the default one, lowered to the maximum.
+    fn limit(&self, limit: Option<Limit>) -> Option<Limit> {
+        let limit = limit.map(|x| x.0).or(self.default_limit)?;
+
+        Some(Limit(self.max_limit.map_or(limit, |max| limit.min(max))))
+    }
+
+    fn of(extensions: &Extensions) -> Cow<'_, Self> {
+        extensions
+            .get::<Self>()
+            .map_or_else(|| Cow::Owned(Self::default()), Cow::Borrowed)
     }
 }
 
@@ -24,7 +130,276 @@ where
     type Rejection = FilterRejection;
 
     async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
-        let query = parts.uri.query().unwrap_or("");
-        Self::from_str(query).map_err(FilterRejection)
+        // the query is percent-encoded in the URI, e.g. `%5C` for the escape of a bracket
+        let query = parts.uri.query();
+        let config = FilterConfig::of(&parts.extensions);
+        let filter = decode_query(query.unwrap_or(""), &config.options)
+            .and_then(|pairs| config.parse(&pairs))
+            .map_err(FilterRejection)?;
+
+        Ok(Self {
+            raw: query.map(str::to_string),
+            ..filter
+        })
+    }
+}
+
+impl<T> FromQueryFilter<T>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId + Default,
+{
+    /// Parses a query already extracted with `RawQuery`, the same way the
+    /// `FromQueryFilter` extractor does with `config`: the query is percent-decoded, and
+    /// a request without one gives the defaults, with the default limit if any.
+    ///
+    /// ```rust,ignore
+    /// async fn handler(
+    ///     Extension(config): Extension<FilterConfig>,
+    ///     query: RawQuery,
+    /// ) -> Result<String, FilterRejection> {
+    ///     let filter = FromQueryFilter::<UserFilter>::from_raw_query(query, &config)
+    ///         .map_err(FilterRejection)?;
+    ///     ...
+    /// }
+    /// ```
+    pub fn from_raw_query(
+        RawQuery(query): RawQuery,
+        config: &FilterConfig,
+    ) -> Result<Self, FilterParseError> {
+        let filter = decode_query(query.as_deref().unwrap_or(""), &config.options)
+            .and_then(|pairs| config.parse(&pairs))?;
+
+        Ok(Self {
+            raw: query,
+            ..filter
+        })
+    }
+}
+
+/// Like [`FromQueryFilter::from_raw_query`], with the default [`FilterConfig`]; use that
+/// to apply an app's config.
+///
+/// ```rust,ignore
+/// async fn handler(query: RawQuery) -> Result<String, FilterRejection> {
+///     let filter = FromQueryFilter::<UserFilter>::try_from(query).map_err(FilterRejection)?;
+///     ...
+/// }
+/// ```
+impl<T> TryFrom<RawQuery> for FromQueryFilter<T>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId + Default,
+{
+    type Error = FilterParseError;
+
+    fn try_from(query: RawQuery) -> Result<Self, Self::Error> {
+        Self::from_raw_query(query, &FilterConfig::default())
+    }
+}
+
+/// An extractor that never rejects: a query that fails to parse yields
+/// [`FromQueryFilter::empty`], with the default limit of the [`FilterConfig`] if any,
+/// instead of a `400 Bad Request`.
+///
+/// Use it in place of the strict `FromQueryFilter<T>` extractor on endpoints that
+/// should ignore malformed filters. With the `tracing` feature, the parse error is
+/// logged at debug level.
+///
+/// ```rust,ignore
+/// async fn handler(Lenient(filter): Lenient<FromQueryFilter<UserFilter>>) { ... }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Lenient<T>(pub T);
+
+impl<T> Deref for Lenient<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Lenient<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequestParts<S> for Lenient<FromQueryFilter<T>>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId + Default + Send + Sync,
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let query = parts.uri.query();
+
+        let config = FilterConfig::of(&parts.extensions);
+        let filter = decode_query(query.unwrap_or(""), &config.options)
+            .and_then(|pairs| config.parse(&pairs))
+            .unwrap_or_else(|_err| {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(error = %_err, query, "ignoring malformed filter query");
+
+                // an ignored query still gets the default limit
+                FromQueryFilter {
+                    limit: config.limit(None),
+                    ..FromQueryFilter::empty()
+                }
+            });
+
+        // the raw query is kept even when it was ignored, so it can still be logged
+        Ok(Lenient(FromQueryFilter {
+            raw: query.map(str::to_string),
+            ..filter
+        }))
+    }
+}
+
+/// An extractor that reads the filters from an `application/x-www-form-urlencoded`
+/// request body, e.g. an HTML form posting its filters, instead of the query string.
+///
+/// The body is decoded as a browser encodes it, with [`decode_query`], so
+/// `age%5Bgte%5D=18&name=Tom+%26+Jerry` reads `age[gte]` as `18` and `name` as
+/// `Tom & Jerry`, then parsed like a query string, with the [`FilterConfig`] of the
+/// request if it has one. The body is kept, as posted, in `raw`. Any other
+/// `Content-Type` is rejected with `415 Unsupported Media Type`, a body that can't be read
+/// with [`FilterParseError::Body`], and a body that fails to parse with
+/// `400 Bad Request`.
+///
+/// ```rust,ignore
+/// async fn search(FilterForm(filter): FilterForm<FromQueryFilter<UserFilter>>) { ... }
+///
+/// let app = Router::new().route("/search", post(search));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FilterForm<T>(pub T);
+
+impl<T> Deref for FilterForm<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for FilterForm<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, S> FromRequest<S> for FilterForm<FromQueryFilter<T>>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId + Default + Send + Sync,
+    S: Send + Sync,
+{
+    type Rejection = FilterRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        // parameters like `; charset=utf-8` are allowed after the media type
+        let is_form = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.split(';').next())
+            .is_some_and(|x| {
+                x.trim()
+                    .eq_ignore_ascii_case("application/x-www-form-urlencoded")
+            });
+
+        if !is_form {
+            return Err(FilterRejection(FilterParseError::ContentType));
+        }
+
+        let config = FilterConfig::of(req.extensions()).into_owned();
+
+        let body = Bytes::from_request(req, state)
+            .await
+            .map_err(|err| FilterRejection(FilterParseError::Body(err.into())))?;
+        let body = std::str::from_utf8(&body)
+            .map_err(|err| FilterRejection(FilterParseError::Body(err.into())))?;
+
+        let filter = decode_query(body, &config.options)
+            .and_then(|pairs| config.parse(&pairs))
+            .map_err(FilterRejection)?;
+
+        Ok(FilterForm(FromQueryFilter {
+            raw: Some(body.to_string()),
+            ..filter
+        }))
+    }
+}
+
+/// Wraps a filter extractor to reject with the app's own error type `E` instead of
+/// [`FilterRejection`], so a malformed filter gets the same response shape as every
+/// other error of the app.
+///
+/// `E` is built from the [`FilterParseError`] with `From`. Works with
+/// `FromQueryFilter<T>` and [`FilterForm`].
+///
+/// ```rust,ignore
+/// impl From<FilterParseError> for AppError { ... }
+/// impl IntoResponse for AppError { ... }
+///
+/// async fn handler(
+///     WithRejection(filter, _): WithRejection<FromQueryFilter<UserFilter>, AppError>,
+/// ) { ... }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WithRejection<T, E>(pub T, pub PhantomData<E>);
+
+impl<T, E> WithRejection<T, E> {
+    /// Returns the wrapped extractor.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T, E> Deref for WithRejection<T, E> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T, E> DerefMut for WithRejection<T, E> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T, E, S> FromRequestParts<S> for WithRejection<T, E>
+where
+    T: FromRequestParts<S, Rejection = FilterRejection>,
+    E: From<FilterParseError> + IntoResponse,
+    S: Send + Sync,
+{
+    type Rejection = E;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let inner = T::from_request_parts(parts, state)
+            .await
+            .map_err(|x| E::from(x.0))?;
+
+        Ok(WithRejection(inner, PhantomData))
+    }
+}
+
+impl<T, E, S> FromRequest<S> for WithRejection<T, E>
+where
+    T: FromRequest<S, Rejection = FilterRejection>,
+    E: From<FilterParseError> + IntoResponse,
+    S: Send + Sync,
+{
+    type Rejection = E;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let inner = T::from_request(req, state)
+            .await
+            .map_err(|x| E::from(x.0))?;
+
+        Ok(WithRejection(inner, PhantomData))
     }
 }
@@ -1,51 +1,823 @@
-use std::str::FromStr;
+use std::{borrow::Cow, collections::HashSet, str::FromStr};
 
-use crate::{errors::FilterParseError, regex::query_regex};
+use crate::{
+    errors::{BoxError, FilterParseError},
+    filter_id::FilterId,
+    op::Op,
+    options::ParseOptions,
+    order_by::OrderBy,
+    regex::query_regex,
+    schema::FieldSchema,
+};
 
 pub trait FromStrFilter<T>: Sized {
-    fn from_str(filter_key: &str, value: T) -> Result<Self, FilterParseError>;
+    /// Builds a filter from the operator of a parameter and its parsed value, or returns
+    /// `FilterParseError::UnknownFilter` if `op` isn't supported.
+    fn from_str(op: &Op, value: T) -> Result<Self, FilterParseError>;
 }
 
-pub fn from_str<V, T>(search_id: &str, value: &str) -> Result<Vec<T>, FilterParseError>
+/// Builds a filter from the operator of a parameter and its raw value, read as a `V`.
+///
+/// Every [`FromStrFilter`] implements it for its `FromStr` value, parsing the value
+/// first. The crate's own filters also implement it for operand types that read the
+/// value straight from the query, without copying it.
+pub trait FromParam<V>: Sized {
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError>;
+}
+
+impl<V, F> FromParam<V> for F
 where
-    T: FromStrFilter<V>,
     V: FromStr,
+    V::Err: Into<BoxError>,
+    F: FromStrFilter<V>,
 {
-    if value.is_empty() {
-        return Ok(Vec::new());
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        let value = value.parse().map_err(FilterParseError::value)?;
+        F::from_str(op, value)
     }
+}
+
+pub fn from_str<V, T>(search_id: &str, value: &str) -> Result<Vec<T>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    from_str_with(search_id, value, &ParseOptions::default())
+}
+
+/// Parses every filter for `search_id` out of `value`, a query string or a query already
+/// split into pairs (see [`QueryParams`]).
+///
+/// Empty parameters (e.g. from a trailing `&`) are ignored, and so are parameters with
+/// an empty value (`age[eq]=`) unless `options.keep_empty_values` is set.
+///
+/// A bare `age=30` is read as `age[eq]=30`, and every parameter is kept in query order:
+/// `name=Bob&name[ne]=Alice`, or a repeated `name=Bob&name=Ann`, yields both filters,
+/// which are ANDed. With `options.explicit_eq_overrides_bare`, the bare parameters of a
+/// field are dropped when it also has an explicit `eq`.
+///
+/// A field name with brackets, like `data[json]`, is written with them escaped, as
+/// `data\[json\][ne]=x`, so they aren't read as the operator.
+///
+/// A leading `?`, as in a query string copied from a URL, is ignored.
+pub fn from_str_with<'a, V, T>(
+    search_id: &str,
+    value: impl Into<QueryParams<'a>>,
+    options: &ParseOptions,
+) -> Result<Vec<T>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    let value = value.into();
 
     // age[lte]=10&age[gte]=20&age[eq]=30
     let mut filters = Vec::new();
+    let overridden = overridden_bare(value.keys(), options);
 
-    for part in value.split('&') {
-        let (id_and_filter, value) = part
-            .split_once('=')
-            .ok_or(FilterParseError::FilterStructure)?;
+    for param in value.params(options) {
+        let (id_and_filter, value) = param?;
 
-        let rg = query_regex()
-            .captures(id_and_filter)
-            .ok_or(FilterParseError::FilterStructure)?;
+        if let Some(filter) = parse_param(search_id, id_and_filter, value, &overridden, options)? {
+            filters.push(filter);
+        }
+    }
 
-        let id = rg.get(1).ok_or(FilterParseError::FilterStructure)?.as_str();
+    Ok(filters)
+}
 
-        let filter = rg.get(3).map_or("eq", |x| x.as_str());
+/// Returns `true` if the query has any parameter for `field`, even one with an empty
+/// value.
+///
+/// This tells a field the client left out (`""`) apart from one it cleared
+/// (`active=`), which both parse to an empty filter.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::common::has_field;
+///
+/// assert!(has_field("active=&age[gte]=18", "active"));
+/// assert!(has_field("active=&age[gte]=18", "age"));
+/// assert!(!has_field("active=&age[gte]=18", "name"));
+/// ```
+pub fn has_field<'a>(value: impl Into<QueryParams<'a>>, field: &str) -> bool {
+    value
+        .into()
+        .keys()
+        .any(|key| parse_key(key).is_some_and(|(id, _)| id == field))
+}
 
-        if id != search_id {
-            continue;
-        }
+/// Parses every filter for `field` out of a query that was already split into
+/// `(key, value)` pairs, e.g. `("age[gte]", "18")`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use filtrum::common::parse_field;
+/// use filtrum::number_filter::NumberFilter;
+///
+/// let pairs = [(Cow::from("age[gte]"), Cow::from("18")), (Cow::from("name"), Cow::from("x"))];
+/// let filters = parse_field::<i32, NumberFilter<i32>>(&pairs, "age").unwrap();
+///
+/// assert_eq!(filters, vec![NumberFilter::Gte(18)]);
+/// ```
+pub fn parse_field<V, T>(
+    pairs: &[(Cow<'_, str>, Cow<'_, str>)],
+    field: &str,
+) -> Result<Vec<T>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    parse_field_with(pairs, field, &ParseOptions::default())
+}
 
-        let value = value.parse().map_err(|_| FilterParseError::Value)?;
+/// Like [`parse_field`], using `options`.
+pub fn parse_field_with<V, T>(
+    pairs: &[(Cow<'_, str>, Cow<'_, str>)],
+    field: &str,
+    options: &ParseOptions,
+) -> Result<Vec<T>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    from_str_with(field, pairs, options)
+}
 
-        let filter = T::from_str(filter, value)?;
-        filters.push(filter);
+/// Parses every filter for a field whose name starts with `prefix` out of the query
+/// string `value`, paired with the rest of the name.
+///
+/// A field named just `prefix` is not matched, there must be a name after it.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::common::from_str_prefix;
+/// use filtrum::number_filter::NumberFilter;
+///
+/// let query = "attr_size[gte]=10&attr_weight=3&age=20";
+/// let filters = from_str_prefix::<i32, NumberFilter<i32>>("attr_", query).unwrap();
+///
+/// assert_eq!(
+///     filters,
+///     vec![
+///         ("size".to_string(), NumberFilter::Gte(10)),
+///         ("weight".to_string(), NumberFilter::Eq(3))
+///     ]
+/// );
+/// ```
+pub fn from_str_prefix<V, T>(
+    prefix: &str,
+    value: &str,
+) -> Result<Vec<(String, T)>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    from_str_prefix_with(prefix, value, &ParseOptions::default())
+}
+
+/// Like [`from_str_prefix`], using `options`, for a query string or pairs.
+pub fn from_str_prefix_with<'a, V, T>(
+    prefix: &str,
+    value: impl Into<QueryParams<'a>>,
+    options: &ParseOptions,
+) -> Result<Vec<(String, T)>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    let value = value.into();
+    let mut filters = Vec::new();
+    let overridden = overridden_bare(value.keys(), options);
+
+    for param in value.params(options) {
+        let (id_and_filter, value) = param?;
+
+        let matches = |id: &str| id.strip_prefix(prefix).is_some_and(|x| !x.is_empty());
+        if let Some((id, filter)) =
+            parse_param_if(matches, id_and_filter, value, &overridden, options)?
+        {
+            filters.push((id[prefix.len()..].to_string(), filter));
+        }
     }
 
     Ok(filters)
 }
 
+/// The parameters of a query, as the parsers read them: a query string in the raw
+/// syntax, like `age[gte]=18&name=Bob`, or a query already split into `(key, value)`
+/// pairs, like the ones [`decode_query`] percent-decodes. A value of a pair is taken as
+/// it is, so it may hold an `&` or an `=`.
+///
+/// The parsers taking one also take a `&str` or the pairs, converted with `From`.
+///
+/// # Example
+///
+/// ```rust
+/// use std::borrow::Cow;
+/// use filtrum::common::from_str_with;
+/// use filtrum::options::ParseOptions;
+/// use filtrum::string_filter::StringFilter;
+///
+/// let pairs = vec![(Cow::from("name"), Cow::from("Tom & Jerry"))];
+/// let filters: Vec<StringFilter> =
+///     from_str_with::<String, _>("name", &pairs, &ParseOptions::default()).unwrap();
+///
+/// assert_eq!(filters, vec![StringFilter::Eq("Tom & Jerry".to_string())]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryParams<'a> {
+    /// A query string, e.g. `age[gte]=18&name=Bob`.
+    Str(&'a str),
+    /// A query split into `(key, value)` pairs, e.g. `("age[gte]", "18")`.
+    Pairs(&'a [QueryPair<'a>]),
+}
+
+/// A parameter of a query split into pairs, as its key and its value.
+pub type QueryPair<'a> = (Cow<'a, str>, Cow<'a, str>);
+
+impl<'a> QueryParams<'a> {
+    /// Returns every parameter as its key and its value, or `None` for a part of a query
+    /// string without `=`. Empty parts of a query string are skipped.
+    fn parts(self) -> impl Iterator<Item = (&'a str, Option<&'a str>)> {
+        let (query, pairs) = match self {
+            QueryParams::Str(value) => (Some(strip_query(value)), None),
+            QueryParams::Pairs(pairs) => (None, Some(pairs)),
+        };
+
+        let query = query
+            .into_iter()
+            .flat_map(|x| x.split('&'))
+            .filter(|part| !part.is_empty())
+            .map(|part| match split_param(part) {
+                Some((key, value)) => (key, Some(value)),
+                None => (part, None),
+            });
+        let pairs = pairs
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (key.as_ref(), Some(value.as_ref())));
+
+        query.chain(pairs)
+    }
+
+    /// Returns the keys of the parameters.
+    fn keys(self) -> impl Iterator<Item = &'a str> {
+        self.parts().map(|(key, _)| key)
+    }
+
+    /// Returns every parameter as its key and value, reading a bare flag like `archived`
+    /// as `archived=true` if `options.presence_flags` is set.
+    fn params(
+        self,
+        options: &ParseOptions,
+    ) -> impl Iterator<Item = Result<(&'a str, &'a str), FilterParseError>> {
+        let presence_flags = options.presence_flags;
+
+        self.parts().map(move |(key, value)| match value {
+            Some(value) => Ok((key, value)),
+            None if presence_flags && is_flag(key) => Ok((key, "true")),
+            None => Err(FilterParseError::FilterStructure),
+        })
+    }
+
+    /// Returns the query as a query string, joining the pairs, for a parser that only
+    /// reads one, like a hand-written `FromStr`.
+    ///
+    /// A pair holding an `&`, which a query string has no escape for, is rejected, as is
+    /// a key holding an `=` that would end it.
+    pub fn to_query_string(self) -> Result<Cow<'a, str>, FilterParseError> {
+        let pairs = match self {
+            QueryParams::Str(value) => return Ok(Cow::Borrowed(value)),
+            QueryParams::Pairs(pairs) => pairs,
+        };
+
+        let mut query = String::new();
+
+        for (key, value) in pairs {
+            // an `=` in the brackets of an operator, like `age[>=]`, doesn't end the key
+            if key.contains('&') || split_param(&format!("{key}=")).is_some_and(|x| x.0 != key) {
+                return Err(FilterParseError::FilterStructure);
+            }
+            if value.contains('&') {
+                return Err(FilterParseError::value("a value can't contain `&`"));
+            }
+
+            if !query.is_empty() {
+                query.push('&');
+            }
+            query.push_str(key);
+            query.push('=');
+            query.push_str(value);
+        }
+
+        Ok(Cow::Owned(query))
+    }
+}
+
+impl<'a> From<&'a str> for QueryParams<'a> {
+    fn from(value: &'a str) -> Self {
+        QueryParams::Str(value)
+    }
+}
+
+impl<'a> From<&'a String> for QueryParams<'a> {
+    fn from(value: &'a String) -> Self {
+        QueryParams::Str(value)
+    }
+}
+
+impl<'a> From<&'a [QueryPair<'a>]> for QueryParams<'a> {
+    fn from(pairs: &'a [QueryPair<'a>]) -> Self {
+        QueryParams::Pairs(pairs)
+    }
+}
+
+impl<'a> From<&'a Vec<QueryPair<'a>>> for QueryParams<'a> {
+    fn from(pairs: &'a Vec<QueryPair<'a>>) -> Self {
+        QueryParams::Pairs(pairs)
+    }
+}
+
+/// Parses a single `key=value` parameter, or returns `None` if it is for another field
+/// or its empty value is skipped.
+fn parse_param<V, T>(
+    search_id: &str,
+    id_and_filter: &str,
+    value: &str,
+    overridden: &HashSet<Cow<'_, str>>,
+    options: &ParseOptions,
+) -> Result<Option<T>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    let filter = parse_param_if(
+        |id| id == search_id,
+        id_and_filter,
+        value,
+        overridden,
+        options,
+    )?;
+    Ok(filter.map(|(_, filter)| filter))
+}
+
+/// Like [`parse_param`], for any field `matches` accepts. Returns the field name too.
+///
+/// A bare parameter of a field in `overridden` is skipped, see [`overridden_bare`].
+fn parse_param_if<'a, V, T>(
+    matches: impl Fn(&str) -> bool,
+    id_and_filter: &'a str,
+    value: &str,
+    overridden: &HashSet<Cow<'_, str>>,
+    options: &ParseOptions,
+) -> Result<Option<(Cow<'a, str>, T)>, FilterParseError>
+where
+    T: FromParam<V>,
+{
+    let (id, op) = parse_key(id_and_filter).ok_or(FilterParseError::FilterStructure)?;
+
+    if !matches(&id) || (op.is_none() && overridden.contains(&*id)) {
+        return Ok(None);
+    }
+
+    // operators are ASCII, so `LIKE` and `Gte` are read as `like` and `gte`
+    let op = match op {
+        Some(x) => Op::from(&*options.aliases.resolve(&lowercase(x))),
+        None => Op::Eq,
+    };
+
+    if let Some(allowed) = options.allowed_ops.get(&id)
+        && !allowed.contains(&op)
+    {
+        return Err(FilterParseError::UnknownFilter);
+    }
+
+    if value.is_empty() && !options.keep_empty_values {
+        return Ok(None);
+    }
+
+    match T::from_param(&op, value) {
+        Ok(filter) => Ok(Some((id, filter))),
+        // an empty value is only kept if it parses, e.g. as a `String`
+        Err(FilterParseError::Value(_)) if value.is_empty() => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Lowercases an operator, only copying it if it has an uppercase letter, which most
+/// operators don't.
+pub(crate) fn lowercase(op: &str) -> Cow<'_, str> {
+    match op.bytes().any(|x| x.is_ascii_uppercase()) {
+        true => Cow::Owned(op.to_ascii_lowercase()),
+        false => Cow::Borrowed(op),
+    }
+}
+
+/// Strips the leading `?` of a query string copied from a URL, e.g. `?age=5`.
+pub(crate) fn strip_query(value: &str) -> &str {
+    value.strip_prefix('?').unwrap_or(value)
+}
+
+/// Splits a query string or form body as a browser encodes it, e.g.
+/// `age%5Bgte%5D=18&name=Tom+%26+Jerry`, into its parameters, then percent-decodes each
+/// key and value: `("age[gte]", "18")` and `("name", "Tom & Jerry")`. A `+` is read as a
+/// space, and a `%` that doesn't start an escape is kept as is.
+///
+/// The parsers don't decode a query string on their own, so a raw query can hold a
+/// literal `%`; pass them the pairs instead, as [`QueryParams`]. A bare flag like
+/// `archived` is read as `archived=true` if `options.presence_flags` is set, and rejected
+/// otherwise, as the parsers do.
+pub fn decode_query<'a>(
+    value: &'a str,
+    options: &ParseOptions,
+) -> Result<Vec<QueryPair<'a>>, FilterParseError> {
+    QueryParams::Str(value)
+        .params(options)
+        .map(|param| {
+            let (key, value) = param?;
+            Ok((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect()
+}
+
+/// Percent-decodes one key or value of a query, reading `+` as a space.
+fn percent_decode(value: &str) -> Result<Cow<'_, str>, FilterParseError> {
+    if !value.contains(['%', '+']) {
+        return Ok(Cow::Borrowed(value));
+    }
+
+    let hex = |x: u8| (x as char).to_digit(16).map(|x| x as u8);
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let escape = (bytes[i] == b'%')
+            .then(|| Some(hex(*bytes.get(i + 1)?)? << 4 | hex(*bytes.get(i + 2)?)?))
+            .flatten();
+
+        match (bytes[i], escape) {
+            (_, Some(x)) => {
+                decoded.push(x);
+                i += 3;
+            }
+            (b'+', None) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (x, None) => {
+                decoded.push(x);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8(decoded)
+        .map(Cow::Owned)
+        .map_err(FilterParseError::value)
+}
+
+/// Returns `true` if `part` is a bare flag: a field name without operator or value.
+fn is_flag(part: &str) -> bool {
+    !part.is_empty() && part.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// Rewrites every bare flag in the query string `value`, like `archived`, as
+/// `archived=true`, for parsers that don't take [`ParseOptions`].
+pub(crate) fn expand_flags(value: &str) -> Cow<'_, str> {
+    if !value.split('&').any(is_flag) {
+        return Cow::Borrowed(value);
+    }
+
+    let parts: Vec<_> = value
+        .split('&')
+        .map(|part| match is_flag(part) {
+            true => Cow::Owned(format!("{part}=true")),
+            false => Cow::Borrowed(part),
+        })
+        .collect();
+
+    Cow::Owned(parts.join("&"))
+}
+
+/// Returns the fields whose bare `field=value` parameters are overridden by an explicit
+/// `eq` among `keys`, or none unless `options.explicit_eq_overrides_bare` is set.
+fn overridden_bare<'a>(
+    keys: impl Iterator<Item = &'a str>,
+    options: &ParseOptions,
+) -> HashSet<Cow<'a, str>> {
+    if !options.explicit_eq_overrides_bare {
+        return HashSet::new();
+    }
+
+    keys.filter_map(|key| {
+        let (id, op) = parse_key(key)?;
+        let op = lowercase(op?);
+
+        (Op::from(&*options.aliases.resolve(&op)) == Op::Eq).then_some(id)
+    })
+    .collect()
+}
+
+/// Splits a parameter key like `age[gte]` into its field and its operator, as written.
+///
+/// A bracket that is part of the field is escaped with a backslash, so `data\[json\]`
+/// is the field `data[json]` and `data\[json\][ne]` its `ne` filter. Only `[`, `]` and
+/// `.` can be escaped, and the rest of the field is made of word characters and `.`, so
+/// a key with any other character, like a quote or a lone backslash, isn't parsed.
+fn parse_key(key: &str) -> Option<(Cow<'_, str>, Option<&str>)> {
+    if let Some((id, op)) = parse_plain_key(key) {
+        return Some((Cow::Borrowed(id), op));
+    }
+
+    if !key.contains('\\') {
+        let rg = query_regex().captures(key)?;
+        let id = rg.get(1)?.as_str();
+        return Some((Cow::Borrowed(id), rg.get(3).map(|x| x.as_str())));
+    }
+
+    let mut id = String::new();
+    let mut chars = key.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some((_, c @ ('[' | ']' | '.'))) => id.push(c),
+                _ => return None,
+            },
+            '[' => {
+                // the operator is everything up to the closing bracket, like the regex
+                let op = key[i + 1..].split(']').next().filter(|x| !x.is_empty())?;
+                return (!id.is_empty()).then_some((Cow::Owned(id), Some(op)));
+            }
+            c if c.is_alphanumeric() || c == '_' || c == '.' => id.push(c),
+            _ => return None,
+        }
+    }
+
+    (!id.is_empty()).then_some((Cow::Owned(id), None))
+}
+
+/// Splits the common keys, an ASCII field like `age` or `attrs.color` with at most an
+/// operator like `[gte]`, without running the regex, which allocates. Any other key is
+/// left to [`parse_key`], which reads these the same way.
+fn parse_plain_key(key: &str) -> Option<(&str, Option<&str>)> {
+    let word = |x: &str| {
+        x.find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(x.len())
+    };
+
+    let mut end = word(key);
+    // the attribute of a dynamic field, e.g. `attrs.color`
+    if end > 0
+        && let Some(rest) = key[end..].strip_prefix('.')
+    {
+        match word(rest) {
+            0 => return None,
+            len => end += 1 + len,
+        }
+    }
+    let (id, rest) = key.split_at(end);
+
+    if id.is_empty() {
+        return None;
+    }
+    if rest.is_empty() {
+        return Some((id, None));
+    }
+
+    let op = rest.strip_prefix('[')?.strip_suffix(']')?;
+    (!op.is_empty() && !op.contains(['[', ']'])).then_some((id, Some(op)))
+}
+
+/// Splits a `key=value` parameter at the first `=` outside the brackets of the key, so
+/// an operator like `age[>=]` stays whole.
+pub(crate) fn split_param(part: &str) -> Option<(&str, &str)> {
+    let mut depth = 0usize;
+
+    for (i, c) in part.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '=' if depth == 0 => return Some((&part[..i], &part[i + 1..])),
+            _ => {}
+        }
+    }
+
+    // an unclosed bracket, which the key can't be parsed from anyway
+    part.split_once('=')
+}
+
+/// Parses a two-value range like `18,65`, as used by `between`.
+///
+/// A comma that is part of a value is written escaped, as `\,`.
+pub(crate) fn parse_pair<T>(value: &str) -> Result<(T, T), FilterParseError>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    parse_pair_with(value, |x| x.parse().map_err(FilterParseError::value))
+}
+
+/// Like [`parse_pair`], parsing each value with `parse`.
+pub(crate) fn parse_pair_with<T>(
+    value: &str,
+    parse: impl Fn(&str) -> Result<T, FilterParseError>,
+) -> Result<(T, T), FilterParseError> {
+    let parts = split_list(value);
+    let [low, high] = parts.as_slice() else {
+        return Err(FilterParseError::Value(None));
+    };
+
+    let low = parse(low)?;
+    let high = parse(high)?;
+
+    Ok((low, high))
+}
+
+/// Splits a comma-separated list like `a,b,c`, where a comma that is part of a value
+/// is written escaped, as `\,`. Without an escape, the values borrow from `value`.
+pub(crate) fn split_list(value: &str) -> Vec<Cow<'_, str>> {
+    if !value.contains('\\') {
+        return value.split(',').map(Cow::Borrowed).collect();
+    }
+
+    let mut parts = vec![String::new()];
+    let mut chars = value.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&',') => {
+                parts.last_mut().unwrap().push(',');
+                chars.next();
+            }
+            ',' => parts.push(String::new()),
+            c => parts.last_mut().unwrap().push(c),
+        }
+    }
+
+    parts.into_iter().map(Cow::Owned).collect()
+}
+
 pub trait WithFilterId {
     fn filter_id() -> Option<&'static str>;
+
+    /// A column appended as the last sort key, so rows with equal sort values come back
+    /// in a deterministic order (usually the primary key).
+    fn default_tiebreak() -> Option<&'static str> {
+        None
+    }
+
+    /// The sort to use when the query has no `order_by`, e.g. newest first.
+    ///
+    /// `FromQueryFilter` leaves its `order_by` as `None` in that case, so a client sort
+    /// can still be told apart from the default; [`FromQueryFilter::sort`] applies it.
+    /// Unlike a client sort, it is not checked against `sortable_columns`.
+    ///
+    /// [`FromQueryFilter::sort`]: crate::query_filter::FromQueryFilter::sort
+    fn default_order_by() -> Option<OrderBy> {
+        None
+    }
+
+    /// The columns `order_by` may sort on, or `None` to accept any column.
+    ///
+    /// `FromQueryFilter` rejects any other column with `FilterParseError::UnknownField`,
+    /// so a client can't sort on columns that aren't exposed.
+    fn sortable_columns() -> Option<&'static [&'static str]> {
+        None
+    }
+
+    /// The fields parsed from a default value when the query leaves them out, with that
+    /// value, e.g. `("is_active", "true")`.
+    ///
+    /// [`FromQueryFilter::merge_query`] drops these from the query it overlays, so a
+    /// default doesn't reset a field that was already set.
+    ///
+    /// [`FromQueryFilter::merge_query`]: crate::query_filter::FromQueryFilter::merge_query
+    fn default_values() -> &'static [(&'static str, &'static str)] {
+        &[]
+    }
+
+    /// The column a client sort on `field` renders, like `users.full_name` for a field
+    /// `name` with a table and an alias, or `None` to sort on the field name, qualified
+    /// with [`WithFilterId::filter_id`].
+    ///
+    /// `FromQueryFilter` resolves the `order_by` it parses through it, after checking it
+    /// against `sortable_columns`, so a field sorts on the column it filters on.
+    fn sort_column(field: &str) -> Option<FilterId> {
+        let _ = field;
+        None
+    }
+
+    /// The operators `field` may be filtered with, or `None` to accept any operator.
+    ///
+    /// An operator is matched once it is parsed into an [`Op`], so listing
+    /// `Op::StartsWith` also allows its short spelling `sw`, while a negation like `!sw`
+    /// has to be listed as its own `Op::Not`; a bare `field=value` is `Op::Eq`.
+    ///
+    /// The parsers check it through [`ParseOptions::allowed_ops`], after resolving any
+    /// operator alias, and the `Filterable` derive sets it from
+    /// `#[filtrum(allowed_ops = "eq, starts_with")]`. `FromQueryFilter` also runs
+    /// [`check_ops_with`] before parsing the filters, for a `FromStr` impl that parses
+    /// without the options.
+    fn allowed_ops(field: &str) -> Option<&'static [Op]> {
+        let _ = field;
+        None
+    }
+
+    /// The fields of the filter with the operators each accepts, or an empty list if
+    /// they aren't known. The `Filterable` derive lists every non-skipped field.
+    fn filter_schema() -> Vec<FieldSchema> {
+        Vec::new()
+    }
+
+    /// Parses the filter from the query `value` with `options`, as
+    /// [`FromQueryFilter::from_str_with`] does with its inner filter.
+    ///
+    /// The `Filterable` derive parses every field with `options`, checked against its
+    /// own [`WithFilterId::allowed_ops`]. By default, the filter's `FromStr` parses the
+    /// query, joined back into a query string, which only sees the presence flags of
+    /// `options`, expanded into `flag=true` up front.
+    ///
+    /// [`FromQueryFilter::from_str_with`]: crate::query_filter::FromQueryFilter::from_str_with
+    fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError>
+    where
+        Self: FromStr<Err = FilterParseError> + Sized,
+    {
+        let value = value.into().to_query_string()?;
+
+        match options.presence_flags {
+            true => expand_flags(&value).parse(),
+            false => value.parse(),
+        }
+    }
+}
+
+/// Checks every parameter of the query string `value` against
+/// [`WithFilterId::allowed_ops`], returning `FilterParseError::UnknownFilter` for the
+/// first operator that isn't allowed for its field.
+///
+/// `FromQueryFilter` already runs this; call it from a `FromStr` impl that is parsed on
+/// its own.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::common::{check_ops, WithFilterId};
+/// use filtrum::errors::FilterParseError;
+/// use filtrum::op::Op;
+///
+/// struct UserFilter;
+///
+/// impl WithFilterId for UserFilter {
+///     fn filter_id() -> Option<&'static str> {
+///         None
+///     }
+///
+///     fn allowed_ops(field: &str) -> Option<&'static [Op]> {
+///         match field {
+///             "age" => Some(&[Op::Eq, Op::Gte, Op::Lte]),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// assert!(check_ops::<UserFilter>("age[gte]=18&name[like]=al").is_ok());
+/// assert!(matches!(
+///     check_ops::<UserFilter>("age[ne]=18"),
+///     Err(FilterParseError::UnknownFilter)
+/// ));
+/// ```
+pub fn check_ops<F: WithFilterId + ?Sized>(value: &str) -> Result<(), FilterParseError> {
+    check_ops_with::<F>(value, &ParseOptions::default())
+}
+
+/// Like [`check_ops`], resolving the operator aliases of `options` first, for a query
+/// string or pairs.
+pub fn check_ops_with<'a, F: WithFilterId + ?Sized>(
+    value: impl Into<QueryParams<'a>>,
+    options: &ParseOptions,
+) -> Result<(), FilterParseError> {
+    for key in value.into().keys() {
+        let Some((id, op)) = parse_key(key) else {
+            continue;
+        };
+
+        let Some(allowed) = F::allowed_ops(&id) else {
+            continue;
+        };
+
+        let op = op.map_or(Op::Eq, |x| {
+            Op::from(&*options.aliases.resolve(&lowercase(x)))
+        });
+
+        if !allowed.contains(&op) {
+            return Err(FilterParseError::UnknownFilter);
+        }
+    }
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -56,8 +828,8 @@ mod tests {
     struct MockFilter(String, i32);
 
     impl FromStrFilter<i32> for MockFilter {
-        fn from_str(filter_key: &str, value: i32) -> Result<Self, FilterParseError> {
-            Ok(MockFilter(filter_key.to_string(), value))
+        fn from_str(op: &Op, value: i32) -> Result<Self, FilterParseError> {
+            Ok(MockFilter(op.to_string(), value))
         }
     }
 
@@ -100,12 +872,356 @@ mod tests {
         let qs = "age";
         let res: Result<Vec<MockFilter>, _> = from_str("age", qs);
         assert!(matches!(res, Err(FilterParseError::FilterStructure)));
+
+        // a malformed key isn't read as the field it starts with
+        for qs in ["age[gte=5", "age-x=5", "age[eq]x=5"] {
+            let res: Result<Vec<MockFilter>, _> = from_str("age", qs);
+            assert!(
+                matches!(res, Err(FilterParseError::FilterStructure)),
+                "{qs}"
+            );
+        }
     }
 
     #[test]
     fn test_invalid_value() {
         let qs = "age[eq]=notanumber";
         let res: Result<Vec<MockFilter>, _> = from_str("age", qs);
-        assert!(matches!(res, Err(FilterParseError::Value)));
+        assert!(matches!(res, Err(FilterParseError::Value(_))));
+    }
+
+    #[test]
+    fn test_empty_values_are_skipped() {
+        let res: Vec<MockFilter> = from_str("age", "age[eq]=&age[lt]=20").unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].1, 20);
+
+        // a number can't be empty, even when asked to keep empty values
+        let options = ParseOptions {
+            keep_empty_values: true,
+            ..Default::default()
+        };
+        let res: Vec<MockFilter> = from_str_with("age", "age=", &options).unwrap();
+        assert!(res.is_empty());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_decode_query() {
+        let options = ParseOptions::default();
+        let decode = |x| decode_query(x, &options).unwrap();
+        let pair = |key: &'static str, value: &'static str| (Cow::from(key), Cow::from(value));
+
+        assert_eq!(
+            decode("age%5Bgte%5D=18&name=John+Doe"),
+            [pair("age[gte]", "18"), pair("name", "John Doe")]
+        );
+        assert_eq!(decode("name=%E2%82%AC&"), [pair("name", "€")]);
+        assert_eq!(
+            decode("data%5C%5Bjson%5C%5D=1"),
+            [pair(r"data\[json\]", "1")]
+        );
+        assert_eq!(decode("age[>=]=1+2"), [pair("age[>=]", "1 2")]);
+        assert!(matches!(
+            decode("age=5")[0],
+            (Cow::Borrowed(_), Cow::Borrowed(_))
+        ));
+
+        // a `%` that doesn't start an escape is kept
+        assert_eq!(
+            decode("name=50%&x=%zz%4"),
+            [pair("name", "50%"), pair("x", "%zz%4")]
+        );
+
+        // the query is split before it is decoded, so a value can hold `&` and `=`
+        assert_eq!(
+            decode("name=Tom%20%26%20Jerry&q=a%3Db"),
+            [pair("name", "Tom & Jerry"), pair("q", "a=b")]
+        );
+        let res: Vec<MockFilter> = parse_field(&decode("age=1&q=%26"), "age").unwrap();
+        assert_eq!(ops(&res), [("eq", 1)]);
+
+        // a bare flag is read as the parsers read it
+        assert!(matches!(
+            decode_query("active", &options),
+            Err(FilterParseError::FilterStructure)
+        ));
+        let flags = ParseOptions {
+            presence_flags: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            decode_query("active", &flags).unwrap(),
+            [pair("active", "true")]
+        );
+
+        assert!(matches!(
+            decode_query("name=%FF", &options),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_query_params_to_query_string() {
+        let pairs = [
+            (Cow::from("age[>=]"), Cow::from("1")),
+            (Cow::from("q"), Cow::from("a=b")),
+        ];
+        assert_eq!(
+            QueryParams::from(&pairs[..]).to_query_string().unwrap(),
+            "age[>=]=1&q=a=b"
+        );
+
+        let pairs = [(Cow::from("q"), Cow::from("a&b"))];
+        assert!(matches!(
+            QueryParams::from(&pairs[..]).to_query_string(),
+            Err(FilterParseError::Value(_))
+        ));
+
+        let pairs = [(Cow::from("a=b"), Cow::from("1"))];
+        assert!(matches!(
+            QueryParams::from(&pairs[..]).to_query_string(),
+            Err(FilterParseError::FilterStructure)
+        ));
+    }
+
+    #[test]
+    fn test_leading_question_mark() {
+        let res: Vec<MockFilter> = from_str("age", "?age=5").unwrap();
+        assert_eq!(ops(&res), [("eq", 5)]);
+
+        let res: Vec<MockFilter> = from_str("age", "?height=1&age[gte]=5").unwrap();
+        assert_eq!(ops(&res), [("gte", 5)]);
+
+        let res: Vec<(String, MockFilter)> = from_str_prefix("attr_", "?attr_a=1").unwrap();
+        assert_eq!(res[0].0, "a");
+
+        assert!(has_field("?age=5", "age"));
+    }
+
+    #[test]
+    fn test_trailing_separators() {
+        let res: Vec<MockFilter> = from_str("age", "age=10&").unwrap();
+        assert_eq!(res.len(), 1);
+
+        let res: Vec<MockFilter> = from_str("age", "&&age=10&&").unwrap();
+        assert_eq!(res.len(), 1);
+        assert_eq!(res[0].1, 10);
+    }
+
+    #[test]
+    fn test_escaped_field_brackets() {
+        let qs = r"data\[json\][GTE]=1&data[json]=2&data\[json\]=3&data=4";
+        let res: Vec<MockFilter> = from_str("data[json]", qs).unwrap();
+        assert_eq!(ops(&res), [("gte", 1), ("eq", 3)]);
+
+        // without the escape, the bracket is the operator of `data`
+        let res: Vec<MockFilter> = from_str("data", qs).unwrap();
+        assert_eq!(ops(&res), [("json", 2), ("eq", 4)]);
+
+        let res: Vec<MockFilter> = from_str("a.b]", r"a\.b\]=5").unwrap();
+        assert_eq!(ops(&res), [("eq", 5)]);
+
+        // only brackets and `.` are escaped, and no other character is part of a field
+        for qs in [
+            r"a\\b=1",
+            r"a\'b=1",
+            r"a\[b\]'=1",
+            r"a\[b\]\=1",
+            r"a\[b\] c=1",
+        ] {
+            let res: Result<Vec<MockFilter>, _> = from_str("a", qs);
+            assert!(
+                matches!(res, Err(FilterParseError::FilterStructure)),
+                "{qs}"
+            );
+        }
+
+        assert!(has_field(r"data\[json\][ne]=1", "data[json]"));
+        assert!(!has_field(r"data\[json\][ne]=1", "data"));
+
+        let res: Vec<MockFilter> = from_str("[json]", r"\[json\]=1").unwrap();
+        assert_eq!(ops(&res), [("eq", 1)]);
+    }
+
+    #[test]
+    fn test_plain_keys_match_the_regex() {
+        let keys = [
+            "age",
+            "age[gte]",
+            "age[!in]",
+            "age[>=]",
+            "a_1[x y]",
+            "age[]",
+            "age[gte",
+            "age[a][0]",
+            "attrs.color",
+            "attrs.color[ne]",
+            "attrs.",
+            ".color",
+            "a.b.c",
+            "attrs.cölor",
+            "age[[x]]",
+            "?age",
+            "-age[eq]",
+            "née[eq]",
+            "na-me",
+            "[eq]",
+            "",
+        ];
+
+        for key in keys {
+            let regex = query_regex().captures(key).map(|rg| {
+                let id = rg.get(1).unwrap().as_str();
+                (Cow::Borrowed(id), rg.get(3).map(|x| x.as_str()))
+            });
+            assert_eq!(parse_key(key), regex, "{key}");
+        }
+
+        assert_eq!(parse_plain_key("age[gte]"), Some(("age", Some("gte"))));
+        assert_eq!(parse_plain_key("née"), None);
+    }
+
+    #[test]
+    fn test_split_list_borrows_without_escapes() {
+        let parts = split_list("a,,b");
+        assert_eq!(parts, ["a", "", "b"]);
+        assert!(parts.iter().all(|x| matches!(x, Cow::Borrowed(_))));
+
+        assert_eq!(split_list(r"a\,b,c"), ["a,b", "c"]);
+    }
+
+    #[test]
+    fn test_parse_pair() {
+        assert_eq!(parse_pair::<i32>("18,65").unwrap(), (18, 65));
+        assert_eq!(
+            parse_pair::<String>("a\\,b,c").unwrap(),
+            ("a,b".to_string(), "c".to_string())
+        );
+
+        assert!(matches!(
+            parse_pair::<i32>("18"),
+            Err(FilterParseError::Value(_))
+        ));
+        assert!(matches!(
+            parse_pair::<i32>("1,2,3"),
+            Err(FilterParseError::Value(_))
+        ));
+        assert!(matches!(
+            parse_pair::<i32>("1,x"),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_field_pairs() {
+        let pairs = [
+            (Cow::from("age[gte]"), Cow::from("10")),
+            (Cow::from("name"), Cow::from("x")),
+            (Cow::from("age"), Cow::from("")),
+            (Cow::from("age[lt]"), Cow::from(String::from("20"))),
+        ];
+
+        let res: Vec<MockFilter> = parse_field(&pairs, "age").unwrap();
+        assert_eq!(res.len(), 2);
+        assert_eq!((res[0].0.as_str(), res[0].1), ("gte", 10));
+        assert_eq!((res[1].0.as_str(), res[1].1), ("lt", 20));
+
+        // values are taken as they are, so `a=b` is a value and not another pair
+        let pairs = [(Cow::from("age"), Cow::from("1=2"))];
+        let res = parse_field::<i32, MockFilter>(&pairs, "age");
+        assert!(matches!(res, Err(FilterParseError::Value(_))));
+    }
+
+    fn ops(res: &[MockFilter]) -> Vec<(&str, i32)> {
+        res.iter().map(|x| (x.0.as_str(), x.1)).collect()
+    }
+
+    #[test]
+    fn test_bare_and_explicit_eq() {
+        // by default every parameter is kept, in query order
+        let res: Vec<MockFilter> = from_str("age", "age=1&age[ne]=2&age[eq]=3&age=4").unwrap();
+        assert_eq!(ops(&res), [("eq", 1), ("ne", 2), ("eq", 3), ("eq", 4)]);
+
+        let options = ParseOptions {
+            explicit_eq_overrides_bare: true,
+            aliases: crate::options::OperatorAliases::new().with("is", "eq"),
+            ..Default::default()
+        };
+
+        // an explicit `eq` drops every bare parameter of the field
+        let res: Vec<MockFilter> =
+            from_str_with("age", "age=1&age[ne]=2&age[eq]=3&age=4", &options).unwrap();
+        assert_eq!(ops(&res), [("ne", 2), ("eq", 3)]);
+
+        let res: Vec<MockFilter> = from_str_with("age", "age[IS]=3&age=4", &options).unwrap();
+        assert_eq!(ops(&res), [("eq", 3)]);
+
+        // without one, the bare parameters stay, even when repeated
+        let res: Vec<MockFilter> =
+            from_str_with("age", "age=1&age[ne]=2&age=4&height[eq]=5", &options).unwrap();
+        assert_eq!(ops(&res), [("eq", 1), ("ne", 2), ("eq", 4)]);
+
+        // a negated `eq` doesn't override
+        let res: Vec<MockFilter> = from_str_with("age", "age=1&age[!eq]=2", &options).unwrap();
+        assert_eq!(ops(&res), [("eq", 1), ("!eq", 2)]);
+
+        let pairs = [
+            (Cow::from("age"), Cow::from("1")),
+            (Cow::from("age[eq]"), Cow::from("3")),
+        ];
+        let res: Vec<MockFilter> = parse_field_with(&pairs, "age", &options).unwrap();
+        assert_eq!(ops(&res), [("eq", 3)]);
+
+        let res: Vec<(String, MockFilter)> =
+            from_str_prefix_with("attr_", "attr_a=1&attr_a[eq]=2&attr_b=3", &options).unwrap();
+        let res: Vec<_> = res.iter().map(|(id, x)| (id.as_str(), x.1)).collect();
+        assert_eq!(res, [("a", 2), ("b", 3)]);
+    }
+
+    struct AgeFilter;
+
+    impl WithFilterId for AgeFilter {
+        fn filter_id() -> Option<&'static str> {
+            None
+        }
+
+        fn allowed_ops(field: &str) -> Option<&'static [Op]> {
+            match field {
+                "age" => Some(&[Op::Eq, Op::Gte]),
+                _ => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_allowed_ops_with_aliases() {
+        let options = ParseOptions {
+            aliases: crate::options::OperatorAliases::new()
+                .with("$gte", "gte")
+                .with("$ne", "ne"),
+            allowed_ops: crate::options::AllowedOps::of::<AgeFilter>(),
+            ..Default::default()
+        };
+
+        // an alias is checked as the operator it stands for
+        let res: Vec<MockFilter> = from_str_with("age", "age=1&age[$GTE]=2", &options).unwrap();
+        assert_eq!(res.len(), 2);
+        assert!(check_ops_with::<AgeFilter>("age=1&age[$gte]=2", &options).is_ok());
+
+        for query in ["age[$ne]=2", "age[ne]=2", "age[!eq]=2"] {
+            let res = from_str_with::<i32, MockFilter>("age", query, &options);
+            assert!(
+                matches!(res, Err(FilterParseError::UnknownFilter)),
+                "{query}"
+            );
+            assert!(
+                check_ops_with::<AgeFilter>(query, &options).is_err(),
+                "{query}"
+            );
+        }
+
+        // other fields accept any operator
+        let res: Vec<MockFilter> = from_str_with("height", "height[$ne]=2", &options).unwrap();
+        assert_eq!(res.len(), 1);
+    }
+}
@@ -0,0 +1,269 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::{
+    common::{from_str_with, FromStrFilter, QueryParams},
+    errors::FilterParseError,
+    op::Op,
+    options::ParseOptions,
+};
+
+/// A keyset pagination cursor: the sort key values of the last row on a page.
+///
+/// Parses `cursor=<token>` from the query string, where the token is the values joined
+/// with commas and encoded as URL-safe base64. A plain token can be decoded and edited
+/// by the client, so sign it with a [`CursorKey`] when that must not be possible.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::cursor::Cursor;
+///
+/// let cursor = Cursor::new(vec!["2024-01-01".to_string(), "42".to_string()]);
+/// let query = format!("cursor={}", cursor.encode());
+///
+/// assert_eq!(Cursor::from_str(&query).unwrap(), Some(cursor));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Cursor(pub Vec<String>);
+
+impl Cursor {
+    pub fn new(values: Vec<String>) -> Self {
+        Self(values)
+    }
+
+    /// Encodes the cursor as an unsigned token.
+    pub fn encode(&self) -> String {
+        URL_SAFE_NO_PAD.encode(self.payload())
+    }
+
+    /// Decodes an unsigned token made by [`Cursor::encode`].
+    pub fn decode(token: &str) -> Result<Self, FilterParseError> {
+        let payload = URL_SAFE_NO_PAD
+            .decode(token)
+            .map_err(|_| FilterParseError::InvalidCursor)?;
+
+        Self::from_payload(&payload)
+    }
+
+    // `None` when the parameter is absent, which `FromStr` can't express
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Result<Option<Self>, FilterParseError> {
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        token(value, options)?.map(|x| Self::decode(&x)).transpose()
+    }
+
+    /// Like [`Cursor::from_str`], but only accepts tokens signed with `key`.
+    pub fn from_str_signed(value: &str, key: &CursorKey) -> Result<Option<Self>, FilterParseError> {
+        Self::from_str_signed_with(value, key, &ParseOptions::default())
+    }
+
+    pub fn from_str_signed_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        key: &CursorKey,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        token(value, options)?.map(|x| key.decode(&x)).transpose()
+    }
+
+    // `\` and `,` inside a value are escaped, so the values split back unambiguously
+    fn payload(&self) -> String {
+        self.0
+            .iter()
+            .map(|x| x.replace('\\', "\\\\").replace(',', "\\,"))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    fn from_payload(payload: &[u8]) -> Result<Self, FilterParseError> {
+        let payload = std::str::from_utf8(payload).map_err(|_| FilterParseError::InvalidCursor)?;
+
+        let mut values = vec![String::new()];
+        let mut chars = payload.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some(c @ ('\\' | ',')) => values.last_mut().unwrap().push(c),
+                    _ => return Err(FilterParseError::InvalidCursor),
+                },
+                ',' => values.push(String::new()),
+                c => values.last_mut().unwrap().push(c),
+            }
+        }
+
+        Ok(Self(values))
+    }
+}
+
+/// A server-side key that signs cursors with HMAC-SHA256.
+///
+/// A signed token is `<payload>.<signature>`; decoding it with a different key, or
+/// after the payload was edited, fails with [`FilterParseError::InvalidCursor`].
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::cursor::{Cursor, CursorKey};
+///
+/// let key = CursorKey::new(b"server secret");
+/// let token = key.encode(&Cursor::new(vec!["42".to_string()]));
+///
+/// assert_eq!(key.decode(&token).unwrap().0, vec!["42"]);
+/// assert!(CursorKey::new(b"other secret").decode(&token).is_err());
+/// ```
+#[derive(Clone)]
+pub struct CursorKey(Vec<u8>);
+
+impl CursorKey {
+    pub fn new(key: impl AsRef<[u8]>) -> Self {
+        Self(key.as_ref().to_vec())
+    }
+
+    /// Encodes `cursor` as a signed token.
+    pub fn encode(&self, cursor: &Cursor) -> String {
+        let payload = cursor.encode();
+
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+        format!("{payload}.{signature}")
+    }
+
+    /// Decodes a token made by [`CursorKey::encode`] with the same key.
+    pub fn decode(&self, token: &str) -> Result<Cursor, FilterParseError> {
+        let (payload, signature) = token
+            .split_once('.')
+            .ok_or(FilterParseError::InvalidCursor)?;
+
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| FilterParseError::InvalidCursor)?;
+
+        // `verify_slice` compares in constant time
+        let mut mac = self.mac();
+        mac.update(payload.as_bytes());
+        mac.verify_slice(&signature)
+            .map_err(|_| FilterParseError::InvalidCursor)?;
+
+        Cursor::decode(payload)
+    }
+
+    fn mac(&self) -> Hmac<Sha256> {
+        Hmac::new_from_slice(&self.0).expect("HMAC accepts keys of any length")
+    }
+}
+
+// keeps the key out of logs
+impl std::fmt::Debug for CursorKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CursorKey(..)")
+    }
+}
+
+struct Token(String);
+
+impl FromStrFilter<String> for Token {
+    fn from_str(_op: &Op, value: String) -> Result<Self, FilterParseError> {
+        Ok(Token(value))
+    }
+}
+
+/// The raw `cursor` token; like `skip`, the last one wins unless parsing is strict.
+fn token<'a>(
+    value: impl Into<QueryParams<'a>>,
+    options: &ParseOptions,
+) -> Result<Option<String>, FilterParseError> {
+    let mut all = from_str_with::<String, Token>("cursor", value, options)?;
+
+    if options.strict && all.len() > 1 {
+        return Err(FilterParseError::Duplicate);
+    }
+
+    Ok(all.pop().map(|x| x.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cursor() -> Cursor {
+        Cursor::new(vec![
+            "Smith, John".to_string(),
+            "C:\\temp".to_string(),
+            "".to_string(),
+            "42".to_string(),
+        ])
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let token = cursor().encode();
+        assert!(!token.contains(['&', '=', '+', '/']));
+        assert_eq!(Cursor::decode(&token).unwrap(), cursor());
+
+        let query = format!("limit=10&cursor={token}");
+        assert_eq!(Cursor::from_str(&query).unwrap(), Some(cursor()));
+        assert_eq!(Cursor::from_str("limit=10").unwrap(), None);
+
+        assert!(matches!(
+            Cursor::from_str("cursor=not*base64"),
+            Err(FilterParseError::InvalidCursor)
+        ));
+    }
+
+    #[test]
+    fn test_signed_cursor_round_trip() {
+        let key = CursorKey::new(b"secret");
+        let token = key.encode(&cursor());
+
+        assert_eq!(key.decode(&token).unwrap(), cursor());
+
+        let query = format!("cursor={token}");
+        assert_eq!(
+            Cursor::from_str_signed(&query, &key).unwrap(),
+            Some(cursor())
+        );
+    }
+
+    #[test]
+    fn test_signed_cursor_tampered() {
+        let key = CursorKey::new(b"secret");
+        let token = key.encode(&cursor());
+        let (_, signature) = token.split_once('.').unwrap();
+
+        // a client re-encodes the payload to point at another row
+        let forged = Cursor::new(vec!["Smith, John".to_string(), "1".to_string()]).encode();
+        let tampered = format!("{forged}.{signature}");
+
+        for token in [
+            tampered.as_str(),
+            forged.as_str(),
+            &token[..token.len() - 1],
+            "",
+        ] {
+            assert!(
+                matches!(key.decode(token), Err(FilterParseError::InvalidCursor)),
+                "{token}"
+            );
+        }
+
+        // signed with another key
+        assert!(CursorKey::new(b"other").decode(&token).is_err());
+
+        // an unsigned token is rejected where a signed one is expected
+        let query = format!("cursor={}", cursor().encode());
+        assert!(matches!(
+            Cursor::from_str_signed(&query, &key),
+            Err(FilterParseError::InvalidCursor)
+        ));
+    }
+}
@@ -0,0 +1,267 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    common::{from_str_prefix_with, QueryParams},
+    errors::{BoxError, FilterParseError},
+    filter::{condition_shape, Filter, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    op::Op,
+    options::ParseOptions,
+    string_filter::{StringFilter, StringOperand},
+};
+
+/// String filters on a family of dynamic attributes, e.g. the keys of a JSON column.
+///
+/// For the field `attr`, every parameter named `attr.<name>` is parsed as a string
+/// filter on the attribute `<name>`: `attr.color=red&attr.size[ne]=XL` filters on
+/// `color` and `size`. The names are not known up front, so any attribute the client
+/// sends is accepted, as long as it is made of ASCII word characters (`[A-Za-z0-9_]`):
+/// the name is written into the SQL, so any other name, like `attr.x'`, is rejected
+/// with [`FilterParseError::FilterStructure`]. The `.` can't be part of a field name,
+/// so no other field's parameter is taken for an attribute, e.g. `attr_count` of a
+/// field `attr_count`.
+///
+/// With SQLx, each attribute is read from the JSON column of the field as text
+/// (`attr->>'color'` on Postgres, `json_extract(attr, '$.color')` on SQLite).
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::dynamic_filter::DynamicFilters;
+/// use filtrum::string_filter::StringFilter;
+///
+/// let query = "attr.color=red&attr.size[ne]=XL&attr_count=3";
+/// let attrs = DynamicFilters::<String>::from_str("attr", query).unwrap();
+///
+/// assert_eq!(
+///     attrs.0,
+///     vec![
+///         ("color".to_string(), StringFilter::Eq("red".to_string())),
+///         ("size".to_string(), StringFilter::Ne("XL".to_string()))
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct DynamicFilters<T = String>(pub Vec<(String, StringFilter<T>)>, pub Option<FilterId>)
+where
+    T: FromStr + Display;
+
+impl<T> DynamicFilters<T>
+where
+    T: FromStr + Display,
+{
+    /// Parses the attribute filters of the field `search_id` from a query string.
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    /// Parses the attribute filters of the field `search_id` from a query string.
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Parses the attribute filters of the field `search_id` from a query string, using
+    /// `options`.
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
+        let prefix = format!("{}.", search_id.id());
+        let filters = from_str_prefix_with::<StringOperand<T>, _>(&prefix, value, options)?;
+
+        if !filters.iter().all(|(name, _)| is_attribute_name(name)) {
+            return Err(FilterParseError::FilterStructure);
+        }
+
+        Ok(Self(filters, Some(search_id)))
+    }
+
+    /// Returns `true` if no filters were parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the `FilterId` the filters apply to.
+    pub fn with_id(self, search_id: FilterId) -> Self {
+        Self(self.0, Some(search_id))
+    }
+
+    /// Returns the filters on the attribute `name`.
+    pub fn attribute<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a StringFilter<T>> {
+        self.0
+            .iter()
+            .filter(move |(x, _)| x == name)
+            .map(|(_, x)| x)
+    }
+}
+
+/// Returns `true` if `name` is made of ASCII word characters, so it can be written into
+/// the SQL as is.
+fn is_attribute_name(name: &str) -> bool {
+    !name.is_empty() && name.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_')
+}
+
+/// Each attribute filter is one condition, in the order they were given. The attribute
+/// names aren't part of the conditions; read them from the filters themselves.
+impl<T> Filter for DynamicFilters<T>
+where
+    T: FromStr + Display + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        DynamicFilters::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.0
+            .iter()
+            .map(|(_, x)| (x.operator(), x.filter_value()))
+            .collect()
+    }
+
+    /// Each attribute is read from the JSON column by name, so the name is part of the
+    /// shape, e.g. `.color[eq]`.
+    fn condition_shapes(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|(name, x)| {
+                let shape = condition_shape(&x.operator(), &x.filter_value());
+                format!(".{name}{shape}")
+            })
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for DynamicFilters<T>
+where
+    T: FromStr + Display + ToFilterValue,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        let Some(id) = &self.1 else {
+            return;
+        };
+
+        let column = id.qualified();
+        for (name, filter) in &self.0 {
+            visit(
+                format!("{column}.{name}"),
+                filter.operator(),
+                filter.filter_value(),
+            );
+        }
+    }
+}
+
+/// A filter in `other` replaces the filters in `self` on the same attribute with the
+/// same operator.
+impl<T> Merge for DynamicFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn merge(&mut self, other: Self) {
+        self.0.retain(|(name, filter)| {
+            !other
+                .0
+                .iter()
+                .any(|(x, y)| x == name && y.operator() == filter.operator())
+        });
+        self.0.extend(other.0);
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dynamic_filters() {
+        // `attr_a` is a field of its own, not an attribute
+        let qs =
+            "attr.color=red&attr.color[!eq]=blue&attr.size[LEN_GT]=1&attr=x&attrs.a=1&attr_a=1";
+        let f = DynamicFilters::<String>::from_str("attr", qs).unwrap();
+
+        assert_eq!(
+            f.0,
+            vec![
+                ("color".to_string(), StringFilter::Eq("red".to_string())),
+                (
+                    "color".to_string(),
+                    StringFilter::Not(Box::new(StringFilter::Eq("blue".to_string())))
+                ),
+                ("size".to_string(), StringFilter::LenGt(1))
+            ]
+        );
+        assert_eq!(
+            f.attribute("size").collect::<Vec<_>>(),
+            vec![&StringFilter::LenGt(1)]
+        );
+        assert_eq!(f.attribute("weight").count(), 0);
+
+        assert!(
+            DynamicFilters::<String>::from_str("meta", qs)
+                .unwrap()
+                .is_empty()
+        );
+        assert!(matches!(
+            DynamicFilters::<String>::from_str("attr", "attr.color[gte]=1"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+
+    #[test]
+    fn test_dynamic_filters_reject_other_names() {
+        for qs in [r"attr.x\'=1", "attr.cölor=red", r"attr.x\[0\]=1"] {
+            let res = DynamicFilters::<String>::from_str("attr", qs);
+            assert!(
+                matches!(res, Err(FilterParseError::FilterStructure)),
+                "{qs}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dynamic_filters_merge() {
+        let mut f =
+            DynamicFilters::<String>::from_str("attr", "attr.color=red&attr.size=M").unwrap();
+        f.merge(DynamicFilters::from_str("attr", "attr.size=L&attr.fit[sw]=sl").unwrap());
+
+        assert_eq!(
+            f.0,
+            vec![
+                ("color".to_string(), StringFilter::Eq("red".to_string())),
+                ("size".to_string(), StringFilter::Eq("L".to_string())),
+                (
+                    "fit".to_string(),
+                    StringFilter::StartsWith("sl".to_string())
+                )
+            ]
+        );
+    }
+}
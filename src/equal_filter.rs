@@ -1,9 +1,14 @@
 use std::str::FromStr;
 
 use crate::{
-    common::{from_str, FromStrFilter},
-    errors::FilterParseError,
+    common::{from_str_with, split_list, FromParam, FromStrFilter, QueryParams},
+    errors::{BoxError, FilterParseError},
+    filter::{visit_filter, Filter, Merge, VisitConditions},
     filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    in_filter::ListOperand,
+    op::Op,
+    options::ParseOptions,
 };
 
 /// A simple filter for exact equality.
@@ -11,6 +16,11 @@ use crate::{
 /// This is typically used for boolean flags or when only a single value is allowed (no ranges or partial matches).
 /// It parses `field=value` into `Some(value)`.
 ///
+/// A comma-separated list with the `in` operator, `role[in]=admin,editor`, matches any of
+/// its values (`role IN (?, ?)`). A comma that is part of a listed value is written
+/// escaped, as `\,`. Without `in` a comma is part of the value, so `city=Washington, D.C.`
+/// is a single city.
+///
 /// # Example
 ///
 /// ```rust
@@ -20,46 +30,163 @@ use crate::{
 /// let filter = EqualFilter::<bool>::from_str("is_active", query).unwrap();
 ///
 /// assert_eq!(filter.into_inner(), Some(true));
+///
+/// let role = EqualFilter::<String>::from_str("role", "role[in]=admin,editor").unwrap();
+/// assert_eq!(role.values().collect::<Vec<_>>(), ["admin", "editor"]);
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
-pub struct EqualFilter<T>(pub Option<T>, pub Option<FilterId>);
+pub struct EqualFilter<T>(pub Vec<T>, pub Option<FilterId>);
 
 impl<T> EqualFilter<T> {
-    /// Consumes the filter and returns the inner value, if present.
+    /// Consumes the filter and returns the inner value, if present. Of a list, this is
+    /// the first value.
     pub fn into_inner(self) -> Option<T> {
-        self.0
+        self.0.into_iter().next()
     }
 
-    /// Returns a reference to the inner value, if present.
+    /// Returns `true` if no value was parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns a reference to the inner value, if present. Of a list, this is the first
+    /// value.
     pub fn as_ref(&self) -> Option<&T> {
-        self.0.as_ref()
+        self.0.first()
+    }
+
+    /// Returns every value the column is compared against, in query order.
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.0.iter()
+    }
+
+    /// Returns the inner value as a [`FilterValue`], if present. A list gives a
+    /// [`FilterValue::List`] of its values.
+    pub fn filter_value(&self) -> Option<FilterValue>
+    where
+        T: ToFilterValue,
+    {
+        match self.0.as_slice() {
+            [] => None,
+            [value] => Some(value.to_filter_value()),
+            values => Some(FilterValue::List(
+                values.iter().map(|x| x.to_filter_value()).collect(),
+            )),
+        }
     }
 }
 
 impl<T> FromStrFilter<T> for EqualFilter<T> {
-    fn from_str(_filter_key: &str, value: T) -> Result<Self, FilterParseError> {
-        // we don't need to the filter id here so we ignore it.
-        Ok(Self(Some(value), Some("".to_string().into())))
+    fn from_str(_op: &Op, value: T) -> Result<Self, FilterParseError> {
+        // there is no id to set yet, as only the operator is known;
+        // `from_id_value_with` attaches the real one.
+        Ok(Self(vec![value], None))
+    }
+}
+
+impl<T> FromParam<ListOperand<T>> for EqualFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        // only `in` takes a list, any other comma is part of the value
+        if *op != Op::In {
+            let value = value.parse().map_err(FilterParseError::value)?;
+            return Ok(Self(vec![value], None));
+        }
+
+        let values = split_list(value)
+            .iter()
+            .map(|x| {
+                if x.is_empty() {
+                    return Err(FilterParseError::Value(None));
+                }
+
+                x.parse().map_err(FilterParseError::value)
+            })
+            .collect::<Result<Vec<T>, _>>()?;
+
+        Ok(Self(values, None))
     }
 }
 
 impl<T> EqualFilter<T>
 where
-    T: FromStr + Clone,
+    T: FromStr,
+    T::Err: Into<BoxError>,
 {
     pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
         Self::from_id_value(search_id.to_string().into(), value)
     }
 
     pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
         // we use the same algorithm as others, but we ignore the filter
-        let u = from_str::<T, EqualFilter<T>>(search_id.id(), value)?
-            .first()
-            .cloned();
+        let filter =
+            from_str_with::<ListOperand<T>, EqualFilter<T>>(search_id.id(), value, options)?
+                .into_iter()
+                .next();
+
+        Ok(Self(filter.map_or_else(Vec::new, |x| x.0), Some(search_id)))
+    }
+}
+
+impl<T> Filter for EqualFilter<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        EqualFilter::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        let operator = if self.0.len() > 1 { Op::In } else { Op::Eq };
+        self.filter_value()
+            .map(|x| (operator, x))
+            .into_iter()
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for EqualFilter<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
 
-        match u {
-            Some(u) => Ok(Self(u.0, Some(search_id))),
-            None => Ok(Self(None, Some(search_id))),
+impl<T> Merge for EqualFilter<T> {
+    fn merge(&mut self, other: Self) {
+        if !other.0.is_empty() {
+            self.0 = other.0;
+        }
+
+        if self.1.is_none() {
+            self.1 = other.1;
         }
     }
 }
@@ -78,4 +205,132 @@ mod tests {
         let f = EqualFilter::<i32>::from_str("age", qs).unwrap();
         assert_eq!(f.into_inner(), None);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_equal_filter_error_source() {
+        use std::error::Error;
+
+        // a single value and an item of a list both keep the `ParseIntError`
+        for qs in ["age=x", "age[in]=1,x"] {
+            let err = EqualFilter::<i32>::from_str("age", qs).unwrap_err();
+            let source = err.source().expect(qs);
+            assert!(source.is::<std::num::ParseIntError>(), "{qs}");
+        }
+    }
+
+    #[test]
+    fn test_equal_filter_id() {
+        let f = EqualFilter::<bool>::from_str("active", "active=true").unwrap();
+        assert_eq!(f.1, Some(FilterId::Alone("active".to_string())));
+
+        let id = FilterId::WithPrefix("users".to_string(), "active".to_string());
+        let f = EqualFilter::<bool>::from_id_value(id.clone(), "active=true").unwrap();
+        assert_eq!(f, EqualFilter(vec![true], Some(id)));
+
+        // the single-filter parse has no field to attach
+        let f = <EqualFilter<bool> as FromStrFilter<bool>>::from_str(&Op::Eq, true).unwrap();
+        assert_eq!(f.1, None);
+    }
+
+    #[test]
+    fn test_equal_filter_empty_value() {
+        let f = EqualFilter::<i32>::from_str("age", "age=").unwrap();
+        assert_eq!(f.into_inner(), None);
+
+        let f = EqualFilter::<String>::from_str("name", "name=").unwrap();
+        assert_eq!(f.into_inner(), None);
+
+        let options = ParseOptions {
+            keep_empty_values: true,
+            ..Default::default()
+        };
+        let f =
+            EqualFilter::<String>::from_id_value_with("name".to_string().into(), "name=", &options)
+                .unwrap();
+        assert_eq!(f.into_inner(), Some("".to_string()));
+    }
+
+    #[test]
+    fn test_equal_filter_list() {
+        let f = EqualFilter::<String>::from_str("role", "role[in]=admin,editor").unwrap();
+        assert_eq!(f.as_ref().map(String::as_str), Some("admin"));
+        assert_eq!(f.values().collect::<Vec<_>>(), ["admin", "editor"]);
+        assert_eq!(
+            f.conditions(),
+            vec![(
+                Op::In,
+                FilterValue::List(vec![
+                    FilterValue::String("admin".to_string()),
+                    FilterValue::String("editor".to_string())
+                ])
+            )]
+        );
+
+        // a single value is still an `eq`
+        let f = EqualFilter::<i32>::from_str("age", "age=20").unwrap();
+        assert_eq!(f.values().collect::<Vec<_>>(), [&20]);
+        assert_eq!(f.conditions(), vec![(Op::Eq, FilterValue::Int(20))]);
+
+        let f = EqualFilter::<i32>::from_str("age", "age[IN]=1,2,3").unwrap();
+        assert_eq!(
+            f,
+            EqualFilter(vec![1, 2, 3], Some("age".to_string().into()))
+        );
+
+        let f = EqualFilter::<String>::from_str("name", r"name[in]=Smith\, John,Doe").unwrap();
+        assert_eq!(f.values().collect::<Vec<_>>(), ["Smith, John", "Doe"]);
+
+        // without `in`, a comma is part of the value
+        for query in ["city=Washington, D.C.", "city[eq]=Washington, D.C."] {
+            let f = EqualFilter::<String>::from_str("city", query).unwrap();
+            assert_eq!(f.values().collect::<Vec<_>>(), ["Washington, D.C."]);
+        }
+        assert!(matches!(
+            EqualFilter::<i32>::from_str("age", "age=1,2"),
+            Err(FilterParseError::Value(_))
+        ));
+
+        assert!(matches!(
+            EqualFilter::<i32>::from_str("age", "age[in]=1,x"),
+            Err(FilterParseError::Value(_))
+        ));
+        assert!(matches!(
+            EqualFilter::<String>::from_str("role", "role[in]=admin,"),
+            Err(FilterParseError::Value(_))
+        ));
+
+        // a new value replaces the whole list
+        let mut f = EqualFilter::<i32>::from_str("age", "age[in]=1,2").unwrap();
+        f.merge(EqualFilter::from_str("age", "age=3").unwrap());
+        assert_eq!(f.values().collect::<Vec<_>>(), [&3]);
+    }
+
+    #[test]
+    fn test_equal_filter_presence_flag() {
+        let options = ParseOptions {
+            presence_flags: true,
+            ..Default::default()
+        };
+        let f = EqualFilter::<bool>::from_id_value_with(
+            "archived".to_string().into(),
+            "archived&limit=10",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(f.into_inner(), Some(true));
+
+        // an explicit value still wins
+        let f = EqualFilter::<bool>::from_id_value_with(
+            "archived".to_string().into(),
+            "archived=false",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(f.into_inner(), Some(false));
+
+        assert!(matches!(
+            EqualFilter::<bool>::from_str("archived", "archived&limit=10"),
+            Err(FilterParseError::FilterStructure)
+        ));
+    }
+}
@@ -1,11 +1,51 @@
 use thiserror::Error;
 
+/// The error of a failed value conversion, kept as the source of
+/// [`FilterParseError::Value`].
+///
+/// The value type of every filter must have a `FromStr::Err` that converts into it,
+/// i.e. a `std::error::Error + Send + Sync` like `ParseIntError`, or a `String`. This
+/// is a breaking change for a custom value type whose error doesn't: implement `Error`
+/// for it.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
 #[derive(Debug, Error)]
 pub enum FilterParseError {
     #[error("invalid filter structure")]
     FilterStructure,
+    /// A value that could not be parsed. Holds the error of the conversion that failed,
+    /// e.g. a `ParseIntError`, when there is one to keep.
     #[error("invalid filter value")]
-    Value,
+    Value(#[source] Option<BoxError>),
     #[error("unknown filter")]
     UnknownFilter,
+    #[error("duplicate parameter")]
+    Duplicate,
+    #[error("unknown field `{0}`")]
+    UnknownField(String),
+    #[error("invalid cursor")]
+    InvalidCursor,
+    /// A `skip` over [`ParseOptions::max_skip`](crate::options::ParseOptions::max_skip),
+    /// which it holds.
+    #[error("skip exceeds the maximum of {0}")]
+    SkipTooLarge(u64),
+    /// A request body that isn't `application/x-www-form-urlencoded`, where filters were
+    /// expected in a form.
+    #[error("expected an `application/x-www-form-urlencoded` body")]
+    ContentType,
+    /// A request body that couldn't be read, e.g. one over the size limit, or that isn't
+    /// UTF-8. Holds the error it failed with.
+    #[error("invalid request body")]
+    Body(#[source] BoxError),
+}
+
+impl FilterParseError {
+    /// A [`FilterParseError::Value`] caused by `source`. A `source` that is already a
+    /// `FilterParseError`, from a value type parsed by this crate, is returned as is.
+    pub fn value(source: impl Into<BoxError>) -> Self {
+        match source.into().downcast::<FilterParseError>() {
+            Ok(err) => *err,
+            Err(source) => FilterParseError::Value(Some(source)),
+        }
+    }
 }
@@ -0,0 +1,311 @@
+use crate::{
+    common::QueryParams, errors::FilterParseError, filter_id::FilterId,
+    filter_value::FilterValue, op::Op, options::ParseOptions,
+};
+
+/// The shape shared by the field filters, like [`StringFilters`](crate::StringFilters),
+/// [`NumberFilters`](crate::NumberFilters) and [`EqualFilter`](crate::EqualFilter), and
+/// by an optional one.
+///
+/// Each is parsed for one field from a query string, and holds the `FilterId` of that
+/// field plus any number of conditions. Generic code, like validation or metadata
+/// helpers, can use this trait instead of matching on every concrete type.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::{Filter, NumberFilters, Op, StringFilters};
+///
+/// fn operators<F: Filter>(field: &str, query: &str) -> Vec<Op> {
+///     let filter = F::from_id_value(field.to_string().into(), query).unwrap();
+///     filter.conditions().into_iter().map(|(op, _)| op).collect()
+/// }
+///
+/// let query = "name[sw]=Al&age[gte]=18&age[lt]=65";
+/// assert_eq!(operators::<StringFilters>("name", query), vec![Op::StartsWith]);
+/// assert_eq!(operators::<NumberFilters<i32>>("age", query), vec![Op::Gte, Op::Lt]);
+/// ```
+pub trait Filter: Sized {
+    /// Parses the filter for `search_id` from a query string or pairs (see
+    /// [`QueryParams`]), using `options`.
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError>;
+
+    /// Parses the filter for `search_id` from a query string.
+    fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Returns the field the filter applies to, if one is set.
+    fn filter_id(&self) -> Option<&FilterId>;
+
+    /// Returns `true` if there are no conditions to apply.
+    fn is_empty(&self) -> bool;
+
+    /// Returns every condition as its operator (e.g. [`Op::Gte`]) and the value it
+    /// compares against, in the order they are applied.
+    fn conditions(&self) -> Vec<(Op, FilterValue)>;
+
+    /// Returns the shape of every condition, as [`FromQueryFilter::shape`] writes it
+    /// after the field name: its operator (`[gte]`), plus anything else that changes the
+    /// SQL, like the number of values of a list (`[in;3]`) or a `NULL` value
+    /// (`[eq;null]`).
+    ///
+    /// [`FromQueryFilter::shape`]: crate::query_filter::FromQueryFilter::shape
+    fn condition_shapes(&self) -> Vec<String> {
+        self.conditions()
+            .iter()
+            .map(|(op, value)| condition_shape(op, value))
+            .collect()
+    }
+}
+
+/// Writes the shape of a condition for [`Filter::condition_shapes`].
+pub(crate) fn condition_shape(op: &Op, value: &FilterValue) -> String {
+    match value {
+        // each value is bound on its own
+        FilterValue::List(values) => format!("[{op};{}]", values.len()),
+        // `IS NULL` rather than a bound value
+        FilterValue::Null => format!("[{op};null]"),
+        _ => format!("[{op}]"),
+    }
+}
+
+/// Lists the conditions of every field of a filter struct, as data rather than SQL, e.g.
+/// to feed a search service.
+///
+/// The `Filterable` derive implements it from the [`Filter`] impl of every non-skipped
+/// field, so the value type of each field must implement
+/// [`ToFilterValue`](crate::ToFilterValue). [`FromQueryFilter::to_filter_map`] collects
+/// it into a map.
+///
+/// [`FromQueryFilter::to_filter_map`]: crate::query_filter::FromQueryFilter::to_filter_map
+pub trait FilterFields {
+    /// Returns the name of each field with its [`Filter::conditions`], in declaration
+    /// order.
+    fn filter_fields(&self) -> Vec<(&'static str, Vec<(Op, FilterValue)>)>;
+
+    /// Returns the name of each field with its [`Filter::condition_shapes`], in
+    /// declaration order. By default, they are read from [`FilterFields::filter_fields`].
+    fn filter_shapes(&self) -> Vec<(&'static str, Vec<String>)> {
+        self.filter_fields()
+            .into_iter()
+            .map(|(field, conditions)| {
+                let shapes = conditions
+                    .iter()
+                    .map(|(op, value)| condition_shape(op, value))
+                    .collect();
+                (field, shapes)
+            })
+            .collect()
+    }
+}
+
+/// Folds the conditions of another filter of the same type into this one, with `other`
+/// taking precedence.
+///
+/// For the field filters, a condition in `other` replaces any condition in `self` with
+/// the same operator, and the rest of both are kept. The `Filterable` derive implements
+/// it field by field, which is what [`FromQueryFilter::merge`] uses to overlay one
+/// query onto another.
+///
+/// [`FromQueryFilter::merge`]: crate::query_filter::FromQueryFilter::merge
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::{Merge, NumberFilter, NumberFilters};
+///
+/// let mut age = NumberFilters::<i32>::from_str("age", "age[gte]=18&age[lt]=65").unwrap();
+/// age.merge(NumberFilters::from_str("age", "age[gte]=21").unwrap());
+///
+/// assert_eq!(age.0, vec![NumberFilter::Lt(65), NumberFilter::Gte(21)]);
+/// ```
+pub trait Merge {
+    fn merge(&mut self, other: Self);
+}
+
+/// Walks the conditions of a filter with the column each one applies to, e.g. for an
+/// audit log of the columns a user filtered on.
+///
+/// The column is the one the SQL compares, qualified with its table (`users.age`). The
+/// field filters visit their [`Filter::conditions`] on the column of their `FilterId`,
+/// and visit nothing without one. A `DynamicFilters` visits each attribute on
+/// `column.attribute`, and a `SearchFilter` visits its term with `contains` on each
+/// column it searches. The `Filterable` derive implements it field by field, which is
+/// what [`FromQueryFilter::iter_conditions`] uses.
+///
+/// [`FromQueryFilter::iter_conditions`]: crate::query_filter::FromQueryFilter::iter_conditions
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::{FilterId, FilterValue, NumberFilters, Op, VisitConditions};
+///
+/// let id = FilterId::WithPrefix("users".to_string(), "age".to_string());
+/// let age = NumberFilters::<i32>::from_id_value(id, "age[gte]=18").unwrap();
+///
+/// let mut conditions = Vec::new();
+/// age.visit_conditions(&mut |column, op, value| conditions.push((column, op, value)));
+///
+/// assert_eq!(
+///     conditions,
+///     vec![("users.age".to_string(), Op::Gte, FilterValue::Int(18))]
+/// );
+/// ```
+pub trait VisitConditions {
+    /// Calls `visit` with the column, operator and value of every condition, in the
+    /// order they are applied.
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue));
+}
+
+/// Visits the conditions of `filter` on the column of its `FilterId`.
+pub(crate) fn visit_filter<F: Filter>(filter: &F, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+    let Some(id) = filter.filter_id() else {
+        return;
+    };
+
+    let column = id.qualified();
+    for (op, value) in filter.conditions() {
+        visit(column.clone(), op, value);
+    }
+}
+
+/// Resets one field of a filter struct by name, dropping its conditions, e.g. to turn a
+/// filter off per tenant. The `Filterable` derive implements it for every non-skipped
+/// field; [`FromQueryFilter::disable_field`] uses it.
+///
+/// [`FromQueryFilter::disable_field`]: crate::query_filter::FromQueryFilter::disable_field
+pub trait ClearField {
+    /// Resets `field` to its default, returning `false` if there is no such field.
+    fn clear_field(&mut self, field: &str) -> bool;
+}
+
+/// An optional field: a filter without any condition parses as `None`, and `None` has
+/// no conditions.
+impl<F: Filter> Filter for Option<F> {
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        let filter = F::from_id_value_with(search_id, value, options)?;
+        Ok((!filter.is_empty()).then_some(filter))
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.as_ref().and_then(Filter::filter_id)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.as_ref().is_none_or(Filter::is_empty)
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.as_ref().map_or_else(Vec::new, Filter::conditions)
+    }
+
+    fn condition_shapes(&self) -> Vec<String> {
+        self.as_ref()
+            .map_or_else(Vec::new, Filter::condition_shapes)
+    }
+}
+
+/// An absent filter has no conditions to visit.
+impl<F: VisitConditions> VisitConditions for Option<F> {
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        if let Some(filter) = self {
+            filter.visit_conditions(visit);
+        }
+    }
+}
+
+/// An absent filter takes `other` as it is.
+impl<F: Merge> Merge for Option<F> {
+    fn merge(&mut self, other: Self) {
+        match (self.as_mut(), other) {
+            (Some(this), Some(other)) => this.merge(other),
+            (None, Some(other)) => *self = Some(other),
+            (_, None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{EqualFilter, NumberFilters, StringFilters};
+
+    /// A generic helper, written once for every filter type.
+    fn describe<F: Filter>(field: &str, query: &str) -> Option<String> {
+        let filter = F::from_id_value(field.to_string().into(), query).unwrap();
+        if filter.is_empty() {
+            return None;
+        }
+
+        let conditions = filter
+            .conditions()
+            .into_iter()
+            .map(|(op, value)| format!("{op} {value}"))
+            .collect::<Vec<_>>();
+
+        Some(format!(
+            "{}: {}",
+            filter.filter_id()?.id(),
+            conditions.join(", ")
+        ))
+    }
+
+    #[test]
+    fn test_filter_generic() {
+        let query = "name[sw]=Al&age[gte]=18&age[lt]=65&active=true";
+
+        assert_eq!(
+            describe::<StringFilters>("name", query).as_deref(),
+            Some("name: starts_with Al")
+        );
+        assert_eq!(
+            describe::<NumberFilters<i32>>("age", query).as_deref(),
+            Some("age: gte 18, lt 65")
+        );
+        assert_eq!(
+            describe::<EqualFilter<bool>>("active", query).as_deref(),
+            Some("active: eq true")
+        );
+
+        assert_eq!(describe::<StringFilters>("email", query), None);
+        assert_eq!(describe::<NumberFilters<i32>>("height", query), None);
+        assert_eq!(describe::<EqualFilter<bool>>("deleted", query), None);
+    }
+
+    #[test]
+    fn test_merge() {
+        use crate::StringFilter;
+
+        let mut name =
+            StringFilters::<String>::from_str("name", "name[sw]=Al&name[ne]=Bob").unwrap();
+        name.merge(StringFilters::from_str("name", "name[ne]=Alice&name[ew]=son").unwrap());
+        assert_eq!(
+            name.0,
+            vec![
+                StringFilter::StartsWith("Al".to_string()),
+                StringFilter::Ne("Alice".to_string()),
+                StringFilter::EndsWith("son".to_string())
+            ]
+        );
+
+        let mut active = EqualFilter::<bool>::from_str("active", "active=true").unwrap();
+        active.merge(EqualFilter::from_str("active", "").unwrap());
+        assert_eq!(active.as_ref(), Some(&true));
+        active.merge(EqualFilter::from_str("active", "active=false").unwrap());
+        assert_eq!(active.as_ref(), Some(&false));
+
+        let mut age: Option<NumberFilters<i32>> = None;
+        age.merge(Some(NumberFilters::from_str("age", "age[gt]=1").unwrap()));
+        age.merge(None);
+        assert_eq!(age.map(|x| x.0.len()), Some(1));
+    }
+}
@@ -29,6 +29,15 @@ impl FilterId {
             FilterId::WithPrefixAndAlias(_, _, alias) => alias,
         }
     }
+
+    /// Returns the column qualified with its prefix (e.g. `users.age`), or just the
+    /// column when there is no prefix.
+    pub fn qualified(&self) -> String {
+        match self.prefix() {
+            Some(prefix) => format!("{}.{}", prefix, self.key()),
+            None => self.key().to_string(),
+        }
+    }
 }
 
 impl From<String> for FilterId {
@@ -60,6 +69,22 @@ mod tests {
         assert_eq!(f3.prefix(), Some("user"));
     }
 
+    #[test]
+    fn test_qualified() {
+        let f = FilterId::Alone("age".to_string());
+        assert_eq!(f.qualified(), "age");
+
+        let f = FilterId::WithPrefix("users".to_string(), "age".to_string());
+        assert_eq!(f.qualified(), "users.age");
+
+        let f = FilterId::WithPrefixAndAlias(
+            "users".to_string(),
+            "age".to_string(),
+            "years".to_string(),
+        );
+        assert_eq!(f.qualified(), "users.years");
+    }
+
     #[test]
     fn test_from_string() {
         let f: FilterId = "age".to_string().into();
@@ -0,0 +1,174 @@
+use std::fmt::Display;
+
+/// A type-erased filter value.
+///
+/// Used to inspect the value(s) a filter compares against without knowing its
+/// concrete type, e.g. for logging or rendering.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::filter_value::FilterValue;
+/// use filtrum::number_filter::NumberFilter;
+///
+/// let filter = NumberFilter::Gte(18);
+/// assert_eq!(filter.filter_value(), FilterValue::Int(18));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterValue {
+    String(String),
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    List(Vec<FilterValue>),
+    /// `NULL`, as compared against by a NULL-safe equality.
+    Null,
+}
+
+impl Display for FilterValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterValue::String(v) => write!(f, "{}", v),
+            FilterValue::Int(v) => write!(f, "{}", v),
+            FilterValue::Float(v) => write!(f, "{}", v),
+            FilterValue::Bool(v) => write!(f, "{}", v),
+            FilterValue::Null => f.write_str("null"),
+            FilterValue::List(values) => {
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Converts a filter's bound value into a [`FilterValue`].
+pub trait ToFilterValue {
+    fn to_filter_value(&self) -> FilterValue;
+}
+
+impl ToFilterValue for String {
+    fn to_filter_value(&self) -> FilterValue {
+        FilterValue::String(self.clone())
+    }
+}
+
+impl ToFilterValue for &str {
+    fn to_filter_value(&self) -> FilterValue {
+        FilterValue::String(self.to_string())
+    }
+}
+
+impl ToFilterValue for bool {
+    fn to_filter_value(&self) -> FilterValue {
+        FilterValue::Bool(*self)
+    }
+}
+
+macro_rules! impl_to_filter_value {
+    ($variant:ident as $as:ty: $($t:ty),*) => {
+        $(
+            impl ToFilterValue for $t {
+                fn to_filter_value(&self) -> FilterValue {
+                    FilterValue::$variant(*self as $as)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_filter_value!(Int as i64: i8, i16, i32, i64, u8, u16, u32);
+impl_to_filter_value!(Float as f64: f32, f64);
+
+// values that don't fit in an `i64` are kept as their decimal representation.
+macro_rules! impl_to_filter_value_wide {
+    ($($t:ty),*) => {
+        $(
+            impl ToFilterValue for $t {
+                fn to_filter_value(&self) -> FilterValue {
+                    i64::try_from(*self)
+                        .map(FilterValue::Int)
+                        .unwrap_or_else(|_| FilterValue::String(self.to_string()))
+                }
+            }
+        )*
+    };
+}
+
+impl_to_filter_value_wide!(u64, i128, u128, usize, isize);
+
+// kept as text, since converting to a float would lose the exactness decimals are for.
+#[cfg(feature = "decimal")]
+impl ToFilterValue for rust_decimal::Decimal {
+    fn to_filter_value(&self) -> FilterValue {
+        FilterValue::String(self.to_string())
+    }
+}
+
+impl<T: ToFilterValue> ToFilterValue for Option<T> {
+    fn to_filter_value(&self) -> FilterValue {
+        self.as_ref()
+            .map_or(FilterValue::Null, ToFilterValue::to_filter_value)
+    }
+}
+
+impl<T: ToFilterValue> ToFilterValue for Vec<T> {
+    fn to_filter_value(&self) -> FilterValue {
+        FilterValue::List(self.iter().map(|x| x.to_filter_value()).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        equal_filter::EqualFilter, number_filter::NumberFilter, string_filter::StringFilter,
+    };
+
+    #[test]
+    fn test_primitive_values() {
+        assert_eq!(10i32.to_filter_value(), FilterValue::Int(10));
+        assert_eq!(
+            u64::MAX.to_filter_value(),
+            FilterValue::String(u64::MAX.to_string())
+        );
+        assert_eq!(1.5f64.to_filter_value(), FilterValue::Float(1.5));
+        assert_eq!(true.to_filter_value(), FilterValue::Bool(true));
+        assert_eq!(
+            "a".to_string().to_filter_value(),
+            FilterValue::String("a".to_string())
+        );
+        assert_eq!(
+            vec![1, 2].to_filter_value(),
+            FilterValue::List(vec![FilterValue::Int(1), FilterValue::Int(2)])
+        );
+    }
+
+    #[test]
+    fn test_filter_variants() {
+        assert_eq!(NumberFilter::Lt(3u8).filter_value(), FilterValue::Int(3));
+        assert_eq!(
+            NumberFilter::Eq(2.5).filter_value(),
+            FilterValue::Float(2.5)
+        );
+        assert_eq!(
+            StringFilter::Contains("x".to_string()).filter_value(),
+            FilterValue::String("x".to_string())
+        );
+        assert_eq!(
+            EqualFilter(vec![false], None).filter_value(),
+            Some(FilterValue::Bool(false))
+        );
+        assert_eq!(EqualFilter::<bool>(Vec::new(), None).filter_value(), None);
+    }
+
+    #[test]
+    fn test_display() {
+        let v = FilterValue::List(vec![FilterValue::Int(1), FilterValue::String("a".into())]);
+        assert_eq!(v.to_string(), "1,a");
+    }
+}
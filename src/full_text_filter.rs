@@ -0,0 +1,144 @@
+use crate::{
+    common::{from_str, FromStrFilter},
+    errors::FilterParseError,
+    filter_id::FilterId,
+    op::Op,
+};
+
+/// A full-text search over a text column.
+///
+/// Parses `q[fts]=rust web` (or just `q=rust web`) into the search terms. The column that
+/// is searched doesn't have to match the parameter name, set it with [`FullTextFilter::on_column`].
+///
+/// With the `postgres` feature, applying it to a query emits
+/// `to_tsvector('english', column) @@ plainto_tsquery('english', ?)`.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::full_text_filter::FullTextFilter;
+///
+/// let filter = FullTextFilter::from_str("q", "q[fts]=rust web")
+///     .unwrap()
+///     .on_column("body")
+///     .with_language("simple")
+///     .unwrap();
+///
+/// assert_eq!(filter.query.as_deref(), Some("rust web"));
+/// assert_eq!(filter.column(), Some("body"));
+/// assert_eq!(filter.language(), "simple");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FullTextFilter {
+    /// The search terms, if present.
+    pub query: Option<String>,
+    /// The parameter the search terms are read from.
+    pub id: Option<FilterId>,
+    column: Option<String>,
+    language: String,
+}
+
+impl Default for FullTextFilter {
+    fn default() -> Self {
+        Self {
+            query: None,
+            id: None,
+            column: None,
+            language: "english".to_string(),
+        }
+    }
+}
+
+struct FullTextQuery(String);
+
+impl FromStrFilter<String> for FullTextQuery {
+    fn from_str(op: &Op, value: String) -> Result<Self, FilterParseError> {
+        match op {
+            Op::Fts | Op::Eq => Ok(FullTextQuery(value)),
+            _ => Err(FilterParseError::UnknownFilter),
+        }
+    }
+}
+
+impl FullTextFilter {
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        let query = from_str::<String, FullTextQuery>(search_id.id(), value)?
+            .into_iter()
+            .next()
+            .map(|x| x.0);
+
+        Ok(Self {
+            query,
+            id: Some(search_id),
+            ..Self::default()
+        })
+    }
+
+    /// Sets the column to search, instead of the parameter's own column.
+    pub fn on_column(mut self, column: &str) -> Self {
+        self.column = Some(column.to_string());
+        self
+    }
+
+    /// Sets the text search configuration (`'english'` by default).
+    ///
+    /// The name is written into the SQL as a literal, so only ASCII letters, digits and
+    /// `_` are accepted.
+    pub fn with_language(mut self, language: &str) -> Result<Self, FilterParseError> {
+        if language.is_empty()
+            || !language
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(FilterParseError::Value(None));
+        }
+
+        self.language = language.to_string();
+        Ok(self)
+    }
+
+    /// Returns the column being searched.
+    pub fn column(&self) -> Option<&str> {
+        self.column
+            .as_deref()
+            .or_else(|| self.id.as_ref().map(|x| x.key()))
+    }
+
+    /// Returns the text search configuration.
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_text_from_str() {
+        let f = FullTextFilter::from_str("q", "q[fts]=rust web&other=1").unwrap();
+        assert_eq!(f.query.as_deref(), Some("rust web"));
+        assert_eq!(f.column(), Some("q"));
+        assert_eq!(f.language(), "english");
+
+        let f = FullTextFilter::from_str("q", "q=rust").unwrap();
+        assert_eq!(f.query.as_deref(), Some("rust"));
+
+        let f = FullTextFilter::from_str("q", "other=1").unwrap();
+        assert_eq!(f.query, None);
+
+        assert!(FullTextFilter::from_str("q", "q[gt]=1").is_err());
+    }
+
+    #[test]
+    fn test_full_text_language() {
+        let f = FullTextFilter::default();
+        assert!(f.clone().with_language("spanish").is_ok());
+        assert!(f.clone().with_language("english'); --").is_err());
+        assert!(f.with_language("").is_err());
+    }
+}
@@ -0,0 +1,349 @@
+use std::{borrow::Cow, str::FromStr};
+
+use async_graphql::{
+    indexmap::IndexMap,
+    registry::{Deprecation, MetaInputValue, MetaType, MetaTypeId, Registry},
+    InputType, InputValueError, InputValueResult, Name, Number, Value,
+};
+
+use crate::{
+    common::{lowercase, WithFilterId},
+    errors::FilterParseError,
+    filter::FilterFields,
+    filter_value::FilterValue,
+    op::Op,
+    options::ParseOptions,
+    order_by::OrderBy,
+    query_filter::FromQueryFilter,
+    schema::{FieldSchema, Operand, OperatorSchema, ValueType},
+};
+
+/// Flattens a GraphQL input object into the query string it stands for.
+///
+/// Each field maps either to its operators, `{ age: { gte: 18, lte: 65 } }` giving
+/// `age[gte]=18&age[lte]=65`, or to a value compared with `eq`, `{ active: true }`
+/// giving `active=true`. `order_by`, `limit` and `skip` are plain values. Lists are
+/// joined with commas, for `between` and `in`, escaping a comma inside an item as `\,`.
+///
+/// A `null` is left out, as if the operator wasn't given, except under `nseq`, where it
+/// compares with `NULL`; [`to_query_with_schema`] also keeps it for the operators of a
+/// field that take one, like the `eq` of a `NullableFilter`.
+///
+/// # Example
+///
+/// ```rust
+/// use async_graphql::{value, Value};
+/// use filtrum::graphql::to_query;
+///
+/// let input = value!({ "age": { "gte": 18, "lte": null, "between": [1, 9] }, "limit": 10 });
+/// assert_eq!(to_query(&input).unwrap(), "age[gte]=18&age[between]=1,9&limit=10");
+/// ```
+pub fn to_query(input: &Value) -> Result<String, FilterParseError> {
+    to_query_with_schema(input, &[])
+}
+
+/// Like [`to_query`], keeping a `null` for the operators `schema` lists with an
+/// [`Operand::Nullable`], e.g. the fields of [`WithFilterId::filter_schema`].
+pub fn to_query_with_schema(
+    input: &Value,
+    schema: &[FieldSchema],
+) -> Result<String, FilterParseError> {
+    let Value::Object(fields) = input else {
+        return Err(FilterParseError::FilterStructure);
+    };
+
+    let mut params = Vec::new();
+    for (field, value) in fields {
+        let field_schema = schema.iter().find(|x| x.name == field.as_str());
+
+        // a bare value is compared with `eq`
+        let ops = match value {
+            Value::Object(ops) => ops.iter().map(|(op, value)| (Some(op), value)).collect(),
+            value => vec![(None, value)],
+        };
+
+        for (op, value) in ops {
+            if *value == Value::Null {
+                let key = Op::from(op.map_or(Cow::Borrowed("eq"), |x| lowercase(x)).as_ref());
+                let nullable = key == Op::NullSafeEq
+                    || field_schema.and_then(|x| x.operand(&key)) == Some(Operand::Nullable);
+
+                if !nullable {
+                    continue;
+                }
+            }
+
+            let value = query_value(value)?;
+            match op {
+                Some(op) => params.push(format!("{field}[{op}]={value}")),
+                None => params.push(format!("{field}={value}")),
+            }
+        }
+    }
+
+    Ok(params.join("&"))
+}
+
+fn query_value(value: &Value) -> Result<String, FilterParseError> {
+    let value = match value {
+        Value::List(items) => items
+            .iter()
+            .map(|x| match x {
+                Value::Null => Err(FilterParseError::value("`null` isn't allowed in a list")),
+                // a comma would split the item in two
+                x => query_scalar(x).map(|x| x.replace(',', r"\,")),
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .join(","),
+        value => query_scalar(value)?,
+    };
+
+    // an `&` would end the parameter early
+    if value.contains('&') {
+        return Err(FilterParseError::value(format!(
+            "`&` isn't allowed in a filter value: `{value}`"
+        )));
+    }
+
+    Ok(value)
+}
+
+fn query_scalar(value: &Value) -> Result<String, FilterParseError> {
+    match value {
+        Value::Null => Ok("null".to_string()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::String(s) => Ok(s.clone()),
+        Value::Boolean(b) => Ok(b.to_string()),
+        Value::Enum(name) => Ok(name.to_string()),
+        Value::List(_) | Value::Binary(_) | Value::Object(_) => {
+            Err(FilterParseError::FilterStructure)
+        }
+    }
+}
+
+impl<T: Default> FromQueryFilter<T>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId,
+{
+    /// Parses a GraphQL input object, laid out as described on [`to_query`], into the
+    /// same filter as the query string it stands for.
+    pub fn from_graphql(input: &Value) -> Result<Self, FilterParseError> {
+        Self::from_graphql_with(input, &ParseOptions::default())
+    }
+
+    /// Like [`FromQueryFilter::from_graphql`], using `options` as
+    /// [`FromQueryFilter::from_str_with`] does.
+    pub fn from_graphql_with(
+        input: &Value,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        let query = to_query_with_schema(input, &T::filter_schema())?;
+        Self::from_str_with(&query, options)
+    }
+}
+
+/// The operators of `field` offered in the GraphQL input type: the ones
+/// [`WithFilterId::allowed_ops`] allows, except `range`, whose bounds are written in the
+/// query string syntax.
+fn graphql_operators<T: WithFilterId>(field: &FieldSchema) -> Vec<&OperatorSchema> {
+    let allowed = T::allowed_ops(field.name);
+
+    field
+        .operators
+        .iter()
+        .filter(|x| x.operand != Operand::Range)
+        .filter(|x| allowed.is_none_or(|allowed| allowed.contains(&x.op)))
+        .collect()
+}
+
+fn scalar_type<S: InputType>(registry: &mut Registry) -> String {
+    S::create_type_info(registry);
+    S::type_name().into_owned()
+}
+
+fn input_value(name: &str, ty: String) -> MetaInputValue {
+    MetaInputValue {
+        name: name.to_string(),
+        description: None,
+        ty,
+        deprecation: Deprecation::NoDeprecated,
+        default_value: None,
+        visible: None,
+        inaccessible: false,
+        tags: Vec::new(),
+        is_secret: false,
+        directive_invocations: Vec::new(),
+    }
+}
+
+fn input_object(
+    name: String,
+    description: String,
+    input_fields: IndexMap<String, MetaInputValue>,
+    rust_typename: &'static str,
+) -> MetaType {
+    MetaType::InputObject {
+        name,
+        description: Some(description),
+        input_fields,
+        visible: None,
+        inaccessible: false,
+        tags: Vec::new(),
+        rust_typename: Some(rust_typename),
+        oneof: false,
+        directive_invocations: Vec::new(),
+    }
+}
+
+fn graphql_value(value: FilterValue) -> Value {
+    match value {
+        FilterValue::String(v) => Value::String(v),
+        FilterValue::Int(v) => Value::Number(v.into()),
+        FilterValue::Float(v) => Number::from_f64(v).map_or(Value::Null, Value::Number),
+        FilterValue::Bool(v) => Value::Boolean(v),
+        FilterValue::List(values) => Value::List(values.into_iter().map(graphql_value).collect()),
+        FilterValue::Null => Value::Null,
+    }
+}
+
+/// Takes a `FromQueryFilter<T>` as a GraphQL argument, given as an input object laid out
+/// as described on [`to_query`].
+///
+/// It is registered as an input object named after `T` with an `Input` suffix, e.g.
+/// `UserFilterInput`, built from [`WithFilterId::filter_schema`]. Each field is an
+/// input object of its operators, like `UserFilterAgeInput` with `gte: Int`, and
+/// `order_by`, `limit` and `skip` are plain values. `between` and the lists take a list
+/// of values, and a field without any operator, like a `DynamicFilters`, is left out.
+impl<T> InputType for FromQueryFilter<T>
+where
+    T: FromStr<Err = FilterParseError> + WithFilterId + FilterFields + Default + Send + Sync,
+{
+    type RawValueType = Self;
+
+    fn type_name() -> Cow<'static, str> {
+        let name = std::any::type_name::<T>();
+        let name = name.split('<').next().unwrap_or(name);
+        let name = name.rsplit("::").next().unwrap_or(name);
+        Cow::Owned(format!("{name}Input"))
+    }
+
+    fn create_type_info(registry: &mut Registry) -> String {
+        registry.create_input_type::<Self, _>(MetaTypeId::InputObject, |registry| {
+            let name = Self::type_name();
+            let prefix = name.strip_suffix("Input").unwrap_or(&name);
+            let rust_typename = std::any::type_name::<Self>();
+
+            let mut input_fields = IndexMap::new();
+            for field in T::filter_schema() {
+                let operators = graphql_operators::<T>(&field);
+                if operators.is_empty() {
+                    continue;
+                }
+
+                let value = match field.value {
+                    ValueType::String => scalar_type::<String>(registry),
+                    ValueType::Int => scalar_type::<i32>(registry),
+                    ValueType::Float => scalar_type::<f64>(registry),
+                    ValueType::Bool => scalar_type::<bool>(registry),
+                };
+
+                let ops = operators
+                    .into_iter()
+                    .map(|x| {
+                        let op = x.op.to_string();
+                        let ty = match x.operand {
+                            Operand::Value | Operand::Nullable => value.clone(),
+                            Operand::Pair | Operand::List => format!("[{value}!]"),
+                            Operand::Length => scalar_type::<i32>(registry),
+                            Operand::Range => unreachable!("`range` isn't offered"),
+                        };
+
+                        (op.clone(), input_value(&op, ty))
+                    })
+                    .collect();
+
+                let pascal = field
+                    .name
+                    .split('_')
+                    .map(|x| {
+                        let mut chars = x.chars();
+                        chars.next().map_or_else(String::new, |c| {
+                            c.to_uppercase().chain(chars).collect::<String>()
+                        })
+                    })
+                    .collect::<String>();
+                let ty = format!("{prefix}{pascal}Input");
+
+                registry.types.entry(ty.clone()).or_insert_with(|| {
+                    let description = format!("The operators `{}` is filtered with.", field.name);
+                    input_object(ty.clone(), description, ops, rust_typename)
+                });
+
+                input_fields.insert(field.name.to_string(), input_value(field.name, ty));
+            }
+
+            for (param, ty) in [
+                ("order_by", scalar_type::<String>(registry)),
+                ("limit", scalar_type::<i32>(registry)),
+                ("skip", scalar_type::<i32>(registry)),
+            ] {
+                input_fields.insert(param.to_string(), input_value(param, ty));
+            }
+
+            let description = "Filters as an object of fields to their operators.".to_string();
+            input_object(name.to_string(), description, input_fields, rust_typename)
+        })
+    }
+
+    fn parse(value: Option<Value>) -> InputValueResult<Self> {
+        let value = value.unwrap_or_else(|| Value::Object(Default::default()));
+        Self::from_graphql(&value).map_err(InputValueError::custom)
+    }
+
+    /// Lays out the current filters, sort and pagination, leaving out the operators
+    /// that aren't in the input type, like a negated one.
+    fn to_value(&self) -> Value {
+        let schema = T::filter_schema();
+        let mut fields = IndexMap::new();
+
+        for (field, conditions) in self.inner.filter_fields() {
+            let Some(field_schema) = schema.iter().find(|x| x.name == field) else {
+                continue;
+            };
+
+            let operators = graphql_operators::<T>(field_schema);
+            let ops = conditions
+                .into_iter()
+                .filter(|(op, _)| operators.iter().any(|x| x.op == *op))
+                .map(|(op, value)| (Name::new(op.to_string()), graphql_value(value)))
+                .collect::<IndexMap<_, _>>();
+
+            if !ops.is_empty() {
+                fields.insert(Name::new(field), Value::Object(ops));
+            }
+        }
+
+        let order_by = match &self.order_by {
+            Some(OrderBy::Asc(id)) => Some(id.id().to_string()),
+            Some(OrderBy::Desc(id)) => Some(format!("-{}", id.id())),
+            // a random sort is only written as `order_by[rand]`
+            Some(OrderBy::Random) | None => None,
+        };
+
+        if let Some(order_by) = order_by {
+            fields.insert(Name::new("order_by"), Value::String(order_by));
+        }
+
+        for (param, value) in [("limit", self.limit_value()), ("skip", self.skip_value())] {
+            if let Some(value) = value {
+                fields.insert(Name::new(param), Value::Number(value.into()));
+            }
+        }
+
+        Value::Object(fields)
+    }
+
+    fn as_raw_value(&self) -> Option<&Self::RawValueType> {
+        Some(self)
+    }
+}
@@ -0,0 +1,246 @@
+use std::str::FromStr;
+
+use crate::{
+    common::{from_str_with, split_list, FromParam, QueryParams},
+    errors::{BoxError, FilterParseError},
+    filter::{visit_filter, Filter, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    op::Op,
+    options::ParseOptions,
+};
+
+/// A membership condition on a list of values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InFilter<T> {
+    /// The column is one of the values (`= ANY(?)` on Postgres, `IN (?, ?)` elsewhere).
+    /// Query param: `field[in]=a,b` or `field=a,b` (inferred).
+    In(Vec<T>),
+    /// The column is none of the values (`<> ALL(?)` on Postgres, `NOT IN (?, ?)`
+    /// elsewhere). Query param: `field[not_in]=a,b`, `field[nin]=a,b` or `field[!in]=a,b`.
+    NotIn(Vec<T>),
+}
+
+impl<T> InFilter<T> {
+    /// Returns the operator of this filter, e.g. [`Op::NotIn`].
+    pub fn operator(&self) -> Op {
+        match self {
+            InFilter::In(_) => Op::In,
+            InFilter::NotIn(_) => Op::NotIn,
+        }
+    }
+
+    /// Returns the values of the filter.
+    pub fn values(&self) -> &[T] {
+        match self {
+            InFilter::In(v) | InFilter::NotIn(v) => v,
+        }
+    }
+}
+
+impl<T> FromParam<ListOperand<T>> for InFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        let values = split_list(value)
+            .iter()
+            .map(|x| {
+                if x.is_empty() {
+                    return Err(FilterParseError::Value(None));
+                }
+
+                x.parse().map_err(FilterParseError::value)
+            })
+            .collect::<Result<_, _>>()?;
+
+        let f = match op {
+            Op::Eq | Op::In => InFilter::In(values),
+            Op::NotIn => InFilter::NotIn(values),
+            Op::Not(op) if **op == Op::In => InFilter::NotIn(values),
+            _ => Err(FilterParseError::UnknownFilter)?,
+        };
+
+        Ok(f)
+    }
+}
+
+/// The raw values of an [`InFilter`], separated by commas, which are only parsed once the
+/// operator is known, so a parse error keeps its source. A comma that is part of a value
+/// is written escaped, as `\,`. Each value is parsed from a slice of the query.
+pub(crate) struct ListOperand<T>(std::marker::PhantomData<T>);
+
+/// A collection of membership filters applied to a specific field.
+///
+/// With SQLx on Postgres, the whole list is bound as a single array parameter, so large
+/// lists don't need a placeholder per value.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::in_filter::{InFilter, InFilters};
+///
+/// let query = "status[in]=active,pending&id[not_in]=3,4";
+/// let status = InFilters::<String>::from_str("status", query).unwrap();
+/// let id = InFilters::<i64>::from_str("id", query).unwrap();
+///
+/// assert_eq!(
+///     status.0,
+///     vec![InFilter::In(vec!["active".to_string(), "pending".to_string()])]
+/// );
+/// assert_eq!(id.0, vec![InFilter::NotIn(vec![3, 4])]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Default)]
+pub struct InFilters<T>(pub Vec<InFilter<T>>, pub Option<FilterId>);
+
+impl<T> InFilters<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    /// Parses membership filters from a query string for a specific search ID.
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    /// Parses membership filters from a query string for a specific `FilterId`.
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Parses membership filters from a query string for a specific `FilterId`, using
+    /// `options`.
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        from_str_with::<ListOperand<T>, _>(search_id.id(), value, options)
+            .map(|x| Self(x, Some(search_id)))
+    }
+}
+
+impl<T> InFilters<T> {
+    /// Returns `true` if no filters were parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the `FilterId` the filters apply to.
+    pub fn with_id(self, search_id: FilterId) -> Self {
+        Self(self.0, Some(search_id))
+    }
+}
+
+impl<T> Filter for InFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        InFilters::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.0
+            .iter()
+            .map(|x| {
+                let values = x.values().iter().map(|x| x.to_filter_value()).collect();
+                (x.operator(), FilterValue::List(values))
+            })
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for InFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
+
+impl<T> Merge for InFilters<T> {
+    fn merge(&mut self, other: Self) {
+        self.0
+            .retain(|x| !other.0.iter().any(|y| y.operator() == x.operator()));
+        self.0.extend(other.0);
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_filter_parsing() {
+        let f = InFilters::<i32>::from_str("id", "id[in]=1,2,3&id[NIN]=4&id[!in]=5,6").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                InFilter::In(vec![1, 2, 3]),
+                InFilter::NotIn(vec![4]),
+                InFilter::NotIn(vec![5, 6])
+            ]
+        );
+
+        let f = InFilters::<i32>::from_str("id", "id=7,8").unwrap();
+        assert_eq!(f.0, vec![InFilter::In(vec![7, 8])]);
+
+        let f = InFilters::<String>::from_str("tag", "tag[not_in]=a\\,b,c").unwrap();
+        assert_eq!(
+            f.0,
+            vec![InFilter::NotIn(vec!["a,b".to_string(), "c".to_string()])]
+        );
+
+        assert!(matches!(
+            InFilters::<i32>::from_str("id", "id[in]=1,,2"),
+            Err(FilterParseError::Value(_))
+        ));
+        assert!(matches!(
+            InFilters::<i32>::from_str("id", "id[in]=1,x"),
+            Err(FilterParseError::Value(Some(_)))
+        ));
+        assert!(matches!(
+            InFilters::<i32>::from_str("id", "id[gte]=1"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+
+    #[test]
+    fn test_in_filter_conditions_and_merge() {
+        let mut f = InFilters::<i32>::from_str("id", "id[in]=1,2&id[nin]=3").unwrap();
+        assert_eq!(
+            f.conditions(),
+            vec![
+                (
+                    Op::In,
+                    FilterValue::List(vec![FilterValue::Int(1), FilterValue::Int(2)])
+                ),
+                (Op::NotIn, FilterValue::List(vec![FilterValue::Int(3)]))
+            ]
+        );
+
+        f.merge(InFilters::from_str("id", "id[in]=9").unwrap());
+        assert_eq!(f.0, vec![InFilter::NotIn(vec![3]), InFilter::In(vec![9])]);
+    }
+}
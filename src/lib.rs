@@ -1,24 +1,50 @@
+pub mod array_filter;
 pub mod common;
+pub mod dynamic_filter;
 pub mod equal_filter;
 pub mod errors;
+pub mod filter;
 pub mod filter_id;
+pub mod filter_value;
+pub mod full_text_filter;
+pub mod in_filter;
 pub mod limit;
+pub mod nullable_filter;
 pub mod number_filter;
+pub mod op;
+pub mod options;
 pub mod order_by;
 pub mod query_filter;
 pub(crate) mod regex;
+pub mod schema;
+pub mod search_filter;
 pub mod skip;
+pub mod sort;
+pub mod sortable;
 pub mod string_filter;
 
+pub use array_filter::*;
 pub use common::*;
+pub use dynamic_filter::*;
 pub use equal_filter::*;
 pub use errors::*;
+pub use filter::*;
 pub use filter_id::*;
+pub use filter_value::*;
+pub use full_text_filter::*;
+pub use in_filter::*;
 pub use limit::*;
+pub use nullable_filter::*;
 pub use number_filter::*;
+pub use op::*;
+pub use options::*;
 pub use order_by::*;
 pub use query_filter::*;
+pub use schema::*;
+pub use search_filter::*;
 pub use skip::*;
+pub use sort::*;
+pub use sortable::*;
 pub use string_filter::*;
 
 #[cfg(feature = "axum")]
@@ -27,5 +53,14 @@ pub mod axum;
 #[cfg(feature = "sqlx")]
 pub mod sqlx;
 
+#[cfg(feature = "cursor")]
+pub mod cursor;
+
+#[cfg(feature = "sea-query")]
+pub mod sea_query;
+
+#[cfg(feature = "graphql")]
+pub mod graphql;
+
 #[cfg(feature = "derive")]
 pub use filtrum_derive::Filterable;
@@ -1,11 +1,19 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    common::{from_str, FromStrFilter},
+    common::{from_str_with, FromStrFilter, QueryParams},
     errors::FilterParseError,
+    op::Op,
+    options::ParseOptions,
 };
 
 /// Represents a limit (pagination) value.
 ///
-/// Parses `limit=N` from the query string.
+/// Parses `limit=N` from the query string. If the parameter is repeated the last value
+/// wins, unless [`ParseOptions::strict`] is set, in which case it is an error. The
+/// parameter can be renamed with [`ParseOptions::reserved`].
+///
+/// Serializes as the bare number, e.g. `20`.
 ///
 /// # Example
 ///
@@ -17,20 +25,47 @@ use crate::{
 ///
 /// assert_eq!(limit.0, 20);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Limit(pub u64);
 
 impl FromStrFilter<u64> for Limit {
-    fn from_str(_id: &str, value: u64) -> Result<Self, FilterParseError> {
+    fn from_str(_op: &Op, value: u64) -> Result<Self, FilterParseError> {
         Ok(Limit(value))
     }
 }
 
 impl Limit {
+    /// Creates a limit of `limit` rows, for queries built in code rather than parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use filtrum::limit::Limit;
+    ///
+    /// assert_eq!(Limit::new(20), Limit::from_str("limit=20").unwrap().unwrap());
+    /// ```
+    pub fn new(limit: u64) -> Self {
+        Self(limit)
+    }
+
+    // `None` when the parameter is absent, which `FromStr` can't express
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Result<Option<Self>, FilterParseError> {
-        let u = from_str("limit", value)?.first().cloned();
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        let all = from_str_with::<u64, Self>(options.reserved.limit, value, options)?;
 
-        Ok(u)
+        if options.strict && all.len() > 1 {
+            return Err(FilterParseError::Duplicate);
+        }
+
+        Ok(all.last().cloned())
     }
 }
 
@@ -48,4 +83,27 @@ mod tests {
         let l = Limit::from_str(qs).unwrap();
         assert!(l.is_none());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_limit_duplicates() {
+        let qs = "limit=10&limit=20";
+        let v = Limit::from_str(qs).unwrap().unwrap();
+        assert_eq!(v.0, 20);
+
+        let options = ParseOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let v = Limit::from_str_with(qs, &options);
+        assert!(matches!(v, Err(FilterParseError::Duplicate)));
+
+        let v = Limit::from_str_with("limit=10", &options).unwrap().unwrap();
+        assert_eq!(v.0, 10);
+    }
+
+    #[test]
+    fn test_limit_new() {
+        assert_eq!(Limit::new(25).0, 25);
+        assert_eq!(serde_json::to_string(&Limit::new(25)).unwrap(), "25");
+    }
+}
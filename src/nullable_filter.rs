@@ -0,0 +1,227 @@
+use std::str::FromStr;
+
+use crate::{
+    common::{from_str_with, QueryParams},
+    equal_filter::EqualFilter,
+    errors::{BoxError, FilterParseError},
+    filter::{visit_filter, Filter, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    op::Op,
+    options::ParseOptions,
+};
+
+/// The value of a [`NullableFilter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nullable<T> {
+    /// The column is `NULL`. Query param: `field=null`.
+    Null,
+    /// The column is not `NULL`. Query param: `field=!null`.
+    NotNull,
+    /// The column equals the value. Query param: `field=value`.
+    Value(T),
+}
+
+/// `null` and `!null` are matched ignoring ASCII case; anything else is parsed as a `T`.
+impl<T> FromStr for Nullable<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    type Err = FilterParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("null") {
+            return Ok(Nullable::Null);
+        }
+
+        if s.eq_ignore_ascii_case("!null") {
+            return Ok(Nullable::NotNull);
+        }
+
+        s.parse()
+            .map(Nullable::Value)
+            .map_err(FilterParseError::value)
+    }
+}
+
+/// An equality filter on a nullable column.
+///
+/// Works like [`EqualFilter`], but the value `null` filters with `IS NULL` and `!null`
+/// with `IS NOT NULL`, where `EqualFilter` can only compare with `=`. A field the client
+/// leaves out doesn't filter at all; to default to non-null rows only, give the field
+/// `#[filtrum(default_value = "!null")]`.
+///
+/// For a `NullableFilter<String>`, a value of `null` always means `IS NULL`, never the
+/// text `"null"`.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::nullable_filter::{Nullable, NullableFilter};
+///
+/// let filter = NullableFilter::<i32>::from_str("parent_id", "parent_id=null").unwrap();
+/// assert_eq!(filter.into_inner(), Some(Nullable::Null));
+///
+/// let filter = NullableFilter::<i32>::from_str("parent_id", "parent_id=5").unwrap();
+/// assert_eq!(filter.into_inner(), Some(Nullable::Value(5)));
+///
+/// let filter = NullableFilter::<i32>::from_str("parent_id", "").unwrap();
+/// assert_eq!(filter.into_inner(), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NullableFilter<T>(pub Option<Nullable<T>>, pub Option<FilterId>);
+
+impl<T> NullableFilter<T> {
+    /// Consumes the filter and returns the inner value, if present.
+    pub fn into_inner(self) -> Option<Nullable<T>> {
+        self.0
+    }
+
+    /// Returns `true` if no value was parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns a reference to the inner value, if present.
+    pub fn as_ref(&self) -> Option<&Nullable<T>> {
+        self.0.as_ref()
+    }
+}
+
+impl<T> NullableFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        // a single value, so a comma is part of it rather than a list of values
+        let value =
+            from_str_with::<Nullable<T>, EqualFilter<Nullable<T>>>(search_id.id(), value, options)?
+                .into_iter()
+                .next()
+                .and_then(|x| x.into_inner());
+
+        Ok(Self(value, Some(search_id)))
+    }
+}
+
+/// `null` is an `eq` with [`FilterValue::Null`] and `!null` an `ne` with it.
+impl<T> Filter for NullableFilter<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        NullableFilter::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_none()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        match &self.0 {
+            Some(Nullable::Null) => vec![(Op::Eq, FilterValue::Null)],
+            Some(Nullable::NotNull) => vec![(Op::Ne, FilterValue::Null)],
+            Some(Nullable::Value(v)) => vec![(Op::Eq, v.to_filter_value())],
+            None => Vec::new(),
+        }
+    }
+}
+
+impl<T> VisitConditions for NullableFilter<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
+
+impl<T> Merge for NullableFilter<T> {
+    fn merge(&mut self, other: Self) {
+        if other.0.is_some() {
+            self.0 = other.0;
+        }
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nullable_filter_error_source() {
+        use std::error::Error;
+
+        let err = NullableFilter::<i32>::from_str("parent_id", "parent_id=x").unwrap_err();
+        assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+    }
+
+    #[test]
+    fn test_nullable_filter() {
+        let f = NullableFilter::<i32>::from_str("parent_id", "parent_id=null").unwrap();
+        assert_eq!(f.as_ref(), Some(&Nullable::Null));
+        assert_eq!(f.1, Some(FilterId::Alone("parent_id".to_string())));
+
+        let f = NullableFilter::<i32>::from_str("parent_id", "parent_id=NULL").unwrap();
+        assert_eq!(f.as_ref(), Some(&Nullable::Null));
+
+        let f = NullableFilter::<i32>::from_str("parent_id", "parent_id=!null").unwrap();
+        assert_eq!(f.as_ref(), Some(&Nullable::NotNull));
+
+        let f = NullableFilter::<i32>::from_str("parent_id", "parent_id=5").unwrap();
+        assert_eq!(f.as_ref(), Some(&Nullable::Value(5)));
+
+        // unlike `EqualFilter`, a comma doesn't make a list
+        let f = NullableFilter::<String>::from_str("city", "city=Paris, TX").unwrap();
+        assert_eq!(f.as_ref(), Some(&Nullable::Value("Paris, TX".to_string())));
+
+        // absent, or cleared, doesn't filter
+        let f = NullableFilter::<i32>::from_str("parent_id", "age=5").unwrap();
+        assert!(f.is_empty());
+        let f = NullableFilter::<i32>::from_str("parent_id", "parent_id=").unwrap();
+        assert!(f.is_empty());
+
+        assert!(matches!(
+            NullableFilter::<i32>::from_str("parent_id", "parent_id=nil"),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_nullable_filter_merge() {
+        let mut f = NullableFilter::<i32>::from_str("parent_id", "parent_id=!null").unwrap();
+        f.merge(NullableFilter::from_str("parent_id", "").unwrap());
+        assert_eq!(f.as_ref(), Some(&Nullable::NotNull));
+
+        f.merge(NullableFilter::from_str("parent_id", "parent_id=3").unwrap());
+        assert_eq!(f.as_ref(), Some(&Nullable::Value(3)));
+    }
+}
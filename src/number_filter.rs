@@ -2,13 +2,17 @@ use std::str::FromStr;
 
 use serde::{
     de::{self, Visitor},
-    Deserialize,
+    Deserialize, Serialize,
 };
 
 use crate::{
-    common::{from_str, FromStrFilter},
-    errors::FilterParseError,
+    common::{from_str_with, parse_pair_with, FromParam, FromStrFilter, QueryParams},
+    errors::{BoxError, FilterParseError},
+    filter::{condition_shape, visit_filter, Filter, Merge, VisitConditions},
     filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    op::Op,
+    options::ParseOptions,
 };
 
 /// Represents numerical comparison operations.
@@ -18,6 +22,10 @@ pub enum NumberFilter<T> {
     Eq(T),
     /// Not equal (`<>` or `!=`). Query param: `field[ne]=10`.
     Ne(T),
+    /// NULL-safe equal (`<=>` on MySQL, `IS NOT DISTINCT FROM` elsewhere), which also
+    /// matches when both sides are `NULL`. Query param: `field[nseq]=10`, or
+    /// `field[nseq]=null` for `None`, which renders `IS NULL`.
+    NullSafeEq(Option<T>),
     /// Greater than (`>`). Query param: `field[gt]=10`.
     Gt(T),
     /// Less than (`<`). Query param: `field[lt]=10`.
@@ -26,17 +34,204 @@ pub enum NumberFilter<T> {
     Gte(T),
     /// Less than or equal (`<=`). Query param: `field[lte]=10`.
     Lte(T),
+    /// Inclusive range (`BETWEEN ? AND ?`). Query param: `field[between]=10,20`.
+    Between(T, T),
+    /// Range with an inclusive or exclusive bound on each side, rendered as the matching
+    /// mix of `>=`/`>` and `<`/`<=`. Query param: `field[range]=[10,20)`, where `[`/`]`
+    /// include the bound and `(`/`)` exclude it.
+    Range(RangeBound<T>, RangeBound<T>),
+}
+
+/// One side of a [`NumberFilter::Range`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RangeBound<T> {
+    /// The bound itself matches, written `[` or `]`.
+    Inclusive(T),
+    /// The bound itself doesn't match, written `(` or `)`.
+    Exclusive(T),
+}
+
+impl<T> RangeBound<T> {
+    /// Returns a reference to the value of the bound.
+    pub fn value(&self) -> &T {
+        match self {
+            RangeBound::Inclusive(v) | RangeBound::Exclusive(v) => v,
+        }
+    }
+
+    /// Returns `true` if the bound itself matches.
+    pub fn is_inclusive(&self) -> bool {
+        matches!(self, RangeBound::Inclusive(_))
+    }
+
+    fn try_map<U>(
+        self,
+        f: impl Fn(T) -> Result<U, FilterParseError>,
+    ) -> Result<RangeBound<U>, FilterParseError> {
+        Ok(match self {
+            RangeBound::Inclusive(v) => RangeBound::Inclusive(f(v)?),
+            RangeBound::Exclusive(v) => RangeBound::Exclusive(f(v)?),
+        })
+    }
+}
+
+/// Parses a range like `[10,20)` into its lower and upper bound.
+fn parse_range_with<T>(
+    value: &str,
+    parse: impl Fn(&str) -> Result<T, FilterParseError>,
+) -> Result<(RangeBound<T>, RangeBound<T>), FilterParseError> {
+    let malformed = || {
+        FilterParseError::value(format!(
+            "expected a range like `[low,high)`, found `{value}`"
+        ))
+    };
+
+    let mut chars = value.chars();
+    let (Some(open), Some(close)) = (chars.next(), chars.next_back()) else {
+        return Err(malformed());
+    };
+
+    let (low, high) = parse_pair_with(chars.as_str(), parse).map_err(|err| match err {
+        FilterParseError::Value(None) => malformed(),
+        err => err,
+    })?;
+
+    let low = match open {
+        '[' => RangeBound::Inclusive(low),
+        '(' => RangeBound::Exclusive(low),
+        _ => return Err(malformed()),
+    };
+    let high = match close {
+        ']' => RangeBound::Inclusive(high),
+        ')' => RangeBound::Exclusive(high),
+        _ => return Err(malformed()),
+    };
+
+    Ok((low, high))
+}
+
+/// Parses a single number of a filter, rejecting `NaN` and the infinities, which
+/// `f64::from_str` accepts but a column can't be compared with.
+fn parse_number<T>(value: &str) -> Result<T, FilterParseError>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    if is_non_finite::<T>(value) {
+        return Err(FilterParseError::value(format!(
+            "expected a finite number, found `{value}`"
+        )));
+    }
+
+    value.parse().map_err(FilterParseError::value)
+}
+
+/// Returns `true` if `value` parses to `NaN` or an infinity as a float the size of `T`:
+/// spelled out, like `-inf`, or a number out of its range, like `1e999`.
+///
+/// `T` is only known to parse from a string, so the value is read as an `f32` for a
+/// 4-byte `T` and as an `f64` otherwise. A float `T` is one of the two, and any other
+/// `T` fails to parse such a value anyway.
+fn is_non_finite<T>(value: &str) -> bool {
+    match size_of::<T>() {
+        4 => value.parse::<f32>().is_ok_and(|x| !x.is_finite()),
+        _ => value.parse::<f64>().is_ok_and(|x| !x.is_finite()),
+    }
+}
+
+impl<T> NumberFilter<T> {
+    /// Returns the operator (e.g. [`Op::Gte`]), which displays as its canonical query
+    /// key.
+    pub fn operator(&self) -> Op {
+        match self {
+            NumberFilter::Eq(_) => Op::Eq,
+            NumberFilter::Ne(_) => Op::Ne,
+            NumberFilter::NullSafeEq(_) => Op::NullSafeEq,
+            NumberFilter::Gt(_) => Op::Gt,
+            NumberFilter::Lt(_) => Op::Lt,
+            NumberFilter::Gte(_) => Op::Gte,
+            NumberFilter::Lte(_) => Op::Lte,
+            NumberFilter::Between(_, _) => Op::Between,
+            NumberFilter::Range(_, _) => Op::Range,
+        }
+    }
+
+    /// Returns a reference to the value the filter compares against, or `None` for a
+    /// NULL-safe comparison with `NULL`. For `Between` and `Range` this is the lower bound.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            NumberFilter::Eq(v)
+            | NumberFilter::Ne(v)
+            | NumberFilter::Gt(v)
+            | NumberFilter::Lt(v)
+            | NumberFilter::Gte(v)
+            | NumberFilter::Lte(v)
+            | NumberFilter::Between(v, _) => Some(v),
+            NumberFilter::NullSafeEq(v) => v.as_ref(),
+            NumberFilter::Range(low, _) => Some(low.value()),
+        }
+    }
+
+    /// Converts the value(s) with `f`, keeping the operator.
+    pub fn try_map<U, E>(
+        self,
+        f: impl Fn(T) -> Result<U, E>,
+    ) -> Result<NumberFilter<U>, FilterParseError>
+    where
+        E: Into<BoxError>,
+    {
+        let f = |x| f(x).map_err(FilterParseError::value);
+
+        Ok(match self {
+            NumberFilter::Eq(v) => NumberFilter::Eq(f(v)?),
+            NumberFilter::Ne(v) => NumberFilter::Ne(f(v)?),
+            NumberFilter::NullSafeEq(v) => NumberFilter::NullSafeEq(v.map(f).transpose()?),
+            NumberFilter::Gt(v) => NumberFilter::Gt(f(v)?),
+            NumberFilter::Lt(v) => NumberFilter::Lt(f(v)?),
+            NumberFilter::Gte(v) => NumberFilter::Gte(f(v)?),
+            NumberFilter::Lte(v) => NumberFilter::Lte(f(v)?),
+            NumberFilter::Between(low, high) => NumberFilter::Between(f(low)?, f(high)?),
+            NumberFilter::Range(low, high) => {
+                NumberFilter::Range(low.try_map(f)?, high.try_map(f)?)
+            }
+        })
+    }
+
+    /// Returns the value the filter compares against as a [`FilterValue`]. For `Between`
+    /// and `Range` this is a list of both bounds.
+    pub fn filter_value(&self) -> FilterValue
+    where
+        T: ToFilterValue,
+    {
+        match self {
+            NumberFilter::Between(low, high) => {
+                FilterValue::List(vec![low.to_filter_value(), high.to_filter_value()])
+            }
+            NumberFilter::Range(low, high) => FilterValue::List(vec![
+                low.value().to_filter_value(),
+                high.value().to_filter_value(),
+            ]),
+            NumberFilter::NullSafeEq(v) => v.to_filter_value(),
+            NumberFilter::Eq(v)
+            | NumberFilter::Ne(v)
+            | NumberFilter::Gt(v)
+            | NumberFilter::Lt(v)
+            | NumberFilter::Gte(v)
+            | NumberFilter::Lte(v) => v.to_filter_value(),
+        }
+    }
 }
 
 impl<T> FromStrFilter<T> for NumberFilter<T> {
-    fn from_str(id: &str, value: T) -> Result<Self, FilterParseError> {
-        let f = match id {
-            "eq" => NumberFilter::Eq(value),
-            "ne" => NumberFilter::Ne(value),
-            "gt" => NumberFilter::Gt(value),
-            "lt" => NumberFilter::Lt(value),
-            "gte" => NumberFilter::Gte(value),
-            "lte" => NumberFilter::Lte(value),
+    fn from_str(op: &Op, value: T) -> Result<Self, FilterParseError> {
+        let f = match op {
+            Op::Eq => NumberFilter::Eq(value),
+            Op::Ne => NumberFilter::Ne(value),
+            Op::NullSafeEq => NumberFilter::NullSafeEq(Some(value)),
+            Op::Gt => NumberFilter::Gt(value),
+            Op::Lt => NumberFilter::Lt(value),
+            Op::Gte => NumberFilter::Gte(value),
+            Op::Lte => NumberFilter::Lte(value),
             _ => Err(FilterParseError::UnknownFilter)?,
         };
 
@@ -44,6 +239,40 @@ impl<T> FromStrFilter<T> for NumberFilter<T> {
     }
 }
 
+/// The raw value of a number filter, which is only parsed once the operator is known,
+/// since `between` and `range` take two values instead of one. It is read straight from
+/// the query, without a copy.
+pub(crate) struct NumberOperand<T>(std::marker::PhantomData<T>);
+
+impl<T> FromParam<NumberOperand<T>> for NumberFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        let parse = parse_number::<T>;
+
+        match op {
+            // `NULL` isn't a number, so it is only read here
+            Op::NullSafeEq if value.eq_ignore_ascii_case("null") => {
+                Ok(NumberFilter::NullSafeEq(None))
+            }
+            Op::Between => {
+                let (low, high) = parse_pair_with(value, parse)?;
+                Ok(NumberFilter::Between(low, high))
+            }
+            Op::Range => {
+                let (low, high) = parse_range_with(value, parse)?;
+                Ok(NumberFilter::Range(low, high))
+            }
+            _ => {
+                let value = parse(value)?;
+                <Self as FromStrFilter<T>>::from_str(op, value)
+            }
+        }
+    }
+}
+
 /// A collection of number filters applied to a specific field.
 ///
 /// # Example
@@ -60,7 +289,11 @@ impl<T> FromStrFilter<T> for NumberFilter<T> {
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct NumberFilters<T>(pub Vec<NumberFilter<T>>, pub Option<FilterId>);
 
-impl<T: FromStr> NumberFilters<T> {
+impl<T> NumberFilters<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
     /// Parses number filters from a query string for a specific search ID.
     pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
         Self::from_id_value(search_id.to_string().into(), value)
@@ -68,7 +301,210 @@ impl<T: FromStr> NumberFilters<T> {
 
     /// Parses number filters from a query string for a specific `FilterId`.
     pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
-        from_str(search_id.id(), value).map(|x| Self(x, Some(search_id)))
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Parses number filters from a query string for a specific `FilterId`, using `options`.
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        from_str_with::<NumberOperand<T>, _>(search_id.id(), value, options)
+            .map(|x| Self(x, Some(search_id)))
+    }
+
+    /// Returns `true` if no filters were parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the `FilterId` the filters apply to.
+    pub fn with_id(self, search_id: FilterId) -> Self {
+        Self(self.0, Some(search_id))
+    }
+}
+
+impl<T> NumberFilters<T> {
+    /// Creates a collection of `filters` applied to the field `search_id`.
+    pub fn new(filters: Vec<NumberFilter<T>>, search_id: FilterId) -> Self {
+        Self(filters, Some(search_id))
+    }
+
+    /// Starts an empty collection for `column`, to add conditions to fluently.
+    ///
+    /// ```rust
+    /// use filtrum::number_filter::{NumberFilter, NumberFilters};
+    ///
+    /// let age = NumberFilters::for_column("age").gte(18).less_than(65);
+    ///
+    /// assert_eq!(age.0, vec![NumberFilter::Gte(18), NumberFilter::Lt(65)]);
+    /// assert_eq!(age.1.unwrap().id(), "age");
+    /// ```
+    pub fn for_column(column: &str) -> Self {
+        Self(Vec::new(), Some(column.to_string().into()))
+    }
+
+    /// Adds `filter` to the collection.
+    pub fn and(mut self, filter: NumberFilter<T>) -> Self {
+        self.0.push(filter);
+        self
+    }
+
+    /// Adds a `= value` condition.
+    pub fn equal_to(self, value: T) -> Self {
+        self.and(NumberFilter::Eq(value))
+    }
+
+    /// Adds a `<> value` condition.
+    pub fn not_equal_to(self, value: T) -> Self {
+        self.and(NumberFilter::Ne(value))
+    }
+
+    /// Adds a NULL-safe `= value` condition; `None` matches the `NULL` rows.
+    pub fn null_safe_eq(self, value: impl Into<Option<T>>) -> Self {
+        self.and(NumberFilter::NullSafeEq(value.into()))
+    }
+
+    /// Adds a `> value` condition.
+    pub fn greater_than(self, value: T) -> Self {
+        self.and(NumberFilter::Gt(value))
+    }
+
+    /// Adds a `< value` condition.
+    pub fn less_than(self, value: T) -> Self {
+        self.and(NumberFilter::Lt(value))
+    }
+
+    /// Adds a `>= value` condition.
+    pub fn gte(self, value: T) -> Self {
+        self.and(NumberFilter::Gte(value))
+    }
+
+    /// Adds a `<= value` condition.
+    pub fn lte(self, value: T) -> Self {
+        self.and(NumberFilter::Lte(value))
+    }
+
+    /// Adds a `BETWEEN low AND high` condition.
+    pub fn between(self, low: T, high: T) -> Self {
+        self.and(NumberFilter::Between(low, high))
+    }
+
+    /// Adds a range condition with an inclusive or exclusive bound on each side.
+    pub fn range(self, low: RangeBound<T>, high: RangeBound<T>) -> Self {
+        self.and(NumberFilter::Range(low, high))
+    }
+
+    /// Converts every value with `f`, keeping the `FilterId`. A failed conversion is a
+    /// `FilterParseError::Value`.
+    ///
+    /// Useful to bind a type the database driver doesn't support, e.g. a `u64` as an
+    /// `i64` on Postgres:
+    ///
+    /// ```rust
+    /// use filtrum::number_filter::{NumberFilter, NumberFilters};
+    ///
+    /// let filters = NumberFilters::<u64>::from_str("id", "id[gt]=10").unwrap();
+    /// let filters = filters.try_map(i64::try_from).unwrap();
+    ///
+    /// assert_eq!(filters.0, vec![NumberFilter::Gt(10i64)]);
+    /// ```
+    pub fn try_map<U, E>(
+        self,
+        f: impl Fn(T) -> Result<U, E>,
+    ) -> Result<NumberFilters<U>, FilterParseError>
+    where
+        E: Into<BoxError>,
+    {
+        let filters = self
+            .0
+            .into_iter()
+            .map(|x| x.try_map(&f))
+            .collect::<Result<_, _>>()?;
+
+        Ok(NumberFilters(filters, self.1))
+    }
+}
+
+/// Wraps the filters without a `FilterId`; attach one with [`NumberFilters::with_id`].
+impl<T> From<Vec<NumberFilter<T>>> for NumberFilters<T> {
+    fn from(value: Vec<NumberFilter<T>>) -> Self {
+        Self(value, None)
+    }
+}
+
+impl<T> From<NumberFilters<T>> for Vec<NumberFilter<T>> {
+    fn from(value: NumberFilters<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> Filter for NumberFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        NumberFilters::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.0
+            .iter()
+            .map(|x| (x.operator(), x.filter_value()))
+            .collect()
+    }
+
+    /// A `range` keeps its brackets, e.g. `[range;[)]`, as each one renders its own
+    /// comparison.
+    fn condition_shapes(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|x| match x {
+                NumberFilter::Range(low, high) => {
+                    let low = if low.is_inclusive() { '[' } else { '(' };
+                    let high = if high.is_inclusive() { ']' } else { ')' };
+                    format!("[{};{low}{high}]", x.operator())
+                }
+                x => condition_shape(&x.operator(), &x.filter_value()),
+            })
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for NumberFilters<T>
+where
+    T: FromStr + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
+
+impl<T> Merge for NumberFilters<T> {
+    fn merge(&mut self, other: Self) {
+        // a condition from `other` replaces the one with the same operator
+        self.0
+            .retain(|x| !other.0.iter().any(|y| y.operator() == x.operator()));
+        self.0.extend(other.0);
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
     }
 }
 
@@ -95,42 +531,181 @@ where
                 formatter.write_str("a number filter")
             }
 
+            // a bare number is inferred as equals, e.g. `{"age": 10}`.
+            fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.to_string()
+                    .parse()
+                    .map(NumberFilter::Eq)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                v.to_string()
+                    .parse()
+                    .map(NumberFilter::Eq)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                // a JSON number is finite, but may not be as an `f32`
+                let value = v.to_string();
+                if is_non_finite::<T>(&value) {
+                    return Err(de::Error::invalid_value(de::Unexpected::Float(v), &self));
+                }
+
+                value
+                    .parse()
+                    .map(NumberFilter::Eq)
+                    .map_err(|_| de::Error::invalid_value(de::Unexpected::Float(v), &self))
+            }
+
             fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
             where
                 E: de::Error,
             {
-                let parts = v.split('=').collect::<Vec<_>>();
-
-                if parts.len() != 2 {
-                    // we infer is equals
-                    return Ok(NumberFilter::Eq(v.parse().map_err(|_|
-                        de::Error::invalid_value(
-                            de::Unexpected::Str(v),
-                            &"a number filter value in dual format",
-                        )
-                    )?));
+                // a bare value is inferred as equals, e.g. `"10"`
+                let (key, value) = v.split_once('=').unwrap_or(("eq", v));
+
+                let missing =
+                    || de::Error::custom(format_args!("missing value for number filter `{key}`"));
+
+                // `NaN` and the infinities are rejected, like in a query string
+                let finite = |x: &str| match is_non_finite::<T>(x) {
+                    true => Err(FilterParseError::Value(None)),
+                    false => x.parse().map_err(|_| FilterParseError::Value(None)),
+                };
+
+                // every key is matched here, so an unknown one is an error rather than
+                // one of the operators
+                let filter: fn(T) -> NumberFilter<T> = match Op::from(key) {
+                    Op::Eq => NumberFilter::Eq,
+                    Op::Ne => NumberFilter::Ne,
+                    Op::NullSafeEq if value.eq_ignore_ascii_case("null") => {
+                        return Ok(NumberFilter::NullSafeEq(None));
+                    }
+                    Op::NullSafeEq => |x| NumberFilter::NullSafeEq(Some(x)),
+                    Op::Gt => NumberFilter::Gt,
+                    Op::Lt => NumberFilter::Lt,
+                    Op::Gte => NumberFilter::Gte,
+                    Op::Lte => NumberFilter::Lte,
+                    Op::Between | Op::Range if value.is_empty() => return Err(missing()),
+                    Op::Between => {
+                        return parse_pair_with(value, finite)
+                            .map(|(low, high)| NumberFilter::Between(low, high))
+                            .map_err(|_| {
+                                de::Error::invalid_value(
+                                    de::Unexpected::Str(value),
+                                    &"a `low,high` pair of numbers for number filter `between`",
+                                )
+                            });
+                    }
+                    Op::Range => {
+                        return parse_range_with(value, finite)
+                            .map(|(low, high)| NumberFilter::Range(low, high))
+                            .map_err(|_| {
+                                de::Error::invalid_value(
+                                    de::Unexpected::Str(value),
+                                    &"a `[low,high)` range of numbers for number filter `range`",
+                                )
+                            });
+                    }
+                    _ => {
+                        return Err(de::Error::custom(format_args!(
+                            "unknown number filter `{key}`"
+                        )));
+                    }
+                };
+
+                if value.is_empty() {
+                    return Err(missing());
                 }
 
-                let (key, value) = (parts[0], parts[1]);
+                if is_non_finite::<T>(value) {
+                    let expected = format!("a finite number for number filter `{key}`");
+                    return Err(de::Error::invalid_value(
+                        de::Unexpected::Str(value),
+                        &expected.as_str(),
+                    ));
+                }
 
                 let value = value.parse().map_err(|err| {
-                    let error_msg = format!("a number in filter value: {:?}", err);
-                    de::Error::invalid_value(de::Unexpected::Str(value), &error_msg.as_str())
+                    let expected = format!("a number for number filter `{key}` ({err:?})");
+                    de::Error::invalid_value(de::Unexpected::Str(value), &expected.as_str())
                 })?;
 
-                match key {
-                    "eq" => Ok(NumberFilter::Eq(value)),
-                    "ne" => Ok(NumberFilter::Ne(value)),
-                    "gt" => Ok(NumberFilter::Gt(value)),
-                    "lt" => Ok(NumberFilter::Lt(value)),
-                    "gte" => Ok(NumberFilter::Gte(value)),
-                    "lte" => Ok(NumberFilter::Lte(value)),
-                    _ => Err(de::Error::custom("unknown number filter")),
-                }
+                Ok(filter(value))
             }
         }
 
-        deserializer.deserialize_str(NumberFilterVisitor(std::marker::PhantomData))
+        deserializer.deserialize_any(NumberFilterVisitor(std::marker::PhantomData))
+    }
+}
+
+impl<T> Serialize for NumberFilter<T>
+where
+    T: std::fmt::Display,
+{
+    // always emits the explicit `key=value` form, so it reads back the same.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            NumberFilter::Between(low, high) => {
+                serializer.collect_str(&format_args!("{}={},{}", self.operator(), low, high))
+            }
+            NumberFilter::Range(low, high) => serializer.collect_str(&format_args!(
+                "{}={}{},{}{}",
+                self.operator(),
+                if low.is_inclusive() { '[' } else { '(' },
+                low.value(),
+                high.value(),
+                if high.is_inclusive() { ']' } else { ')' },
+            )),
+            _ => match self.value() {
+                Some(v) => serializer.collect_str(&format_args!("{}={}", self.operator(), v)),
+                // a NULL-safe comparison with `NULL`
+                None => serializer.collect_str(&format_args!("{}=null", self.operator())),
+            },
+        }
+    }
+}
+
+/// Deserializes from a sequence of filters, e.g. `["gte=18", "lt=65"]`.
+///
+/// The field the filters belong to is not part of the serialized form, so the
+/// resulting collection has no `FilterId`; attach one with [`NumberFilters::with_id`].
+impl<'de, T> Deserialize<'de> for NumberFilters<T>
+where
+    NumberFilter<T>: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<NumberFilter<T>>::deserialize(deserializer).map(|x| Self(x, None))
+    }
+}
+
+/// Serializes into a sequence of filters, e.g. `["gte=18", "lt=65"]`.
+impl<T> Serialize for NumberFilters<T>
+where
+    T: std::fmt::Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
     }
 }
 
@@ -148,6 +723,111 @@ mod tests {
         assert!(filters.contains(&NumberFilter::Lt(100)));
     }
 
+    #[test]
+    fn test_number_filter_float_syntax() {
+        let parse = |qs| NumberFilters::<f64>::from_str("price", qs);
+
+        assert_eq!(
+            parse("price[lte]=-1.5e3").unwrap().0,
+            vec![NumberFilter::Lte(-1500.0)]
+        );
+        assert_eq!(parse("price=1e-9").unwrap().0, vec![NumberFilter::Eq(1e-9)]);
+        assert_eq!(
+            parse("price[gt]=+.5&price[between]=-1E2,1e+2").unwrap().0,
+            vec![NumberFilter::Gt(0.5), NumberFilter::Between(-100.0, 100.0)]
+        );
+
+        let f = NumberFilters::<i32>::from_str("age", "age[gte]=-5").unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Gte(-5)]);
+        assert!(matches!(
+            NumberFilters::<i32>::from_str("age", "age=1e3"),
+            Err(FilterParseError::Value(Some(_)))
+        ));
+
+        // `f64` reads these, but no column compares with them
+        for qs in [
+            "price=NaN",
+            "price[gte]=nan",
+            "price[lt]=inf",
+            "price[gt]=-inf",
+            "price[lte]=+Infinity",
+            "price[between]=0,inf",
+            "price[range]=[0,inf)",
+            "price[gte]=1e999",
+            "price[lt]=-1e400",
+            "price[between]=0,1e309",
+        ] {
+            let err = parse(qs).unwrap_err();
+            assert!(matches!(err, FilterParseError::Value(Some(_))), "{qs}");
+        }
+
+        // out of the range of an `f32`, but not of an `f64`
+        let err = NumberFilters::<f32>::from_str("price", "price[gte]=1e39").unwrap_err();
+        assert!(matches!(err, FilterParseError::Value(Some(_))));
+        assert_eq!(
+            parse("price[gte]=1e39").unwrap().0,
+            vec![NumberFilter::Gte(1e39)]
+        );
+
+        let f: NumberFilter<f64> = serde_json::from_str(r#""lte=-1.5e3""#).unwrap();
+        assert_eq!(f, NumberFilter::Lte(-1500.0));
+        let f: NumberFilter<f64> = serde_json::from_str(r#""1e-9""#).unwrap();
+        assert_eq!(f, NumberFilter::Eq(1e-9));
+
+        for json in [
+            r#""NaN""#,
+            r#""gte=-inf""#,
+            r#""between=0,inf""#,
+            r#""gte=1e999""#,
+        ] {
+            assert!(
+                serde_json::from_str::<NumberFilter<f64>>(json).is_err(),
+                "{json}"
+            );
+        }
+        assert!(serde_json::from_str::<NumberFilter<f32>>("1e39").is_err());
+    }
+
+    #[test]
+    fn test_number_filter_null_safe_eq() {
+        let f = NumberFilters::<i32>::from_str("age", "age[nseq]=3&age[nseq]=NULL").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                NumberFilter::NullSafeEq(Some(3)),
+                NumberFilter::NullSafeEq(None)
+            ]
+        );
+        assert_eq!(f.0[0].operator().to_string(), "nseq");
+        assert_eq!(f.0[1].value(), None);
+        assert_eq!(f.0[1].filter_value(), FilterValue::Null);
+
+        let json = serde_json::to_string(&f.0).unwrap();
+        assert_eq!(json, r#"["nseq=3","nseq=null"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<NumberFilter<i32>>>(&json).unwrap(),
+            f.0
+        );
+    }
+
+    #[test]
+    fn test_number_filter_operator_case() {
+        let qs = "age[GTE]=18&age[Lt]=100&age[BETWEEN]=1,2";
+        let f = NumberFilters::<i32>::from_str("age", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                NumberFilter::Gte(18),
+                NumberFilter::Lt(100),
+                NumberFilter::Between(1, 2)
+            ]
+        );
+
+        // the field name keeps its case
+        let f = NumberFilters::<i32>::from_str("age", "AGE[gte]=18").unwrap();
+        assert!(f.is_empty());
+    }
+
     #[test]
     fn test_deserialization() {
         // "gte=10"
@@ -157,5 +837,344 @@ mod tests {
         // "10" -> Eq(10)
         let f: NumberFilter<i32> = serde_json::from_str("\"10\"").unwrap();
         assert_eq!(f, NumberFilter::Eq(10));
+
+        // only the first `=` splits, the rest is the (invalid) value
+        let err = serde_json::from_str::<NumberFilter<i32>>("\"gte=10=1\"").unwrap_err();
+        assert!(err.to_string().contains("\"10=1\""), "{err}");
+    }
+
+    #[test]
+    fn test_deserialization_errors() {
+        let err = |json: &str| {
+            serde_json::from_str::<NumberFilter<i32>>(json)
+                .unwrap_err()
+                .to_string()
+        };
+
+        // an empty value is reported as missing, not as an unparsable number
+        assert_eq!(
+            err("\"lte=\""),
+            "missing value for number filter `lte` at line 1 column 6"
+        );
+        assert_eq!(
+            err("\"\""),
+            "missing value for number filter `eq` at line 1 column 2"
+        );
+
+        let msg = err("\"gte=abc\"");
+        assert!(msg.contains("\"abc\""), "{msg}");
+        assert!(msg.contains("for number filter `gte`"), "{msg}");
+        assert!(msg.contains("InvalidDigit"), "{msg}");
+
+        let msg = err("\"abc\"");
+        assert!(msg.contains("for number filter `eq`"), "{msg}");
+
+        let msg = err("\"between=1\"");
+        assert!(msg.contains("`low,high` pair"), "{msg}");
+
+        // the operator is checked before the value
+        assert_eq!(
+            err("\"foo=abc\""),
+            "unknown number filter `foo` at line 1 column 9"
+        );
+        assert!(serde_json::from_str::<NumberFilter<i32>>("\"gte=99999999999\"").is_err());
+
+        // no key falls back to another operator
+        for key in ["lteq", "GTE", "in", "le"] {
+            let json = format!("\"{key}=1\"");
+            assert!(err(&json).starts_with("unknown number filter"), "{json}");
+        }
+        assert!(err("\"range=\"").starts_with("missing value for number filter `range`"));
+    }
+
+    #[test]
+    fn test_deserialization_every_operator() {
+        let cases = [
+            ("eq=1", NumberFilter::Eq(1)),
+            ("ne=1", NumberFilter::Ne(1)),
+            ("nseq=1", NumberFilter::NullSafeEq(Some(1))),
+            ("nseq=null", NumberFilter::NullSafeEq(None)),
+            ("gt=1", NumberFilter::Gt(1)),
+            ("lt=1", NumberFilter::Lt(1)),
+            ("gte=1", NumberFilter::Gte(1)),
+            ("lte=1", NumberFilter::Lte(1)),
+            ("between=1,2", NumberFilter::Between(1, 2)),
+            (
+                "range=(1,2]",
+                NumberFilter::Range(RangeBound::Exclusive(1), RangeBound::Inclusive(2)),
+            ),
+        ];
+
+        for (value, expected) in cases {
+            let f: NumberFilter<i32> = serde_json::from_str(&format!("\"{value}\"")).unwrap();
+            assert_eq!(f, expected, "{value}");
+        }
+    }
+
+    #[test]
+    fn test_deserialization_from_number() {
+        let f: NumberFilter<i32> = serde_json::from_str("10").unwrap();
+        assert_eq!(f, NumberFilter::Eq(10));
+
+        let f: NumberFilter<i32> = serde_json::from_str("-3").unwrap();
+        assert_eq!(f, NumberFilter::Eq(-3));
+
+        let f: NumberFilter<f64> = serde_json::from_str("1.5").unwrap();
+        assert_eq!(f, NumberFilter::Eq(1.5));
+
+        // the string form keeps working alongside
+        let f: NumberFilter<f64> = serde_json::from_str("\"gte=1.5\"").unwrap();
+        assert_eq!(f, NumberFilter::Gte(1.5));
+
+        // a fractional number can't become an integer
+        assert!(serde_json::from_str::<NumberFilter<i32>>("1.5").is_err());
+
+        let f: NumberFilters<i32> = serde_json::from_str(r#"[10, "lt=20"]"#).unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Eq(10), NumberFilter::Lt(20)]);
+    }
+
+    #[test]
+    fn test_number_filters_round_trip() {
+        let f: NumberFilters<i32> = serde_json::from_str(r#"["gte=18", "lt=65"]"#).unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Gte(18), NumberFilter::Lt(65)]);
+        assert_eq!(f.1, None);
+
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, r#"["gte=18","lt=65"]"#);
+
+        let back: NumberFilters<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, f);
+
+        let f = back.with_id("age".to_string().into());
+        assert_eq!(f.1, Some(FilterId::Alone("age".to_string())));
+    }
+
+    #[test]
+    fn test_number_filter_empty_values() {
+        let f = NumberFilters::<i32>::from_str("age", "age[gte]=&age[lt]=100&").unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Lt(100)]);
+    }
+
+    #[test]
+    fn test_number_filter_error_source() {
+        use std::error::Error;
+
+        let err = NumberFilters::<i32>::from_str("age", "age[gte]=abc").unwrap_err();
+        assert_eq!(err.to_string(), "invalid filter value");
+        assert!(err.source().is_some());
+        assert!(err.source().unwrap().is::<std::num::ParseIntError>());
+
+        let err = NumberFilters::<i32>::from_str("age", "age[between]=1,x").unwrap_err();
+        assert!(err.source().is_some());
+
+        // a malformed pair has no underlying error
+        let err = NumberFilters::<i32>::from_str("age", "age[between]=18").unwrap_err();
+        assert!(err.source().is_none());
+    }
+
+    #[test]
+    fn test_number_filter_between() {
+        let f = NumberFilters::<i32>::from_str("age", "age[between]=18,65").unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Between(18, 65)]);
+        assert_eq!(
+            f.0[0].filter_value(),
+            FilterValue::List(vec![FilterValue::Int(18), FilterValue::Int(65)])
+        );
+
+        assert!(matches!(
+            NumberFilters::<i32>::from_str("age", "age[between]=18"),
+            Err(FilterParseError::Value(_))
+        ));
+
+        let json = serde_json::to_string(&f.0[0]).unwrap();
+        assert_eq!(json, r#""between=18,65""#);
+        let back: NumberFilter<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, f.0[0]);
+    }
+
+    #[test]
+    fn test_number_filter_range() {
+        let range = |qs: &str| NumberFilters::<i32>::from_str("age", qs).map(|x| x.0);
+
+        assert_eq!(
+            range("age[range]=[18,65]").unwrap(),
+            vec![NumberFilter::Range(
+                RangeBound::Inclusive(18),
+                RangeBound::Inclusive(65)
+            )]
+        );
+        assert_eq!(
+            range("age[range]=[18,65)").unwrap(),
+            vec![NumberFilter::Range(
+                RangeBound::Inclusive(18),
+                RangeBound::Exclusive(65)
+            )]
+        );
+        assert_eq!(
+            range("age[range]=(18,65]").unwrap(),
+            vec![NumberFilter::Range(
+                RangeBound::Exclusive(18),
+                RangeBound::Inclusive(65)
+            )]
+        );
+        assert_eq!(
+            range("age[range]=(18,65)").unwrap(),
+            vec![NumberFilter::Range(
+                RangeBound::Exclusive(18),
+                RangeBound::Exclusive(65)
+            )]
+        );
+
+        for qs in [
+            "age[range]=18,65",
+            "age[range]=[18,65",
+            "age[range]={18,65}",
+            "age[range]=[18)",
+            "age[range]=[18,65,70)",
+            "age[range]=[",
+        ] {
+            assert!(
+                matches!(range(qs), Err(FilterParseError::Value(Some(_)))),
+                "{qs}"
+            );
+        }
+        assert!(matches!(
+            range("age[range]=[18,x)"),
+            Err(FilterParseError::Value(Some(_)))
+        ));
+
+        let f = range("age[range]=(18,65]").unwrap();
+        assert_eq!(
+            f[0].filter_value(),
+            FilterValue::List(vec![FilterValue::Int(18), FilterValue::Int(65)])
+        );
+
+        let json = serde_json::to_string(&f[0]).unwrap();
+        assert_eq!(json, r#""range=(18,65]""#);
+        let back: NumberFilter<i32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, f[0]);
+    }
+
+    #[test]
+    fn test_number_filter_aliases() {
+        use crate::options::OperatorAliases;
+
+        let options = ParseOptions {
+            aliases: OperatorAliases::new()
+                .with("$gte", "gte")
+                .with("$lt", "lt")
+                .with("$ne", "ne")
+                .with(">=", "gte")
+                .with("!=", "ne"),
+            ..Default::default()
+        };
+        let parse = |qs: &str| {
+            NumberFilters::<i32>::from_id_value_with("age".to_string().into(), qs, &options)
+        };
+
+        let f = parse("age[$gte]=18&age[$LT]=65&age[$ne]=30&name[$regex]=x").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                NumberFilter::Gte(18),
+                NumberFilter::Lt(65),
+                NumberFilter::Ne(30)
+            ]
+        );
+
+        let f = parse("age[>=]=21&age[!=]=40&age[lte]=50").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                NumberFilter::Gte(21),
+                NumberFilter::Ne(40),
+                NumberFilter::Lte(50)
+            ]
+        );
+
+        // unregistered spellings are still unknown
+        for qs in ["age[$gt]=1", "age[>]=1"] {
+            assert!(
+                matches!(parse(qs), Err(FilterParseError::UnknownFilter)),
+                "{qs}"
+            );
+        }
+        assert!(matches!(
+            NumberFilters::<i32>::from_str("age", "age[$gte]=18"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_number_filter_decimal() {
+        use rust_decimal::Decimal;
+
+        let qs = "price[gte]=10.50&price[between]=0.1,99.99";
+        let f = NumberFilters::<Decimal>::from_str("price", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                NumberFilter::Gte(Decimal::new(1050, 2)),
+                NumberFilter::Between(Decimal::new(1, 1), Decimal::new(9999, 2))
+            ]
+        );
+        assert_eq!(
+            f.0[0].filter_value(),
+            FilterValue::String("10.50".to_string())
+        );
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_number_filter_widths() {
+        let qs = "n[gt]=-170141183460469231731687303715884105728&n[lt]=18446744073709551616";
+        let f = NumberFilters::<i128>::from_str("n", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![NumberFilter::Gt(i128::MIN), NumberFilter::Lt(1 << 64)]
+        );
+
+        let f = NumberFilters::<u128>::from_str("n", "n=340282366920938463463374607431768211455")
+            .unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Eq(u128::MAX)]);
+
+        let f = NumberFilters::<u64>::from_str("n", "n[gte]=18446744073709551615").unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Gte(u64::MAX)]);
+        assert!(NumberFilters::<u64>::from_str("n", "n=-1").is_err());
+
+        let f = NumberFilters::<f32>::from_str("n", "n[between]=0.5,1e3").unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Between(0.5, 1000.0)]);
+    }
+
+    #[test]
+    fn test_number_filters_try_map() {
+        let f = NumberFilters::<u64>::from_str("n", "n[between]=1,2")
+            .unwrap()
+            .try_map(i64::try_from)
+            .unwrap();
+        assert_eq!(f.0, vec![NumberFilter::Between(1i64, 2)]);
+        assert_eq!(f.1, Some(FilterId::Alone("n".to_string())));
+
+        let f = NumberFilters::<u64>::from_str("n", "n[gt]=18446744073709551615").unwrap();
+        assert!(matches!(
+            f.try_map(i64::try_from),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_number_filters_conversions() {
+        let id = FilterId::WithPrefix("users".to_string(), "age".to_string());
+        let f = NumberFilters::new(vec![NumberFilter::Gte(18)], id.clone());
+        assert_eq!(f.1, Some(id.clone()));
+
+        let filters: Vec<NumberFilter<i32>> = f.into();
+        assert_eq!(filters, vec![NumberFilter::Gte(18)]);
+
+        let f = NumberFilters::from(filters).with_id(id.clone());
+        assert_eq!(f, NumberFilters(vec![NumberFilter::Gte(18)], Some(id)));
+
+        let f: NumberFilters<i32> = Vec::new().into();
+        assert_eq!(f.1, None);
+    }
+}
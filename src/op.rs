@@ -0,0 +1,212 @@
+use std::fmt::{self, Display};
+
+/// The operator of a filter parameter, e.g. the `gte` of `age[gte]=18`.
+///
+/// It is parsed once from the lowercased key, after [`OperatorAliases`] are resolved, so
+/// each filter type matches on the operators it supports instead of their spellings.
+/// The short spellings are the same operator, `sw` being [`Op::StartsWith`], and a
+/// leading `!` negates the operator after it. An operator no filter knows is kept as
+/// [`Op::Other`], so a filter can still reject it with `FilterParseError::UnknownFilter`.
+///
+/// [`OperatorAliases`]: crate::options::OperatorAliases
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::op::Op;
+///
+/// assert_eq!(Op::from("sw"), Op::StartsWith);
+/// assert_eq!(Op::from("!in"), Op::Not(Box::new(Op::In)));
+/// assert_eq!(Op::from("$gte"), Op::Other("$gte".to_string()));
+///
+/// assert_eq!(Op::StartsWith.to_string(), "starts_with");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Op {
+    /// `eq`, also the operator of a bare `field=value`.
+    Eq,
+    /// `ne`.
+    Ne,
+    /// `nseq`.
+    NullSafeEq,
+    /// `gt`.
+    Gt,
+    /// `lt`.
+    Lt,
+    /// `gte`.
+    Gte,
+    /// `lte`.
+    Lte,
+    /// `between`.
+    Between,
+    /// `range`.
+    Range,
+    /// `like` or `l`.
+    Like,
+    /// `not_like` or `nl`.
+    NotLike,
+    /// `ilike`.
+    ILike,
+    /// `starts_with` or `sw`.
+    StartsWith,
+    /// `ends_with` or `ew`.
+    EndsWith,
+    /// `contains` or `c`.
+    Contains,
+    /// `regex`.
+    Regex,
+    /// `iregex`.
+    IRegex,
+    /// `similar` or `sim`.
+    Similar,
+    /// `contains_unaccent`.
+    ContainsUnaccent,
+    /// `len_gt`.
+    LenGt,
+    /// `len_lt`.
+    LenLt,
+    /// `len_eq`.
+    LenEq,
+    /// `in`.
+    In,
+    /// `not_in` or `nin`.
+    NotIn,
+    /// `array_contains`.
+    ArrayContains,
+    /// `fts`.
+    Fts,
+    /// `asc`, for `order_by`.
+    Asc,
+    /// `desc`, for `order_by`.
+    Desc,
+    /// `rand`, for `order_by`.
+    Rand,
+    /// Another operator prefixed with `!`, e.g. `!contains`.
+    Not(Box<Op>),
+    /// An operator none of the above, as written (lowercased).
+    Other(String),
+}
+
+/// The key of every operator but [`Op::Not`] and [`Op::Other`], with its short spelling
+/// after it where it has one, so the first key of an operator is its canonical one.
+const KEYS: &[(&str, Op)] = &[
+    ("eq", Op::Eq),
+    ("ne", Op::Ne),
+    ("nseq", Op::NullSafeEq),
+    ("gt", Op::Gt),
+    ("lt", Op::Lt),
+    ("gte", Op::Gte),
+    ("lte", Op::Lte),
+    ("between", Op::Between),
+    ("range", Op::Range),
+    ("like", Op::Like),
+    ("l", Op::Like),
+    ("not_like", Op::NotLike),
+    ("nl", Op::NotLike),
+    ("ilike", Op::ILike),
+    ("starts_with", Op::StartsWith),
+    ("sw", Op::StartsWith),
+    ("ends_with", Op::EndsWith),
+    ("ew", Op::EndsWith),
+    ("contains", Op::Contains),
+    ("c", Op::Contains),
+    ("regex", Op::Regex),
+    ("iregex", Op::IRegex),
+    ("similar", Op::Similar),
+    ("sim", Op::Similar),
+    ("contains_unaccent", Op::ContainsUnaccent),
+    ("len_gt", Op::LenGt),
+    ("len_lt", Op::LenLt),
+    ("len_eq", Op::LenEq),
+    ("in", Op::In),
+    ("not_in", Op::NotIn),
+    ("nin", Op::NotIn),
+    ("array_contains", Op::ArrayContains),
+    ("fts", Op::Fts),
+    ("asc", Op::Asc),
+    ("desc", Op::Desc),
+    ("rand", Op::Rand),
+];
+
+impl From<&str> for Op {
+    /// Parses a lowercased operator key. Never fails, see [`Op::Other`].
+    fn from(value: &str) -> Self {
+        if let Some((_, op)) = KEYS.iter().find(|(key, _)| *key == value) {
+            return op.clone();
+        }
+
+        match value.strip_prefix('!') {
+            Some(op) => Op::Not(Box::new(Op::from(op))),
+            None => Op::Other(value.to_string()),
+        }
+    }
+}
+
+impl Display for Op {
+    /// Writes the operator key, e.g. `starts_with`, which is the long spelling for
+    /// operators that have a short one.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Op::Not(op) => write!(f, "!{op}"),
+            Op::Other(op) => f.write_str(op),
+            op => {
+                let (key, _) = KEYS
+                    .iter()
+                    .find(|(_, x)| x == op)
+                    .expect("every operator but `Not` and `Other` has a key");
+                f.write_str(key)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_op_parsing() {
+        assert_eq!(Op::from("eq"), Op::Eq);
+        assert_eq!(Op::from("len_gt"), Op::LenGt);
+        assert_eq!(Op::from("contains_unaccent"), Op::ContainsUnaccent);
+
+        // the short spellings are the same operator
+        assert_eq!(Op::from("l"), Op::Like);
+        assert_eq!(Op::from("nl"), Op::NotLike);
+        assert_eq!(Op::from("sw"), Op::StartsWith);
+        assert_eq!(Op::from("ew"), Op::EndsWith);
+        assert_eq!(Op::from("c"), Op::Contains);
+        assert_eq!(Op::from("sim"), Op::Similar);
+        assert_eq!(Op::from("nin"), Op::NotIn);
+
+        assert_eq!(Op::from("!contains"), Op::Not(Box::new(Op::Contains)));
+        assert_eq!(
+            Op::from("!!sw"),
+            Op::Not(Box::new(Op::Not(Box::new(Op::StartsWith))))
+        );
+
+        // keys are expected lowercased, like the operators `from_str` passes on
+        assert_eq!(Op::from("GTE"), Op::Other("GTE".to_string()));
+        assert_eq!(Op::from(">="), Op::Other(">=".to_string()));
+        assert_eq!(
+            Op::from("!="),
+            Op::Not(Box::new(Op::Other("=".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_op_display() {
+        for op in ["eq", "not_in", "!contains", "!!regex", "$gte", "!="] {
+            assert_eq!(Op::from(op).to_string(), op);
+        }
+
+        assert_eq!(Op::from("sw").to_string(), "starts_with");
+        assert_eq!(Op::from("!nin").to_string(), "!not_in");
+
+        // every key parses to an operator that writes a key parsing back to it
+        for (key, op) in KEYS {
+            assert_eq!(Op::from(*key), *op);
+            assert_eq!(Op::from(op.to_string().as_str()), *op);
+        }
+    }
+}
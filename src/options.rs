@@ -0,0 +1,238 @@
+use std::{borrow::Cow, collections::HashMap};
+
+use crate::{common::WithFilterId, op::Op};
+
+/// Options controlling how query strings are parsed.
+///
+/// The defaults match the behavior of the plain `from_str` parsers.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::options::ParseOptions;
+/// use filtrum::string_filter::{StringFilter, StringFilters};
+///
+/// let options = ParseOptions {
+///     keep_empty_values: true,
+///     ..Default::default()
+/// };
+///
+/// let filters = StringFilters::<String>::from_id_value_with(
+///     "name".to_string().into(),
+///     "name[eq]=",
+///     &options,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(filters.0, vec![StringFilter::Eq("".to_string())]);
+/// ```
+// not `PartialEq`, as `allowed_ops` can't be compared
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Keep parameters with an empty value (`name[eq]=`) instead of skipping them.
+    ///
+    /// Only applies to values that can be parsed from an empty string (e.g. `String`),
+    /// an empty number is always skipped.
+    pub keep_empty_values: bool,
+    /// Reject ambiguous queries instead of resolving them, e.g. a repeated `limit` or
+    /// `skip` yields [`FilterParseError::Duplicate`](crate::errors::FilterParseError::Duplicate)
+    /// rather than the last value winning.
+    pub strict: bool,
+    /// The names of the pagination and sorting parameters. Renaming them frees the
+    /// default names, e.g. for a column called `limit`.
+    pub reserved: ReservedParams,
+    /// The largest `skip` accepted, or `None` for no limit. A larger one fails with
+    /// [`FilterParseError::SkipTooLarge`](crate::errors::FilterParseError::SkipTooLarge),
+    /// since a huge offset is usually a client bug and still makes the database walk
+    /// every skipped row.
+    pub max_skip: Option<u64>,
+    /// Other spellings of the operators, e.g. `$gte` or `>=` for `gte`. Empty by default,
+    /// which accepts only the built-in keys.
+    pub aliases: OperatorAliases,
+    /// Treat a bare `field=value` as the lowest-precedence `eq`: when the same field also
+    /// has an explicit `eq` (`field[eq]=value`, or an alias of it), its bare parameters
+    /// are dropped. Off by default, where every parameter is kept and they are ANDed.
+    pub explicit_eq_overrides_bare: bool,
+    /// Read a bare flag without a value, like `archived` in `archived&limit=10`, as
+    /// `archived=true`, instead of failing with
+    /// [`FilterParseError::FilterStructure`](crate::errors::FilterParseError::FilterStructure).
+    ///
+    /// Meant for boolean fields: any other field gets the value `true` too, which a
+    /// number rejects and a string compares against.
+    pub presence_flags: bool,
+    /// The operators each field may be filtered with. Empty by default, which accepts
+    /// any operator.
+    pub allowed_ops: AllowedOps,
+}
+
+/// A table of operator aliases, each resolving to one of the built-in operator keys
+/// before a filter is parsed.
+///
+/// Aliases are matched ignoring ASCII case, like the operators themselves. An alias of a
+/// negatable operator is negated with `!` too, e.g. `name[!$regex]`.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::number_filter::{NumberFilter, NumberFilters};
+/// use filtrum::options::{OperatorAliases, ParseOptions};
+///
+/// let options = ParseOptions {
+///     aliases: OperatorAliases::new().with("$gte", "gte").with(">=", "gte"),
+///     ..Default::default()
+/// };
+///
+/// let filters =
+///     NumberFilters::<i32>::from_id_value_with("age".to_string().into(), "age[$GTE]=18", &options)
+///         .unwrap();
+///
+/// assert_eq!(filters.0, vec![NumberFilter::Gte(18)]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OperatorAliases(HashMap<String, String>);
+
+impl OperatorAliases {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `alias` as another spelling of `operator`, e.g. `"$gte"` for `"gte"`.
+    pub fn with(mut self, alias: &str, operator: &str) -> Self {
+        self.0
+            .insert(alias.to_ascii_lowercase(), operator.to_string());
+        self
+    }
+
+    /// Returns the operator `operator` is an alias of, or `operator` itself if it isn't
+    /// one. `operator` is expected lowercased.
+    pub fn resolve<'a>(&'a self, operator: &'a str) -> Cow<'a, str> {
+        if let Some(x) = self.0.get(operator) {
+            return Cow::Borrowed(x);
+        }
+
+        match operator.strip_prefix('!').and_then(|x| self.0.get(x)) {
+            Some(x) => Cow::Owned(format!("!{x}")),
+            None => Cow::Borrowed(operator),
+        }
+    }
+}
+
+/// The operators each field of a filter may be filtered with, as its
+/// [`WithFilterId::allowed_ops`] lists them.
+///
+/// An operator is checked once its alias is resolved, so `$gte` is allowed wherever
+/// `gte` is, and any other operator fails with
+/// [`FilterParseError::UnknownFilter`](crate::errors::FilterParseError::UnknownFilter).
+/// The `Filterable` derive parses its fields with the operators of its own filter.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::common::WithFilterId;
+/// use filtrum::errors::FilterParseError;
+/// use filtrum::op::Op;
+/// use filtrum::options::{AllowedOps, OperatorAliases, ParseOptions};
+/// use filtrum::string_filter::StringFilters;
+///
+/// struct AccountFilter;
+///
+/// impl WithFilterId for AccountFilter {
+///     fn filter_id() -> Option<&'static str> {
+///         None
+///     }
+///
+///     fn allowed_ops(field: &str) -> Option<&'static [Op]> {
+///         match field {
+///             "username" => Some(&[Op::Eq, Op::StartsWith]),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let options = ParseOptions {
+///     aliases: OperatorAliases::new().with("$sw", "starts_with"),
+///     allowed_ops: AllowedOps::of::<AccountFilter>(),
+///     ..Default::default()
+/// };
+///
+/// let parse = |query| {
+///     StringFilters::<String>::from_id_value_with("username".to_string().into(), query, &options)
+/// };
+///
+/// assert!(parse("username[$sw]=al").is_ok());
+/// assert!(matches!(
+///     parse("username[like]=al"),
+///     Err(FilterParseError::UnknownFilter)
+/// ));
+/// ```
+///
+/// It isn't `PartialEq`: it holds the function that lists the operators, and two
+/// functions can't be told apart reliably, nor their lists compared for every field.
+#[derive(Clone, Copy, Default)]
+pub struct AllowedOps(Option<OpsOf>);
+
+/// The signature of [`WithFilterId::allowed_ops`].
+type OpsOf = fn(&str) -> Option<&'static [Op]>;
+
+impl AllowedOps {
+    /// Takes the operators from `F::allowed_ops`.
+    pub fn of<F: WithFilterId + ?Sized>() -> Self {
+        Self(Some(F::allowed_ops))
+    }
+
+    /// Returns the operators `field` may be filtered with, or `None` to accept any.
+    pub fn get(&self, field: &str) -> Option<&'static [Op]> {
+        self.0.and_then(|x| x(field))
+    }
+}
+
+impl std::fmt::Debug for AllowedOps {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(_) => f.write_str("AllowedOps(..)"),
+            None => f.write_str("AllowedOps(None)"),
+        }
+    }
+}
+
+/// The names of the parameters [`FromQueryFilter`](crate::query_filter::FromQueryFilter)
+/// reads besides the filters: `limit`, `skip` and `order_by` by default.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::limit::Limit;
+/// use filtrum::options::{ParseOptions, ReservedParams};
+///
+/// let options = ParseOptions {
+///     reserved: ReservedParams {
+///         limit: "per_page",
+///         skip: "offset",
+///         order_by: "sort",
+///     },
+///     ..Default::default()
+/// };
+///
+/// let limit = Limit::from_str_with("limit=5&per_page=20", &options).unwrap();
+/// assert_eq!(limit, Some(Limit(20)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservedParams {
+    /// The page size, parsed as a [`Limit`](crate::limit::Limit).
+    pub limit: &'static str,
+    /// The offset, parsed as a [`Skip`](crate::skip::Skip).
+    pub skip: &'static str,
+    /// The sort, parsed as an [`OrderBy`](crate::order_by::OrderBy).
+    pub order_by: &'static str,
+}
+
+impl Default for ReservedParams {
+    fn default() -> Self {
+        Self {
+            limit: "limit",
+            skip: "skip",
+            order_by: "order_by",
+        }
+    }
+}
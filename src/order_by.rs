@@ -1,12 +1,22 @@
+use serde::{de, Deserialize, Serialize};
+
 use crate::{
-    common::{from_str, FromStrFilter},
+    common::{from_str_with, FromStrFilter, QueryParams},
     errors::FilterParseError,
     filter_id::FilterId,
+    op::Op,
+    options::ParseOptions,
 };
 
 /// Represents sorting instructions.
 ///
-/// Parses `order_by[asc]=field` or `order_by[desc]=field`.
+/// Parses `order_by[asc]=field` or `order_by[desc]=field`, as well as the shorthand
+/// `order_by=field` (ascending) and `order_by=-field` (descending). Without an operator
+/// the direction defaults to ascending. `order_by[rand]=1` shuffles the results instead;
+/// its value is ignored. The parameter can be renamed with [`ParseOptions::reserved`].
+///
+/// Serializes as `column:asc`, `column:desc` or `rand`, e.g. to echo the applied sort
+/// in a response. The column is written without its prefix, as the client sent it.
 ///
 /// # Example
 ///
@@ -20,6 +30,9 @@ use crate::{
 ///     OrderBy::Desc(id) => assert_eq!(id.id(), "created_at"),
 ///     _ => panic!("Expected Desc"),
 /// }
+///
+/// let order = OrderBy::from_str("order_by=-created_at").unwrap().unwrap();
+/// assert_eq!(order, OrderBy::Desc("created_at".to_string().into()));
 /// ```
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum OrderBy {
@@ -27,44 +40,159 @@ pub enum OrderBy {
     Asc(FilterId),
     /// Descending order.
     Desc(FilterId),
+    /// Random order, for shuffled results. It sorts on no column.
+    Random,
 }
 
 impl FromStrFilter<String> for OrderBy {
-    fn from_str(id: &str, value: String) -> Result<Self, FilterParseError> {
-        match id {
-            "asc" => Ok(OrderBy::Asc(value.into())),
-            "desc" => Ok(OrderBy::Desc(value.into())),
-            _ => Err(FilterParseError::UnknownFilter)?,
+    fn from_str(op: &Op, value: String) -> Result<Self, FilterParseError> {
+        // a comma list only makes sense for `Sort`, dropping the other keys would sort
+        // differently than the client asked
+        match OrderBy::parse_list(op, &value)?.as_slice() {
+            [order_by] => Ok(order_by.clone()),
+            _ => Err(FilterParseError::value(
+                "`order_by` takes a single column here, parse a `Sort` for several",
+            )),
         }
     }
 }
 
 impl OrderBy {
+    // `None` when the parameter is absent, which `FromStr` can't express
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Result<Option<Self>, FilterParseError> {
-        let u = from_str("order_by", value)?.first().cloned();
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    /// Like [`OrderBy::from_str`], reading the parameter named in `options.reserved`.
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        let defaults = ParseOptions::default();
+        let u = from_str_with(options.reserved.order_by, value, &defaults)?
+            .first()
+            .cloned();
 
         Ok(u)
     }
 
     pub fn from_str_prefix(prefix: &str, value: &str) -> Result<Option<Self>, FilterParseError> {
-        let data = Self::from_str(value)?.map(|x| -> OrderBy {
-            match x {
-                OrderBy::Asc(u) => match u {
-                    FilterId::Alone(value) => {
-                        OrderBy::Asc(FilterId::WithPrefix(prefix.to_string(), value))
-                    }
-                    _ => unreachable!(),
-                },
-                OrderBy::Desc(u) => match u {
-                    FilterId::Alone(value) => {
-                        OrderBy::Desc(FilterId::WithPrefix(prefix.to_string(), value))
-                    }
-                    _ => unreachable!(),
-                },
-            }
-        });
+        Self::from_str_prefix_with(prefix, value, &ParseOptions::default())
+    }
+
+    /// Like [`OrderBy::from_str_prefix`], reading the parameter named in
+    /// `options.reserved`.
+    pub fn from_str_prefix_with<'a>(
+        prefix: &str,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        let data = Self::from_str_with(value, options)?.map(|x| x.with_prefix(prefix));
         Ok(data)
     }
+
+    /// Parses the value of a single `order_by` parameter, which may hold a comma
+    /// separated list of columns.
+    ///
+    /// With an explicit `asc`/`desc` operator every column is sorted in that direction;
+    /// without one (`eq`), a leading `-` marks a column as descending.
+    pub(crate) fn parse_list(op: &Op, value: &str) -> Result<Vec<Self>, FilterParseError> {
+        if *op == Op::Rand {
+            return Ok(vec![OrderBy::Random]);
+        }
+
+        value
+            .split(',')
+            .map(|column| {
+                let order_by = match op {
+                    Op::Asc => OrderBy::Asc(column.to_string().into()),
+                    Op::Desc => OrderBy::Desc(column.to_string().into()),
+                    Op::Eq => match column.strip_prefix('-') {
+                        Some(column) => OrderBy::Desc(column.to_string().into()),
+                        None => OrderBy::Asc(column.to_string().into()),
+                    },
+                    _ => Err(FilterParseError::UnknownFilter)?,
+                };
+
+                if order_by.id().is_some_and(|x| x.id().is_empty()) {
+                    return Err(FilterParseError::Value(None));
+                }
+
+                Ok(order_by)
+            })
+            .collect()
+    }
+
+    /// Returns the column being sorted on, or `None` for [`OrderBy::Random`].
+    pub fn id(&self) -> Option<&FilterId> {
+        match self {
+            OrderBy::Asc(id) => Some(id),
+            OrderBy::Desc(id) => Some(id),
+            OrderBy::Random => None,
+        }
+    }
+
+    /// Sorts on `id` instead, in the same direction.
+    pub(crate) fn with_id(self, id: FilterId) -> Self {
+        match self {
+            OrderBy::Asc(_) => OrderBy::Asc(id),
+            OrderBy::Desc(_) => OrderBy::Desc(id),
+            OrderBy::Random => OrderBy::Random,
+        }
+    }
+
+    /// Qualifies the sorted column with `prefix`.
+    pub(crate) fn with_prefix(self, prefix: &str) -> Self {
+        let prefixed = |id: FilterId| match id {
+            FilterId::Alone(value) => FilterId::WithPrefix(prefix.to_string(), value),
+            id => id,
+        };
+
+        match self {
+            OrderBy::Asc(u) => OrderBy::Asc(prefixed(u)),
+            OrderBy::Desc(u) => OrderBy::Desc(prefixed(u)),
+            OrderBy::Random => OrderBy::Random,
+        }
+    }
+}
+
+impl Serialize for OrderBy {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        match self {
+            OrderBy::Asc(id) => serializer.collect_str(&format_args!("{}:asc", id.id())),
+            OrderBy::Desc(id) => serializer.collect_str(&format_args!("{}:desc", id.id())),
+            OrderBy::Random => serializer.serialize_str("rand"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for OrderBy {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let value = String::deserialize(deserializer)?;
+
+        let order_by = match value.rsplit_once(':') {
+            Some((column, "asc")) if !column.is_empty() => OrderBy::Asc(column.to_string().into()),
+            Some((column, "desc")) if !column.is_empty() => {
+                OrderBy::Desc(column.to_string().into())
+            }
+            None if value == "rand" => OrderBy::Random,
+            _ => {
+                return Err(de::Error::invalid_value(
+                    de::Unexpected::Str(&value),
+                    &"`column:asc`, `column:desc` or `rand`",
+                ));
+            }
+        };
+
+        Ok(order_by)
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +228,112 @@ mod tests {
             _ => panic!("Expected Asc"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_order_by_renamed_param() {
+        let options = ParseOptions {
+            reserved: crate::options::ReservedParams {
+                order_by: "sort",
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let qs = "order_by=name&sort[desc]=age";
+        let ob = OrderBy::from_str_with(qs, &options).unwrap();
+        assert_eq!(ob, Some(OrderBy::Desc("age".to_string().into())));
+        assert_eq!(
+            crate::sort::Sort::from_str_with(qs, &options).unwrap().0,
+            vec![OrderBy::Desc("age".to_string().into())]
+        );
+
+        let ob = OrderBy::from_str_prefix_with("users", "sort=-age", &options).unwrap();
+        assert_eq!(
+            ob,
+            Some(OrderBy::Desc(FilterId::WithPrefix(
+                "users".to_string(),
+                "age".to_string()
+            )))
+        );
+    }
+
+    #[test]
+    fn test_order_by_shorthand() {
+        let ob = OrderBy::from_str("order_by=-age").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Desc("age".to_string().into()));
+        assert_eq!(ob.id().map(|x| x.id()), Some("age"));
+
+        let ob = OrderBy::from_str("order_by=name").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Asc("name".to_string().into()));
+
+        // several columns need a `Sort`, the extra ones aren't silently dropped
+        assert!(matches!(
+            OrderBy::from_str("order_by=-age,name"),
+            Err(FilterParseError::Value(Some(_)))
+        ));
+
+        assert!(matches!(
+            OrderBy::from_str("order_by=-"),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[test]
+    fn test_order_by_default_direction() {
+        // no operator, or the inferred `eq`, means ascending
+        for qs in ["order_by=name", "order_by[eq]=name"] {
+            let ob = OrderBy::from_str(qs).unwrap().unwrap();
+            assert_eq!(ob, OrderBy::Asc("name".to_string().into()));
+        }
+
+        // explicit directions are kept as they are
+        let ob = OrderBy::from_str("order_by[asc]=name").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Asc("name".to_string().into()));
+        let ob = OrderBy::from_str("order_by[desc]=name").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Desc("name".to_string().into()));
+
+        // the operator is case-insensitive, the column is kept as it is
+        let ob = OrderBy::from_str("order_by[DESC]=Name").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Desc("Name".to_string().into()));
+
+        assert!(matches!(
+            OrderBy::from_str("order_by[up]=name"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+
+    #[test]
+    fn test_order_by_random() {
+        let ob = OrderBy::from_str("order_by[rand]=1").unwrap().unwrap();
+        assert_eq!(ob, OrderBy::Random);
+        assert_eq!(ob.id(), None);
+
+        // the value is ignored, and there is no column to prefix
+        let ob = OrderBy::from_str_prefix("users", "order_by[rand]=yes")
+            .unwrap()
+            .unwrap();
+        assert_eq!(ob, OrderBy::Random);
+    }
+
+    #[test]
+    fn test_order_by_serde() {
+        let cases = [
+            (OrderBy::Asc("name".to_string().into()), "\"name:asc\""),
+            (OrderBy::Desc("age".to_string().into()), "\"age:desc\""),
+            (OrderBy::Random, "\"rand\""),
+        ];
+
+        for (order_by, json) in cases {
+            assert_eq!(serde_json::to_string(&order_by).unwrap(), json);
+            assert_eq!(serde_json::from_str::<OrderBy>(json).unwrap(), order_by);
+        }
+
+        // the prefix is not echoed back
+        let ob = OrderBy::from_str_prefix("users", "order_by=-age").unwrap();
+        assert_eq!(serde_json::to_string(&ob).unwrap(), "\"age:desc\"");
+
+        for json in ["\"age\"", "\"age:up\"", "\":asc\"", "\"\"", "1"] {
+            assert!(serde_json::from_str::<OrderBy>(json).is_err(), "{json}");
+        }
+    }
+}
@@ -1,7 +1,21 @@
-use std::str::FromStr;
+use std::{borrow::Cow, collections::HashMap, str::FromStr};
+
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    common::WithFilterId, errors::FilterParseError, limit::Limit, order_by::OrderBy, skip::Skip,
+    common::{
+        check_ops_with, expand_flags, has_field, strip_query, QueryParams, WithFilterId,
+    },
+    errors::FilterParseError,
+    filter::{ClearField, FilterFields, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::FilterValue,
+    limit::Limit,
+    op::Op,
+    options::ParseOptions,
+    order_by::OrderBy,
+    skip::Skip,
+    sort::Sort,
 };
 
 /// A container for parsing and holding query filters, including pagination and sorting.
@@ -12,7 +26,7 @@ use crate::{
 /// # Type Parameters
 ///
 /// * `T`: The type representing the domain-specific filters (e.g., a struct with fields for `name`, `age`, etc.).
-///        Must implement `FromStr`, `WithFilterId`, and `Default`.
+///   Must implement `FromStr`, `WithFilterId`, and `Default`.
 ///
 /// # Example
 ///
@@ -58,6 +72,12 @@ pub struct FromQueryFilter<T: FromStr + WithFilterId + Default> {
     pub limit: Option<Limit>,
     /// Skip (offset) for pagination, if present.
     pub skip: Option<Skip>,
+    /// The query string this was parsed from, kept for logging and auditing. `None` when
+    /// built without parsing, e.g. with [`FromQueryFilter::empty`].
+    pub raw: Option<String>,
+    /// Leaves `T::default_order_by()` and `T::default_tiebreak()` out of
+    /// [`FromQueryFilter::sort`], as set by [`FromQueryFilter::without_default_sort`].
+    pub(crate) skip_default_sort: bool,
 }
 
 impl<T: Default> FromQueryFilter<T>
@@ -72,24 +92,82 @@ where
     /// # Arguments
     ///
     /// * `value`: The query string to parse (e.g., "key=value&limit=10").
+    // inherent like `from_str_with`, which takes the options `FromStr` has no room for
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Result<Self, FilterParseError> {
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    /// Parses a query string into a `FromQueryFilter` instance, using `options` for the
+    /// standard parameters (`order_by`, `limit`, `skip`) and the inner filter.
+    ///
+    /// The inner filter type `T` is parsed with [`WithFilterId::from_str_with`], which
+    /// the `Filterable` derive implements with `options`; a hand-written `T` parses with
+    /// its own `FromStr` by default. A leading `?`, as in a query string copied from a
+    /// URL, is ignored.
+    ///
+    /// `value` can also be a query already split into pairs (see [`QueryParams`]), like
+    /// the ones [`decode_query`] percent-decodes, whose values may hold an `&`; `raw` is
+    /// only kept for a query string.
+    ///
+    /// [`decode_query`]: crate::common::decode_query
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        let value = value.into();
+        // `?age=5`, copied from a URL, is `age=5`
+        let raw = match value {
+            QueryParams::Str(value) => Some(strip_query(value)),
+            QueryParams::Pairs(_) => None,
+        };
+
+        // not every parser takes the options, so the flags of a query string are expanded
+        // up front; a pair always has a value
+        let expanded = raw.map(|raw| match options.presence_flags {
+            true => expand_flags(raw),
+            false => Cow::Borrowed(raw),
+        });
+        let value = match &expanded {
+            Some(expanded) => QueryParams::Str(expanded),
+            None => value,
+        };
+
         let order_by = if let Some(prefix) = T::filter_id() {
-            OrderBy::from_str_prefix(prefix, value)?
+            OrderBy::from_str_prefix_with(prefix, value, options)?
         } else {
-            OrderBy::from_str(value)?
+            OrderBy::from_str_with(value, options)?
         };
 
-        let limit = Limit::from_str(value)?;
+        // `OrderBy::Random` has no column, so it is always allowed
+        if let Some(columns) = T::sortable_columns()
+            && let Some(id) = order_by.as_ref().and_then(|x| x.id())
+            && !columns.contains(&id.id())
+        {
+            return Err(FilterParseError::UnknownField(id.id().to_string()));
+        }
+
+        // a field with its own column, like an alias, sorts on it
+        let order_by = order_by.map(|x| match x.id().and_then(|id| T::sort_column(id.id())) {
+            Some(id) => x.with_id(id),
+            None => x,
+        });
 
-        let skip = Skip::from_str(value)?;
+        let limit = Limit::from_str_with(value, options)?;
 
-        let inner = T::from_str(value)?;
+        let skip = Skip::from_str_with(value, options)?;
+
+        check_ops_with::<T>(value, options)?;
+
+        let inner = T::from_str_with(value, options)?;
 
         Ok(Self {
             order_by,
             limit,
             inner,
             skip,
+            raw: raw.map(str::to_string),
+            skip_default_sort: false,
         })
     }
 }
@@ -110,6 +188,342 @@ where
             ..Self::default()
         }
     }
+
+    /// Creates a `FromQueryFilter` from its parts, the inverse of
+    /// [`FromQueryFilter::into_parts`]. `raw` is left as `None`.
+    pub fn from_parts(
+        inner: T,
+        order_by: Option<OrderBy>,
+        limit: Option<Limit>,
+        skip: Option<Skip>,
+    ) -> Self {
+        Self {
+            inner,
+            order_by,
+            limit,
+            skip,
+            raw: None,
+            skip_default_sort: false,
+        }
+    }
+
+    /// Splits the filter into its inner filter, `order_by`, `limit` and `skip`,
+    /// dropping `raw`.
+    pub fn into_parts(self) -> (T, Option<OrderBy>, Option<Limit>, Option<Skip>) {
+        (self.inner, self.order_by, self.limit, self.skip)
+    }
+
+    /// Clears `order_by`, `limit` and `skip`, keeping only the conditions, e.g. for an
+    /// aggregate query (`COUNT(*)`, `SUM(...)`, `GROUP BY ...`) over the filtered rows.
+    ///
+    /// `T::default_order_by()` and `T::default_tiebreak()` are left out too, so no
+    /// `ORDER BY` is rendered; an aggregate can't be ordered by columns it doesn't group.
+    pub fn without_pagination(mut self) -> Self {
+        self.order_by = None;
+        self.limit = None;
+        self.skip = None;
+        self.without_default_sort()
+    }
+
+    /// Leaves `T::default_order_by()` and `T::default_tiebreak()` out of
+    /// [`FromQueryFilter::sort`] and [`FromQueryFilter::pagination`], so only a sort the
+    /// query asked for is rendered.
+    pub fn without_default_sort(mut self) -> Self {
+        self.skip_default_sort = true;
+        self
+    }
+
+    /// Returns `true` if the default sort is left out, see
+    /// [`FromQueryFilter::without_default_sort`].
+    pub fn skips_default_sort(&self) -> bool {
+        self.skip_default_sort
+    }
+
+    /// Drops the conditions parsed for `field`, so it renders no SQL even if the query
+    /// has it. A field that doesn't exist is ignored. Sorting on the field is still
+    /// governed by [`WithFilterId::sortable_columns`].
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = FromQueryFilter::<UserFilter>::from_str("name=ana&age[gte]=18")?
+    ///     .disable_field("age");
+    ///
+    /// // ... WHERE 1=1 AND name = ?
+    /// filter.apply(&mut qb);
+    /// ```
+    pub fn disable_field(mut self, field: &str) -> Self
+    where
+        T: ClearField,
+    {
+        self.inner.clear_field(field);
+        self
+    }
+
+    /// Returns the limit, if the query has one.
+    pub fn limit_value(&self) -> Option<u64> {
+        self.limit.map(|x| x.0)
+    }
+
+    /// Returns the skip, if the query has one.
+    pub fn skip_value(&self) -> Option<u64> {
+        self.skip.map(|x| x.0)
+    }
+
+    /// Returns the field the query sorts on, e.g. `age` for `order_by=-age`. `None` for
+    /// a random order or no `order_by`; `T::default_order_by()` isn't considered.
+    pub fn order_by_column(&self) -> Option<&str> {
+        match self.order_by.as_ref()? {
+            OrderBy::Asc(id) | OrderBy::Desc(id) => Some(id.id()),
+            OrderBy::Random => None,
+        }
+    }
+
+    /// Sets the limit for pagination.
+    pub fn with_limit(mut self, limit: u64) -> Self {
+        self.limit = Some(Limit(limit));
+        self
+    }
+
+    /// Sets the skip (offset) for pagination.
+    pub fn with_skip(mut self, skip: u64) -> Self {
+        self.skip = Some(Skip(skip));
+        self
+    }
+
+    /// Sets the sorting instruction.
+    pub fn with_order_by(mut self, order_by: OrderBy) -> Self {
+        self.order_by = Some(order_by);
+        self
+    }
+
+    /// Folds `other` into this filter, with `other` taking precedence: its conditions
+    /// are merged into `inner` (see [`Merge`]), and its `order_by`, `limit` and `skip`
+    /// replace these when set.
+    pub fn merge(&mut self, other: Self)
+    where
+        T: Merge,
+    {
+        self.inner.merge(other.inner);
+        self.order_by = other.order_by.or(self.order_by.take());
+        self.limit = other.limit.or(self.limit);
+        self.skip = other.skip.or(self.skip);
+
+        self.raw = match (self.raw.take(), other.raw) {
+            (Some(this), Some(other)) => Some(format!("{this}&{other}")),
+            (this, other) => other.or(this),
+        };
+    }
+
+    /// Parses `query` and overlays it onto this filter, e.g. live parameters onto a
+    /// saved search. Parameters in `query` win over the ones already set.
+    ///
+    /// A field with a default value (see [`WithFilterId::default_values`]) that `query`
+    /// leaves out keeps its current conditions: the default only applies to the query
+    /// the filter was first parsed from.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let mut filter = FromQueryFilter::<UserFilter>::from_str(&saved_search)?;
+    /// filter.merge_query("age[gte]=21&limit=5")?;
+    /// ```
+    pub fn merge_query(&mut self, query: &str) -> Result<(), FilterParseError>
+    where
+        T: Merge + ClearField + FromStr<Err = FilterParseError>,
+    {
+        self.merge_query_with(query, &ParseOptions::default())
+    }
+
+    /// Like [`FromQueryFilter::merge_query`], parsing `query` with `options` as
+    /// [`FromQueryFilter::from_str_with`] does.
+    pub fn merge_query_with<'a>(
+        &mut self,
+        query: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<(), FilterParseError>
+    where
+        T: Merge + ClearField + FromStr<Err = FilterParseError>,
+    {
+        let query = query.into();
+        let mut other = Self::from_str_with(query, options)?;
+
+        for (field, _) in T::default_values() {
+            if !has_field(query, field) {
+                other.inner.clear_field(field);
+            }
+        }
+
+        self.merge(other);
+        Ok(())
+    }
+
+    /// Returns the conditions of every field that has any, keyed by field name, with
+    /// each condition as its operator (e.g. [`Op::Gte`]) and value.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = FromQueryFilter::<UserFilter>::from_str("name[sw]=Al&age[gte]=18")?;
+    /// let map = filter.to_filter_map();
+    ///
+    /// assert_eq!(map["age"], vec![(Op::Gte, FilterValue::Int(18))]);
+    /// ```
+    pub fn to_filter_map(&self) -> HashMap<String, Vec<(Op, FilterValue)>>
+    where
+        T: FilterFields,
+    {
+        self.inner
+            .filter_fields()
+            .into_iter()
+            .filter(|(_, conditions)| !conditions.is_empty())
+            .map(|(field, conditions)| (field.to_string(), conditions))
+            .collect()
+    }
+
+    /// Returns every condition of every field, in declaration order, as the column it
+    /// applies to, its operator (e.g. [`Op::Gte`]) and value, as described on
+    /// [`VisitConditions`]. Fields without conditions yield nothing, and neither do
+    /// `order_by`, `limit` and `skip`.
+    ///
+    /// Unlike [`FromQueryFilter::to_filter_map`] it gives the columns rather than the
+    /// field names, resolving the table and alias of each field, and keeps the order of
+    /// the fields, e.g. for an audit log of what a user filtered on. (With the `sqlx`
+    /// feature, `conditions()` captures the rendered `WHERE` conditions instead.)
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = FromQueryFilter::<UserFilter>::from_str("name[sw]=Al&age[gte]=18")?;
+    ///
+    /// for (column, op, value) in filter.iter_conditions() {
+    ///     tracing::info!(column, %op, %value, "filtered");
+    /// }
+    /// ```
+    pub fn iter_conditions(&self) -> impl Iterator<Item = (String, Op, FilterValue)>
+    where
+        T: VisitConditions,
+    {
+        let mut conditions = Vec::new();
+        self.inner
+            .visit_conditions(&mut |column, op, value| conditions.push((column, op, value)));
+
+        conditions.into_iter()
+    }
+
+    /// Returns the structure of the query without its values: the
+    /// [`Filter::condition_shapes`] of every field, the sort, and whether `limit` and
+    /// `skip` are set, e.g.
+    /// `name[starts_with]&age[range;[)]&id[in;3]&parent_id[eq;null]&order_by=users.age:desc&limit`.
+    ///
+    /// Queries with the same shape render the same SQL and differ only in their bound
+    /// values, so the shape can key a cache of anything derived from the SQL, like
+    /// prepared statements or query plans. The SQL itself is still rendered for every
+    /// query, as the values are bound while it is. It also depends on the
+    /// `ApplyOptions`, so keep one cache per set of options.
+    ///
+    /// [`Filter::condition_shapes`]: crate::filter::Filter::condition_shapes
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let a = FromQueryFilter::<UserFilter>::from_str("age[gte]=18&limit=10")?;
+    /// let b = FromQueryFilter::<UserFilter>::from_str("age[gte]=65&limit=5")?;
+    ///
+    /// assert_eq!(a.shape(), b.shape());
+    /// assert_eq!(a.shape(), "age[gte]&limit");
+    /// ```
+    pub fn shape(&self) -> String
+    where
+        T: FilterFields,
+    {
+        let mut parts = Vec::new();
+
+        for (field, shapes) in self.inner.filter_shapes() {
+            parts.extend(shapes.into_iter().map(|shape| format!("{field}{shape}")));
+        }
+
+        let sort = self.sort();
+        if !sort.0.is_empty() {
+            let keys: Vec<_> = sort
+                .0
+                .iter()
+                .map(|x| match x {
+                    OrderBy::Asc(id) => format!("{}:asc", id.qualified()),
+                    OrderBy::Desc(id) => format!("{}:desc", id.qualified()),
+                    OrderBy::Random => "rand".to_string(),
+                })
+                .collect();
+            parts.push(format!("order_by={}", keys.join(",")));
+        }
+
+        if self.limit.is_some() {
+            parts.push("limit".to_string());
+        }
+
+        if self.skip.is_some() {
+            parts.push("skip".to_string());
+        }
+
+        parts.join("&")
+    }
+
+    /// Returns the sort and pagination that apply to this query, for echoing back in a
+    /// response. The sort is `order_by`, or `T::default_order_by()` when there is none,
+    /// unless it is left out with [`FromQueryFilter::without_default_sort`].
+    pub fn pagination(&self) -> Pagination {
+        let default = || T::default_order_by().filter(|_| !self.skip_default_sort);
+
+        Pagination {
+            sort: self.order_by.clone().or_else(default),
+            limit: self.limit,
+            skip: self.skip,
+        }
+    }
+
+    /// Returns the full sort to apply: `order_by`, or `T::default_order_by()` when there
+    /// is none, followed by the `T::default_tiebreak()` column, if any. Only `order_by`
+    /// is kept after [`FromQueryFilter::without_default_sort`].
+    pub fn sort(&self) -> Sort {
+        if self.skip_default_sort {
+            return Sort::from(self.order_by.clone());
+        }
+
+        let order_by = self.order_by.clone().or_else(|| {
+            let order_by = T::default_order_by()?;
+            Some(match T::filter_id() {
+                Some(prefix) => order_by.with_prefix(prefix),
+                None => order_by,
+            })
+        });
+        let sort = Sort::from(order_by);
+
+        match T::default_tiebreak() {
+            Some(column) => {
+                let id = match T::filter_id() {
+                    Some(prefix) => FilterId::WithPrefix(prefix.to_string(), column.to_string()),
+                    None => FilterId::Alone(column.to_string()),
+                };
+                sort.with_tiebreak(id)
+            }
+            None => sort,
+        }
+    }
+}
+
+/// The sort and pagination of a [`FromQueryFilter`], as returned by
+/// [`FromQueryFilter::pagination`].
+///
+/// Serializes compactly, leaving out what isn't set, e.g.
+/// `{"sort":"age:desc","limit":10}`.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Pagination {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sort: Option<OrderBy>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<Limit>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip: Option<Skip>,
 }
 
 #[cfg(test)]
@@ -142,6 +556,7 @@ mod tests {
         let qs = "age=20&limit=10&order_by[asc]=age";
         let q: FromQueryFilter<MockQuery> = FromQueryFilter::from_str(qs).unwrap();
 
+        assert_eq!(q.raw.as_deref(), Some(qs));
         assert_eq!(q.inner.age.into_inner(), Some(20));
         assert_eq!(q.limit.unwrap().0, 10);
         match q.order_by.unwrap() {
@@ -149,4 +564,357 @@ mod tests {
             _ => panic!("Expected Asc"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_leading_question_mark() {
+        let q = FromQueryFilter::<MockQuery>::from_str("?age=5&limit=10").unwrap();
+        let expected = FromQueryFilter::<MockQuery>::from_str("age=5&limit=10").unwrap();
+
+        assert_eq!(q.inner.age, expected.inner.age);
+        assert_eq!(q.limit, expected.limit);
+        assert_eq!(q.raw.as_deref(), Some("age=5&limit=10"));
+
+        let q = FromQueryFilter::<MockQuery>::from_str("?limit=10").unwrap();
+        assert_eq!(q.limit, Some(Limit(10)));
+        assert!(FromQueryFilter::<MockQuery>::from_str("?").is_ok());
+    }
+
+    #[test]
+    fn test_presence_flags() {
+        let options = ParseOptions {
+            presence_flags: true,
+            ..Default::default()
+        };
+
+        let qs = "age&limit=10";
+        assert!(FromQueryFilter::<MockQuery>::from_str(qs).is_err());
+
+        // the flag reaches the inner filter as `age=true`, which an `i32` rejects
+        assert!(FromQueryFilter::<MockQuery>::from_str_with(qs, &options).is_err());
+
+        let q = FromQueryFilter::<MockQuery>::from_str_with("age=3&limit=10&x", &options).unwrap();
+        assert_eq!(q.raw.as_deref(), Some("age=3&limit=10&x"));
+        assert_eq!(q.inner.age.into_inner(), Some(3));
+        assert_eq!(q.limit, Some(Limit(10)));
+    }
+
+    #[test]
+    fn test_pagination_accessors() {
+        let q =
+            FromQueryFilter::<MockQuery>::from_str("age=1&limit=10&skip=20&order_by=-age").unwrap();
+        assert_eq!(q.limit_value(), Some(10));
+        assert_eq!(q.skip_value(), Some(20));
+        assert_eq!(q.order_by_column(), Some("age"));
+
+        let q = FromQueryFilter::<MockQuery>::from_str("order_by[asc]=age").unwrap();
+        assert_eq!(q.order_by_column(), Some("age"));
+
+        let q = FromQueryFilter::<MockQuery>::from_str("age=1").unwrap();
+        assert_eq!(q.limit_value(), None);
+        assert_eq!(q.skip_value(), None);
+        assert_eq!(q.order_by_column(), None);
+
+        let q = FromQueryFilter::<MockQuery>::from_str("order_by[rand]=1").unwrap();
+        assert_eq!(q.order_by_column(), None);
+    }
+
+    #[test]
+    fn test_order_by_list_is_rejected() {
+        // a single `order_by` is kept, so a list fails rather than losing its other keys
+        for qs in ["order_by=-age,name", "age=1&order_by[desc]=age,name"] {
+            assert!(matches!(
+                FromQueryFilter::<MockQuery>::from_str(qs),
+                Err(FilterParseError::Value(Some(_)))
+            ));
+        }
+    }
+
+    #[derive(Default)]
+    struct LimitColumnQuery {
+        limit: EqualFilter<i32>,
+    }
+
+    impl crate::common::WithFilterId for LimitColumnQuery {
+        fn filter_id() -> Option<&'static str> {
+            None
+        }
+    }
+
+    impl FromStr for LimitColumnQuery {
+        type Err = FilterParseError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(LimitColumnQuery {
+                limit: EqualFilter::from_str("limit", s)?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_renamed_reserved_params() {
+        let options = ParseOptions {
+            reserved: crate::options::ReservedParams {
+                limit: "per_page",
+                skip: "offset",
+                order_by: "sort",
+            },
+            ..Default::default()
+        };
+
+        let qs = "limit=3&per_page=10&offset=20&sort=-limit&skip=99&order_by=x";
+        let q = FromQueryFilter::<LimitColumnQuery>::from_str_with(qs, &options).unwrap();
+
+        // `limit` is an ordinary column now, and the default names are ignored
+        assert_eq!(q.inner.limit.as_ref(), Some(&3));
+        assert_eq!(q.limit, Some(Limit(10)));
+        assert_eq!(q.skip, Some(Skip(20)));
+        assert_eq!(q.order_by, Some(OrderBy::Desc("limit".to_string().into())));
+
+        let q = FromQueryFilter::<LimitColumnQuery>::from_str("limit=3").unwrap();
+        assert_eq!(q.limit, Some(Limit(3)));
+    }
+
+    #[derive(Default)]
+    struct TiebreakQuery;
+
+    impl crate::common::WithFilterId for TiebreakQuery {
+        fn filter_id() -> Option<&'static str> {
+            None
+        }
+
+        fn default_tiebreak() -> Option<&'static str> {
+            Some("id")
+        }
+    }
+
+    impl FromStr for TiebreakQuery {
+        type Err = FilterParseError;
+        fn from_str(_: &str) -> Result<Self, Self::Err> {
+            Ok(TiebreakQuery)
+        }
+    }
+
+    #[test]
+    fn test_sort_with_tiebreak() {
+        let q = FromQueryFilter::<TiebreakQuery>::from_str("order_by[desc]=age").unwrap();
+        assert_eq!(
+            q.sort().0,
+            vec![
+                OrderBy::Desc("age".to_string().into()),
+                OrderBy::Asc("id".to_string().into())
+            ]
+        );
+
+        let q = FromQueryFilter::<TiebreakQuery>::from_str("").unwrap();
+        assert_eq!(q.sort().0, vec![OrderBy::Asc("id".to_string().into())]);
+
+        let q = FromQueryFilter::<MockQuery>::from_str("").unwrap();
+        assert!(q.sort().is_empty());
+    }
+
+    #[derive(Default)]
+    struct NewestFirstQuery;
+
+    impl crate::common::WithFilterId for NewestFirstQuery {
+        fn filter_id() -> Option<&'static str> {
+            Some("posts")
+        }
+
+        fn default_order_by() -> Option<OrderBy> {
+            Some(OrderBy::Desc("created_at".to_string().into()))
+        }
+    }
+
+    impl FromStr for NewestFirstQuery {
+        type Err = FilterParseError;
+        fn from_str(_: &str) -> Result<Self, Self::Err> {
+            Ok(NewestFirstQuery)
+        }
+    }
+
+    #[test]
+    fn test_default_order_by() {
+        let created_at = FilterId::WithPrefix("posts".to_string(), "created_at".to_string());
+
+        // no `order_by`: the default is applied, but not recorded as the client's
+        let q = FromQueryFilter::<NewestFirstQuery>::from_str("limit=10").unwrap();
+        assert_eq!(q.order_by, None);
+        assert_eq!(q.sort().0, vec![OrderBy::Desc(created_at)]);
+
+        // the client overrides it
+        let q = FromQueryFilter::<NewestFirstQuery>::from_str("order_by=title").unwrap();
+        let title = FilterId::WithPrefix("posts".to_string(), "title".to_string());
+        assert_eq!(q.order_by, Some(OrderBy::Asc(title.clone())));
+        assert_eq!(q.sort().0, vec![OrderBy::Asc(title)]);
+    }
+
+    #[test]
+    fn test_builder() {
+        let q = FromQueryFilter::from_inner(MockQuery {
+            age: EqualFilter::from_str("age", "age=3").unwrap(),
+        })
+        .with_limit(10)
+        .with_skip(20)
+        .with_order_by(OrderBy::Desc("age".to_string().into()));
+
+        assert_eq!(q.inner.age.as_ref(), Some(&3));
+        assert_eq!(q.limit, Some(Limit(10)));
+        assert_eq!(q.skip, Some(Skip(20)));
+        assert_eq!(q.order_by, Some(OrderBy::Desc("age".to_string().into())));
+        assert_eq!(q.raw, None);
+        assert_eq!(FromQueryFilter::<MockQuery>::empty().raw, None);
+    }
+
+    #[test]
+    fn test_parts_round_trip() {
+        let q =
+            FromQueryFilter::<MockQuery>::from_str("age=3&order_by=-age&limit=10&skip=20").unwrap();
+        let pagination = q.pagination();
+        let (inner, order_by, limit, skip) = q.into_parts();
+
+        assert_eq!(inner.age.as_ref(), Some(&3));
+        assert_eq!(order_by, Some(OrderBy::Desc("age".to_string().into())));
+        assert_eq!(limit, Some(Limit(10)));
+        assert_eq!(skip, Some(Skip(20)));
+
+        let q = FromQueryFilter::from_parts(inner, order_by, limit, skip);
+        assert_eq!(q.inner.age.as_ref(), Some(&3));
+        assert_eq!(q.pagination(), pagination);
+        assert_eq!(q.raw, None);
+    }
+
+    #[test]
+    fn test_without_pagination() {
+        let q = FromQueryFilter::<MockQuery>::from_str("age=3&order_by=-age&limit=10&skip=20")
+            .unwrap()
+            .without_pagination();
+
+        assert_eq!(q.inner.age.as_ref(), Some(&3));
+        assert_eq!(q.pagination(), Pagination::default());
+        assert!(q.sort().0.is_empty());
+
+        // the default sort is dropped too
+        let q = FromQueryFilter::<NewestFirstQuery>::from_str("order_by=age&limit=10")
+            .unwrap()
+            .without_pagination();
+        assert!(q.sort().is_empty());
+        assert_eq!(q.pagination(), Pagination::default());
+
+        // a sort set afterwards still applies, without the defaults
+        let q = q.with_order_by(OrderBy::Asc("age".to_string().into()));
+        assert_eq!(q.sort().0, vec![OrderBy::Asc("age".to_string().into())]);
+        assert!(q.skips_default_sort());
+    }
+
+    #[test]
+    fn test_without_default_sort() {
+        // the pagination is kept, only the default sort is dropped
+        let q = FromQueryFilter::<NewestFirstQuery>::from_str("limit=10").unwrap();
+        assert!(!q.skips_default_sort());
+
+        let q = q.without_default_sort();
+        assert!(q.skips_default_sort());
+        assert!(q.sort().is_empty());
+        assert_eq!(q.pagination().sort, None);
+        assert_eq!(q.limit, Some(Limit(10)));
+    }
+
+    #[test]
+    fn test_pagination_serde() {
+        let q = FromQueryFilter::<MockQuery>::from_str("age=3&order_by=-age&limit=10").unwrap();
+        let json = serde_json::to_string(&q.pagination()).unwrap();
+        assert_eq!(json, r#"{"sort":"age:desc","limit":10}"#);
+        assert_eq!(
+            serde_json::from_str::<Pagination>(&json).unwrap(),
+            q.pagination()
+        );
+
+        // the default sort is what gets applied, so it is echoed too
+        let q = FromQueryFilter::<NewestFirstQuery>::from_str("skip=20").unwrap();
+        let json = serde_json::to_string(&q.pagination()).unwrap();
+        assert_eq!(json, r#"{"sort":"created_at:desc","skip":20}"#);
+
+        let empty = FromQueryFilter::<MockQuery>::empty().pagination();
+        assert_eq!(serde_json::to_string(&empty).unwrap(), "{}");
+        assert_eq!(serde_json::from_str::<Pagination>("{}").unwrap(), empty);
+    }
+
+    #[derive(Default)]
+    struct AccountQuery {
+        username: crate::string_filter::StringFilters,
+        password: EqualFilter<String>,
+    }
+
+    impl crate::common::WithFilterId for AccountQuery {
+        fn filter_id() -> Option<&'static str> {
+            None
+        }
+
+        fn allowed_ops(field: &str) -> Option<&'static [Op]> {
+            match field {
+                "username" => Some(&[Op::Eq, Op::StartsWith]),
+                "password" => Some(&[]),
+                _ => None,
+            }
+        }
+    }
+
+    impl FromStr for AccountQuery {
+        type Err = FilterParseError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            let options = ParseOptions {
+                allowed_ops: crate::options::AllowedOps::of::<Self>(),
+                ..Default::default()
+            };
+
+            Ok(AccountQuery {
+                username: crate::string_filter::StringFilters::from_id_value_with(
+                    "username".to_string().into(),
+                    s,
+                    &options,
+                )?,
+                password: EqualFilter::from_id_value_with(
+                    "password".to_string().into(),
+                    s,
+                    &options,
+                )?,
+            })
+        }
+    }
+
+    #[test]
+    fn test_allowed_ops() {
+        let q = FromQueryFilter::<AccountQuery>::from_str("username[SW]=al&username=bob&limit=5")
+            .unwrap();
+        assert_eq!(q.inner.username.0.len(), 2);
+        assert!(q.inner.password.is_empty());
+        assert_eq!(q.limit, Some(Limit(5)));
+
+        // the short and long spellings are the same operator
+        let q = FromQueryFilter::<AccountQuery>::from_str("username[starts_with]=al").unwrap();
+        assert_eq!(q.inner.username.0.len(), 1);
+
+        for qs in [
+            "password=hunter2",
+            "password[eq]=hunter2",
+            "username[like]=%25a%25",
+            "username[!sw]=al",
+        ] {
+            assert!(
+                matches!(
+                    FromQueryFilter::<AccountQuery>::from_str(qs),
+                    Err(FilterParseError::UnknownFilter)
+                ),
+                "{qs}"
+            );
+
+            // the filter checks them itself too, when parsed on its own
+            assert!(
+                matches!(
+                    AccountQuery::from_str(qs),
+                    Err(FilterParseError::UnknownFilter)
+                ),
+                "{qs}"
+            );
+        }
+    }
+}
@@ -5,7 +5,7 @@ use regex::Regex;
 static QUERY_REGEX: OnceLock<Regex> = OnceLock::new();
 
 pub fn query_regex() -> &'static Regex {
-    QUERY_REGEX.get_or_init(|| Regex::new(r"(\w+)(\[([a-z]+)])?(\[(\d+)])?").unwrap())
+    QUERY_REGEX.get_or_init(|| Regex::new(r"^(\w+(?:\.\w+)?)(\[([^\[\]]+)])?(\[(\d+)])?$").unwrap())
 }
 
 #[cfg(test)]
@@ -21,6 +21,25 @@ mod tests {
         assert_eq!(caps.get(1).unwrap().as_str(), "age");
         assert_eq!(caps.get(3).unwrap().as_str(), "eq");
 
+        // case: bio[len_gt], operators may contain underscores
+        let caps = re.captures("bio[len_gt]").unwrap();
+        assert_eq!(caps.get(1).unwrap().as_str(), "bio");
+        assert_eq!(caps.get(3).unwrap().as_str(), "len_gt");
+
+        // case: name[!contains], a negated operator
+        let caps = re.captures("name[!contains]").unwrap();
+        assert_eq!(caps.get(3).unwrap().as_str(), "!contains");
+
+        // case: age[GTE], the operator is lowercased when parsed
+        let caps = re.captures("age[GTE]").unwrap();
+        assert_eq!(caps.get(3).unwrap().as_str(), "GTE");
+
+        // case: age[$gte] and age[>=], operator aliases may use symbols
+        let caps = re.captures("age[$gte]").unwrap();
+        assert_eq!(caps.get(3).unwrap().as_str(), "$gte");
+        let caps = re.captures("age[>=]").unwrap();
+        assert_eq!(caps.get(3).unwrap().as_str(), ">=");
+
         // case: age
         let caps = re.captures("age").unwrap();
         assert_eq!(caps.get(1).unwrap().as_str(), "age");
@@ -32,5 +51,17 @@ mod tests {
         assert_eq!(caps.get(1).unwrap().as_str(), "age");
         assert_eq!(caps.get(3).unwrap().as_str(), "eq");
         assert_eq!(caps.get(5).unwrap().as_str(), "10");
+
+        // case: attrs.color[ne], the attribute of a dynamic field
+        let caps = re.captures("attrs.color[ne]").unwrap();
+        assert_eq!(caps.get(1).unwrap().as_str(), "attrs.color");
+        assert_eq!(caps.get(3).unwrap().as_str(), "ne");
+        assert!(re.captures("a.b.c").is_none());
+
+        // the whole key has to match, not just a part of it
+        assert!(re.captures("-age[eq]").is_none());
+        assert!(re.captures("age[gte").is_none());
+        assert!(re.captures("na-me").is_none());
+        assert!(re.captures("age[eq]x").is_none());
     }
 }
\ No newline at end of file
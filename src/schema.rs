@@ -0,0 +1,296 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    array_filter::ArrayFilters, dynamic_filter::DynamicFilters, equal_filter::EqualFilter,
+    in_filter::InFilters, nullable_filter::NullableFilter, number_filter::NumberFilters, op::Op,
+    search_filter::SearchFilter, string_filter::StringFilters,
+};
+
+/// The type of the values a field is compared against, as far as a client needs to
+/// know it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueType {
+    String,
+    Int,
+    Float,
+    Bool,
+}
+
+impl ValueType {
+    /// Returns the value type of `T`: `Int` for the integer types, `Float` for `f32` and
+    /// `f64`, `Bool` for `bool`, and `String` for anything else, e.g. a date or a
+    /// decimal, which are written as text.
+    pub fn of<T: ?Sized>() -> Self {
+        match std::any::type_name::<T>() {
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64"
+            | "u128" | "usize" => ValueType::Int,
+            "f32" | "f64" => ValueType::Float,
+            "bool" => ValueType::Bool,
+            _ => ValueType::String,
+        }
+    }
+}
+
+/// What an operator takes as its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// A single value, e.g. `age[gte]=18`.
+    Value,
+    /// A single value, or `null` to compare with `NULL`, e.g. `age[nseq]=null`.
+    Nullable,
+    /// The two bounds of `between`, e.g. `age[between]=18,65`.
+    Pair,
+    /// Any number of values, e.g. `id[in]=1,2,3`.
+    List,
+    /// A length, whatever the value type, e.g. `name[len_gt]=3`.
+    Length,
+    /// The bounds of `range`, each inclusive or exclusive, e.g. `age[range]=[18,65)`.
+    Range,
+}
+
+/// An operator a field accepts, with what it takes as its value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperatorSchema {
+    pub op: Op,
+    pub operand: Operand,
+}
+
+impl OperatorSchema {
+    fn new(op: Op, operand: Operand) -> Self {
+        Self { op, operand }
+    }
+}
+
+/// A field of a filter struct, with the operators it can be filtered with.
+///
+/// The `Filterable` derive lists one per non-skipped field in
+/// [`WithFilterId::filter_schema`], e.g. to describe the filters to a client or to
+/// generate a GraphQL input type from them.
+///
+/// [`WithFilterId::filter_schema`]: crate::common::WithFilterId::filter_schema
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::number_filter::NumberFilters;
+/// use filtrum::op::Op;
+/// use filtrum::schema::{FieldSchema, Operand, ValueType};
+///
+/// let field = FieldSchema::of::<NumberFilters<i32>>("age");
+/// assert_eq!(field.value, ValueType::Int);
+/// assert_eq!(field.operand(&Op::Between), Some(Operand::Pair));
+/// assert_eq!(field.operand(&Op::Like), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldSchema {
+    /// The name of the field, as written in the query.
+    pub name: &'static str,
+    /// The type of the values the field is compared against.
+    pub value: ValueType,
+    /// The operators the field accepts, by their canonical key.
+    pub operators: Vec<OperatorSchema>,
+}
+
+impl FieldSchema {
+    /// Describes a field named `name` that is parsed as an `F`.
+    pub fn of<F: FieldOperators>(name: &'static str) -> Self {
+        Self {
+            name,
+            value: F::value_type(),
+            operators: F::operators(),
+        }
+    }
+
+    /// Returns what `op` takes as its value on this field, or `None` if the field
+    /// doesn't accept it.
+    pub fn operand(&self, op: &Op) -> Option<Operand> {
+        self.operators
+            .iter()
+            .find(|x| x.op == *op)
+            .map(|x| x.operand)
+    }
+}
+
+/// Lists the operators a filter type parses, for [`FieldSchema`].
+///
+/// Only the canonical spelling of each operator is listed, not its short one (`sw`) nor
+/// its negation (`!eq`), and `EqualFilter` lists `eq` and `in` although it ignores the
+/// operator it is given.
+pub trait FieldOperators {
+    /// The type of the values the operators compare against.
+    fn value_type() -> ValueType;
+
+    /// The operators, in the order they are documented on the filter.
+    fn operators() -> Vec<OperatorSchema>;
+}
+
+impl<T> FieldOperators for NumberFilters<T> {
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![
+            OperatorSchema::new(Op::Eq, Operand::Value),
+            OperatorSchema::new(Op::Ne, Operand::Value),
+            OperatorSchema::new(Op::NullSafeEq, Operand::Nullable),
+            OperatorSchema::new(Op::Gt, Operand::Value),
+            OperatorSchema::new(Op::Lt, Operand::Value),
+            OperatorSchema::new(Op::Gte, Operand::Value),
+            OperatorSchema::new(Op::Lte, Operand::Value),
+            OperatorSchema::new(Op::Between, Operand::Pair),
+            OperatorSchema::new(Op::Range, Operand::Range),
+        ]
+    }
+}
+
+impl<T> FieldOperators for StringFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        let mut operators = vec![
+            OperatorSchema::new(Op::Eq, Operand::Value),
+            OperatorSchema::new(Op::Ne, Operand::Value),
+            OperatorSchema::new(Op::NullSafeEq, Operand::Nullable),
+            OperatorSchema::new(Op::Like, Operand::Value),
+            OperatorSchema::new(Op::NotLike, Operand::Value),
+            OperatorSchema::new(Op::ILike, Operand::Value),
+            OperatorSchema::new(Op::StartsWith, Operand::Value),
+            OperatorSchema::new(Op::EndsWith, Operand::Value),
+            OperatorSchema::new(Op::Contains, Operand::Value),
+            OperatorSchema::new(Op::Regex, Operand::Value),
+            OperatorSchema::new(Op::IRegex, Operand::Value),
+        ];
+
+        #[cfg(feature = "postgres")]
+        operators.extend([
+            OperatorSchema::new(Op::Similar, Operand::Value),
+            OperatorSchema::new(Op::ContainsUnaccent, Operand::Value),
+        ]);
+
+        operators.extend([
+            OperatorSchema::new(Op::Between, Operand::Pair),
+            OperatorSchema::new(Op::LenGt, Operand::Length),
+            OperatorSchema::new(Op::LenLt, Operand::Length),
+            OperatorSchema::new(Op::LenEq, Operand::Length),
+        ]);
+
+        operators
+    }
+}
+
+impl<T> FieldOperators for EqualFilter<T> {
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![
+            OperatorSchema::new(Op::Eq, Operand::Value),
+            OperatorSchema::new(Op::In, Operand::List),
+        ]
+    }
+}
+
+impl<T> FieldOperators for NullableFilter<T> {
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![OperatorSchema::new(Op::Eq, Operand::Nullable)]
+    }
+}
+
+impl<T> FieldOperators for InFilters<T> {
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![
+            OperatorSchema::new(Op::In, Operand::List),
+            OperatorSchema::new(Op::NotIn, Operand::List),
+        ]
+    }
+}
+
+impl<T> FieldOperators for ArrayFilters<T> {
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![OperatorSchema::new(Op::Contains, Operand::List)]
+    }
+}
+
+impl FieldOperators for SearchFilter {
+    fn value_type() -> ValueType {
+        ValueType::String
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        vec![OperatorSchema::new(Op::Eq, Operand::Value)]
+    }
+}
+
+/// The attributes are only known once the query is parsed, so none are listed.
+impl<T> FieldOperators for DynamicFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn value_type() -> ValueType {
+        ValueType::of::<T>()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        Vec::new()
+    }
+}
+
+impl<F: FieldOperators> FieldOperators for Option<F> {
+    fn value_type() -> ValueType {
+        F::value_type()
+    }
+
+    fn operators() -> Vec<OperatorSchema> {
+        F::operators()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_type() {
+        assert_eq!(ValueType::of::<i64>(), ValueType::Int);
+        assert_eq!(ValueType::of::<u8>(), ValueType::Int);
+        assert_eq!(ValueType::of::<f32>(), ValueType::Float);
+        assert_eq!(ValueType::of::<bool>(), ValueType::Bool);
+        assert_eq!(ValueType::of::<String>(), ValueType::String);
+        assert_eq!(ValueType::of::<Option<i32>>(), ValueType::String);
+    }
+
+    #[test]
+    fn test_field_schema() {
+        let field = FieldSchema::of::<Option<NullableFilter<i32>>>("parent_id");
+        assert_eq!(field.name, "parent_id");
+        assert_eq!(field.value, ValueType::Int);
+        assert_eq!(field.operand(&Op::Eq), Some(Operand::Nullable));
+
+        let field = FieldSchema::of::<StringFilters>("name");
+        assert_eq!(field.operand(&Op::StartsWith), Some(Operand::Value));
+        assert_eq!(field.operand(&Op::LenGt), Some(Operand::Length));
+        assert_eq!(field.operand(&Op::Not(Box::new(Op::Eq))), None);
+
+        let field = FieldSchema::of::<DynamicFilters>("attr");
+        assert!(field.operators.is_empty());
+    }
+}
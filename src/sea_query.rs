@@ -0,0 +1,412 @@
+pub use sea_query::{Condition, Order, SelectStatement, SimpleExpr};
+use sea_query::{extension::postgres::PgBinOper, Alias, BinOper, Expr, Func, LikeExpr, Value};
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{
+    array_filter::{ArrayFilter, ArrayFilters},
+    dynamic_filter::DynamicFilters,
+    equal_filter::EqualFilter,
+    filter_id::FilterId,
+    full_text_filter::FullTextFilter,
+    in_filter::{InFilter, InFilters},
+    nullable_filter::{Nullable, NullableFilter},
+    number_filter::{NumberFilter, NumberFilters, RangeBound},
+    order_by::OrderBy,
+    query_filter::FromQueryFilter,
+    search_filter::SearchFilter,
+    sort::Sort,
+    string_filter::{StringFilter, StringFilters},
+};
+
+/// The character `%` and `_` are escaped with in `LIKE` patterns, emitted in their
+/// `ESCAPE` clause.
+const LIKE_ESCAPE: char = '\\';
+
+/// NULL-safe equality, in its standard form, which MySQL doesn't support.
+const NULL_SAFE_EQ: BinOper = BinOper::Custom("IS NOT DISTINCT FROM");
+
+/// A filter that can be turned into a `sea_query` [`Condition`], for queries built with
+/// `sea_query` instead of SQLx.
+///
+/// The condition holds the filter's conditions ANDed together, and is empty when there
+/// are none. `sea_query` renders an empty condition as `TRUE`, so check
+/// [`Condition::is_empty`] before adding it to a query.
+///
+/// A query is only tied to a database when it is rendered, so operators without a
+/// portable form (`regex`, `iregex`, `similar`, `contains_unaccent`, full-text search,
+/// array containment and the attributes of a `DynamicFilters`) always render their
+/// Postgres syntax, and `nseq` renders the standard
+/// `IS NOT DISTINCT FROM`, which MySQL doesn't support.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::number_filter::NumberFilters;
+/// use filtrum::sea_query::SeaQueryFilter;
+/// use sea_query::{Alias, Asterisk, PostgresQueryBuilder, Query};
+///
+/// let age = NumberFilters::<i32>::from_str("age", "age[gte]=18&age[lt]=65").unwrap();
+///
+/// let sql = Query::select()
+///     .column(Asterisk)
+///     .from(Alias::new("users"))
+///     .cond_where(age.condition())
+///     .to_string(PostgresQueryBuilder);
+///
+/// assert_eq!(sql, r#"SELECT * FROM "users" WHERE "age" >= 18 AND "age" < 65"#);
+/// ```
+pub trait SeaQueryFilter {
+    /// Returns the filter's conditions, ANDed together.
+    fn condition(&self) -> Condition;
+}
+
+impl<F: SeaQueryFilter + ?Sized> SeaQueryFilter for Box<F> {
+    fn condition(&self) -> Condition {
+        (**self).condition()
+    }
+}
+
+/// A missing filter has no conditions.
+impl<F: SeaQueryFilter> SeaQueryFilter for Option<F> {
+    fn condition(&self) -> Condition {
+        self.as_ref()
+            .map_or_else(Condition::all, SeaQueryFilter::condition)
+    }
+}
+
+/// Combines the conditions of `filters` with AND. Filters without conditions are
+/// skipped.
+pub fn all_of(filters: &[&dyn SeaQueryFilter]) -> Condition {
+    combine(Condition::all(), filters)
+}
+
+/// Combines the conditions of `filters` with OR, each filter's own conditions staying
+/// ANDed together: `name=a&age[gte]=18&age[lt]=30` becomes
+/// `name = 'a' OR (age >= 18 AND age < 30)`. Filters without conditions are skipped.
+pub fn any_of(filters: &[&dyn SeaQueryFilter]) -> Condition {
+    combine(Condition::any(), filters)
+}
+
+fn combine(cond: Condition, filters: &[&dyn SeaQueryFilter]) -> Condition {
+    filters
+        .iter()
+        .map(|x| x.condition())
+        .filter(|x| !x.is_empty())
+        .fold(cond, Condition::add)
+}
+
+/// Returns the column `id` refers to, qualified with its prefix.
+fn column(id: &FilterId) -> Expr {
+    match id.prefix() {
+        Some(prefix) => Expr::col((Alias::new(prefix), Alias::new(id.key()))),
+        None => Expr::col(Alias::new(id.key())),
+    }
+}
+
+/// Escapes the `LIKE` wildcards `%` and `_`, and the escape character itself, so
+/// `value` matches literally.
+fn escape_like(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c == '_' || c == LIKE_ESCAPE {
+            escaped.push(LIKE_ESCAPE);
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn like(pattern: String) -> LikeExpr {
+    LikeExpr::new(pattern).escape(LIKE_ESCAPE)
+}
+
+impl<T> SeaQueryFilter for StringFilters<T>
+where
+    T: Clone + Display + FromStr + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let Some(col_id) = &self.1 else {
+            return Condition::all();
+        };
+
+        let col = column(col_id);
+        self.0
+            .iter()
+            .fold(Condition::all(), |cond, x| cond.add(string_expr(&col, x)))
+    }
+}
+
+/// Each attribute is read as text from the field's JSON column, `attrs ->> 'color'`.
+impl<T> SeaQueryFilter for DynamicFilters<T>
+where
+    T: Clone + Display + FromStr + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let Some(col_id) = &self.1 else {
+            return Condition::all();
+        };
+
+        self.0.iter().fold(Condition::all(), |cond, (name, x)| {
+            let attribute = Expr::expr(column(col_id).binary(PgBinOper::CastJsonField, name));
+            cond.add(string_expr(&attribute, x))
+        })
+    }
+}
+
+fn string_expr<T>(column: &Expr, filter: &StringFilter<T>) -> SimpleExpr
+where
+    T: Clone + Display + Into<Value>,
+{
+    let col = || column.clone();
+
+    match filter {
+        StringFilter::Eq(v) => col().eq(v.clone()),
+        StringFilter::Ne(v) => col().ne(v.clone()),
+        StringFilter::NullSafeEq(Some(v)) => col().binary(NULL_SAFE_EQ, v.clone()),
+        StringFilter::NullSafeEq(None) => col().is_null(),
+        StringFilter::Like(v) => col().like(v.to_string()),
+        StringFilter::NotLike(v) => col().not_like(v.to_string()),
+        // lowercasing both sides works on every database, not only those with ILIKE
+        StringFilter::ILike(v) => Expr::expr(Func::lower(col()))
+            .binary(BinOper::Like, Func::lower(Expr::val(v.to_string()))),
+        StringFilter::StartsWith(v) => {
+            col().like(like(format!("{}%", escape_like(&v.to_string()))))
+        }
+        StringFilter::EndsWith(v) => col().like(like(format!("%{}", escape_like(&v.to_string())))),
+        StringFilter::Contains(v) => col().like(like(format!("%{}%", escape_like(&v.to_string())))),
+        StringFilter::Regex(v) => col().binary(PgBinOper::Regex, v.to_string()),
+        StringFilter::IRegex(v) => col().binary(PgBinOper::RegexCaseInsensitive, v.to_string()),
+        StringFilter::Similar(v) => col().binary(PgBinOper::Similarity, v.to_string()),
+        StringFilter::ContainsUnaccent(v) => Expr::cust_with_exprs(
+            "unaccent($1) ILIKE unaccent($2) ESCAPE '\\'",
+            [
+                col().into(),
+                Expr::val(format!("%{}%", escape_like(&v.to_string()))).into(),
+            ],
+        ),
+        StringFilter::Between(low, high) => col().between(low.clone(), high.clone()),
+        StringFilter::LenGt(n) => Expr::expr(Func::char_length(col())).gt(*n as i64),
+        StringFilter::LenLt(n) => Expr::expr(Func::char_length(col())).lt(*n as i64),
+        StringFilter::LenEq(n) => Expr::expr(Func::char_length(col())).eq(*n as i64),
+        StringFilter::Not(inner) => string_expr(column, inner).not(),
+    }
+}
+
+impl<T> SeaQueryFilter for NumberFilters<T>
+where
+    T: Clone + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let Some(col_id) = &self.1 else {
+            return Condition::all();
+        };
+
+        self.0.iter().fold(Condition::all(), |cond, x| {
+            let col = column(col_id);
+            cond.add(match x {
+                NumberFilter::Eq(v) => col.eq(v.clone()),
+                NumberFilter::Ne(v) => col.ne(v.clone()),
+                NumberFilter::NullSafeEq(Some(v)) => col.binary(NULL_SAFE_EQ, v.clone()),
+                NumberFilter::NullSafeEq(None) => col.is_null(),
+                NumberFilter::Gt(v) => col.gt(v.clone()),
+                NumberFilter::Lt(v) => col.lt(v.clone()),
+                NumberFilter::Gte(v) => col.gte(v.clone()),
+                NumberFilter::Lte(v) => col.lte(v.clone()),
+                NumberFilter::Between(low, high) => col.between(low.clone(), high.clone()),
+                NumberFilter::Range(low, high) => {
+                    let low = match low {
+                        RangeBound::Inclusive(v) => col.clone().gte(v.clone()),
+                        RangeBound::Exclusive(v) => col.clone().gt(v.clone()),
+                    };
+                    let high = match high {
+                        RangeBound::Inclusive(v) => col.lte(v.clone()),
+                        RangeBound::Exclusive(v) => col.lt(v.clone()),
+                    };
+                    low.and(high)
+                }
+            })
+        })
+    }
+}
+
+impl<T> SeaQueryFilter for EqualFilter<T>
+where
+    T: Clone + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        match (self.0.as_slice(), &self.1) {
+            ([val], Some(col_id)) => Condition::all().add(column(col_id).eq(val.clone())),
+            ([_, ..], Some(col_id)) => {
+                Condition::all().add(column(col_id).is_in(self.values().cloned()))
+            }
+            _ => Condition::all(),
+        }
+    }
+}
+
+impl<T> SeaQueryFilter for NullableFilter<T>
+where
+    T: Clone + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let (Some(val), Some(col_id)) = (&self.0, &self.1) else {
+            return Condition::all();
+        };
+
+        let col = column(col_id);
+        Condition::all().add(match val {
+            Nullable::Null => col.is_null(),
+            Nullable::NotNull => col.is_not_null(),
+            Nullable::Value(v) => col.eq(v.clone()),
+        })
+    }
+}
+
+/// An empty list renders `1 = 2` for `in` and `1 = 1` for `not_in`.
+impl<T> SeaQueryFilter for InFilters<T>
+where
+    T: Clone + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let Some(col_id) = &self.1 else {
+            return Condition::all();
+        };
+
+        self.0.iter().fold(Condition::all(), |cond, x| {
+            let values = x.values().iter().cloned();
+            cond.add(match x {
+                InFilter::In(_) => column(col_id).is_in(values),
+                InFilter::NotIn(_) => column(col_id).is_not_in(values),
+            })
+        })
+    }
+}
+
+/// Renders `col @> ARRAY[ ?, ? ]`, which only Postgres supports.
+impl<T> SeaQueryFilter for ArrayFilters<T>
+where
+    T: Clone + Into<Value>,
+{
+    fn condition(&self) -> Condition {
+        let Some(col_id) = &self.1 else {
+            return Condition::all();
+        };
+
+        self.0.iter().fold(Condition::all(), |cond, x| {
+            let ArrayFilter::Contains(values) = x;
+            // the tokenizer of a custom expression reads `[` as a quote, so the brackets
+            // are pieces of their own, spaced from the values to keep the placeholders apart
+            let placeholders = (2..values.len() + 2)
+                .map(|i| format!("${i}"))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let pieces = std::iter::once(Expr::cust("ARRAY["))
+                .chain(values.iter().map(|v| Expr::val(v.clone()).into()))
+                .chain(std::iter::once(Expr::cust("]")));
+            let array =
+                Expr::cust_with_exprs(format!("$1 {placeholders} ${}", values.len() + 2), pieces);
+            cond.add(column(col_id).binary(PgBinOper::Contains, array))
+        })
+    }
+}
+
+/// Each column matches `LOWER(col) LIKE LOWER(?)`, ORed together.
+impl SeaQueryFilter for SearchFilter {
+    fn condition(&self) -> Condition {
+        let Some(query) = self.query.as_ref().filter(|_| !self.is_empty()) else {
+            return Condition::all();
+        };
+
+        let pattern = format!("%{}%", escape_like(query));
+        let prefix = self.id.as_ref().and_then(|x| x.prefix());
+
+        let any = self.columns().iter().fold(Condition::any(), |cond, name| {
+            let id = match prefix {
+                Some(prefix) => FilterId::WithPrefix(prefix.to_string(), name.clone()),
+                None => FilterId::Alone(name.clone()),
+            };
+
+            cond.add(Expr::expr(Func::lower(column(&id))).like(like(pattern.to_lowercase())))
+        });
+
+        Condition::all().add(any)
+    }
+}
+
+/// Renders `to_tsvector('english', col) @@ plainto_tsquery('english', ?)`, which only
+/// Postgres supports.
+impl SeaQueryFilter for FullTextFilter {
+    fn condition(&self) -> Condition {
+        let (Some(query), Some(col)) = (&self.query, self.column()) else {
+            return Condition::all();
+        };
+
+        let id = match self.id.as_ref().and_then(|x| x.prefix()) {
+            Some(prefix) => FilterId::WithPrefix(prefix.to_string(), col.to_string()),
+            None => FilterId::Alone(col.to_string()),
+        };
+
+        // the language was checked when it was set, so it is safe to inline
+        let language = self.language();
+        Condition::all().add(Expr::cust_with_exprs(
+            format!("to_tsvector('{language}', $1) @@ plainto_tsquery('{language}', $2)"),
+            [column(&id).into(), Expr::val(query.clone()).into()],
+        ))
+    }
+}
+
+impl OrderBy {
+    /// Returns the expression to sort on and its direction. `Random` sorts on
+    /// `RANDOM()`, or `RAND()` on MySQL.
+    pub fn order_expr(&self) -> (SimpleExpr, Order) {
+        match self {
+            OrderBy::Asc(id) => (column(id).into(), Order::Asc),
+            OrderBy::Desc(id) => (column(id).into(), Order::Desc),
+            OrderBy::Random => (Func::random().into(), Order::Asc),
+        }
+    }
+}
+
+impl Sort {
+    /// Returns the expression and direction of every sort key, in order.
+    pub fn order_exprs(&self) -> Vec<(SimpleExpr, Order)> {
+        self.0.iter().map(OrderBy::order_expr).collect()
+    }
+}
+
+impl<T> FromQueryFilter<T>
+where
+    T: SeaQueryFilter + Default + crate::common::WithFilterId + FromStr,
+{
+    /// Adds the conditions of the inner filter to the `WHERE` clause of `select`, if it
+    /// has any, then its sort (see [`FromQueryFilter::sort`]), `limit` and `skip`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = FromQueryFilter::<UserFilter>::from_str("age[gte]=18&limit=10")?;
+    ///
+    /// let mut select = Query::select();
+    /// select.column(Asterisk).from(Alias::new("users"));
+    /// filter.apply_to_select(&mut select);
+    /// ```
+    pub fn apply_to_select(&self, select: &mut SelectStatement) {
+        let cond = self.inner.condition();
+        if !cond.is_empty() {
+            select.cond_where(cond);
+        }
+
+        for (expr, order) in self.sort().order_exprs() {
+            select.order_by_expr(expr, order);
+        }
+
+        if let Some(limit) = self.limit {
+            select.limit(limit.0);
+        }
+
+        if let Some(skip) = self.skip {
+            select.offset(skip.0);
+        }
+    }
+}
@@ -0,0 +1,182 @@
+use crate::{
+    common::{from_str_with, FromStrFilter, QueryParams},
+    errors::FilterParseError,
+    filter::{Filter, Merge, VisitConditions},
+    filter_id::FilterId,
+    filter_value::FilterValue,
+    op::Op,
+    options::ParseOptions,
+};
+
+/// A single search term matched against several columns, as behind a search box.
+///
+/// Parses `q=term` into the term. A row matches if any of the columns set with
+/// [`SearchFilter::on_columns`] contains it, ignoring case. `%` and `_` in the term
+/// match literally.
+///
+/// With SQLx, applying it emits `(name ILIKE ? OR email ILIKE ?)` on Postgres, and
+/// `(LOWER(name) LIKE LOWER(?) OR ...)` elsewhere, binding `%term%`. With the derive,
+/// the columns are set with `#[filtrum(search(name, email))]` on the field.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::search_filter::SearchFilter;
+///
+/// let filter = SearchFilter::from_str("q", "q=alice&limit=10")
+///     .unwrap()
+///     .on_columns(&["name", "email"]);
+///
+/// assert_eq!(filter.query.as_deref(), Some("alice"));
+/// assert_eq!(filter.columns(), ["name", "email"]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SearchFilter {
+    /// The search term, if present.
+    pub query: Option<String>,
+    /// The parameter the search term is read from.
+    pub id: Option<FilterId>,
+    columns: Vec<String>,
+}
+
+struct SearchQuery(String);
+
+impl FromStrFilter<String> for SearchQuery {
+    fn from_str(op: &Op, value: String) -> Result<Self, FilterParseError> {
+        match op {
+            Op::Eq => Ok(SearchQuery(value)),
+            _ => Err(FilterParseError::UnknownFilter),
+        }
+    }
+}
+
+impl SearchFilter {
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value(search_id.to_string().into(), value)
+    }
+
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        let query = from_str_with::<String, SearchQuery>(search_id.id(), value, options)?
+            .into_iter()
+            .next()
+            .map(|x| x.0);
+
+        Ok(Self {
+            query,
+            id: Some(search_id),
+            ..Self::default()
+        })
+    }
+
+    /// Sets the columns to search. They are qualified with the prefix of the
+    /// parameter's `FilterId`, if it has one.
+    pub fn on_columns(mut self, columns: &[&str]) -> Self {
+        self.columns = columns.iter().map(|x| x.to_string()).collect();
+        self
+    }
+
+    /// Returns the columns being searched.
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// Returns `true` if there is nothing to search: no term, or no columns.
+    pub fn is_empty(&self) -> bool {
+        self.query.is_none() || self.columns.is_empty()
+    }
+}
+
+/// The term is an `eq`, given only when there are columns to search.
+impl Filter for SearchFilter {
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        SearchFilter::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.id.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        SearchFilter::is_empty(self)
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        match &self.query {
+            Some(query) if !self.columns.is_empty() => {
+                vec![(Op::Eq, FilterValue::String(query.clone()))]
+            }
+            _ => Vec::new(),
+        }
+    }
+}
+
+impl VisitConditions for SearchFilter {
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        let Some(query) = &self.query else {
+            return;
+        };
+
+        let prefix = self.id.as_ref().and_then(|x| x.prefix());
+        for name in &self.columns {
+            let column = match prefix {
+                Some(prefix) => format!("{prefix}.{name}"),
+                None => name.clone(),
+            };
+
+            visit(column, Op::Contains, FilterValue::String(query.clone()));
+        }
+    }
+}
+
+/// A term in `other` replaces the term in `self`; the columns are kept.
+impl Merge for SearchFilter {
+    fn merge(&mut self, other: Self) {
+        if other.query.is_some() {
+            self.query = other.query;
+        }
+
+        if self.id.is_none() {
+            self.id = other.id;
+        }
+
+        if self.columns.is_empty() {
+            self.columns = other.columns;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_from_str() {
+        let f = SearchFilter::from_str("q", "q=al%ice&name=x")
+            .unwrap()
+            .on_columns(&["name", "email"]);
+        assert_eq!(f.query.as_deref(), Some("al%ice"));
+        assert_eq!(f.columns(), ["name", "email"]);
+        assert!(!f.is_empty());
+
+        let f = SearchFilter::from_str("q", "name=x").unwrap();
+        assert_eq!(f.query, None);
+        assert!(f.is_empty());
+
+        assert!(matches!(
+            SearchFilter::from_str("q", "q[gt]=1"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+    }
+}
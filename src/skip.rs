@@ -1,11 +1,20 @@
+use serde::{Deserialize, Serialize};
+
 use crate::{
-    common::{from_str, FromStrFilter},
+    common::{from_str_with, FromStrFilter, QueryParams},
     errors::FilterParseError,
+    op::Op,
+    options::ParseOptions,
 };
 
 /// Represents a skip/offset (pagination) value.
 ///
-/// Parses `skip=N` from the query string.
+/// Parses `skip=N` from the query string. If the parameter is repeated the last value
+/// wins, unless [`ParseOptions::strict`] is set, in which case it is an error. The
+/// parameter can be renamed with [`ParseOptions::reserved`], and capped with
+/// [`ParseOptions::max_skip`].
+///
+/// Serializes as the bare number, e.g. `10`.
 ///
 /// # Example
 ///
@@ -17,20 +26,57 @@ use crate::{
 ///
 /// assert_eq!(skip.0, 10);
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Skip(pub u64);
 
 impl FromStrFilter<u64> for Skip {
-    fn from_str(_id: &str, value: u64) -> Result<Self, FilterParseError> {
+    fn from_str(_op: &Op, value: u64) -> Result<Self, FilterParseError> {
         Ok(Skip(value))
     }
 }
 
 impl Skip {
+    /// Creates an offset of `skip` rows, for queries built in code rather than parsed.
+    ///
+    /// Unlike [`Skip::from_str_with`], it isn't capped by [`ParseOptions::max_skip`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use filtrum::skip::Skip;
+    ///
+    /// assert_eq!(Skip::new(10), Skip::from_str("skip=10").unwrap().unwrap());
+    /// ```
+    pub fn new(skip: u64) -> Self {
+        Self(skip)
+    }
+
+    // `None` when the parameter is absent, which `FromStr` can't express
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(value: &str) -> Result<Option<Self>, FilterParseError> {
-        let u = from_str("skip", value)?.first().cloned();
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Option<Self>, FilterParseError> {
+        let all = from_str_with::<u64, Self>(options.reserved.skip, value, options)?;
 
-        Ok(u)
+        if options.strict && all.len() > 1 {
+            return Err(FilterParseError::Duplicate);
+        }
+
+        let skip = all.last().cloned();
+
+        if let (Some(Skip(skip)), Some(max)) = (skip, options.max_skip)
+            && skip > max
+        {
+            return Err(FilterParseError::SkipTooLarge(max));
+        }
+
+        Ok(skip)
     }
 }
 
@@ -53,4 +99,47 @@ mod tests {
         let s = Skip::from_str(qs);
         assert!(s.is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_skip_duplicates() {
+        let qs = "skip=10&skip=20";
+        let v = Skip::from_str(qs).unwrap().unwrap();
+        assert_eq!(v.0, 20);
+
+        let options = ParseOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let v = Skip::from_str_with(qs, &options);
+        assert!(matches!(v, Err(FilterParseError::Duplicate)));
+
+        let v = Skip::from_str_with("skip=10", &options).unwrap().unwrap();
+        assert_eq!(v.0, 10);
+    }
+
+    #[test]
+    fn test_skip_max() {
+        let options = ParseOptions {
+            max_skip: Some(10_000),
+            ..Default::default()
+        };
+
+        for qs in ["skip=0", "skip=9999", "skip=10000", "limit=5"] {
+            assert!(Skip::from_str_with(qs, &options).is_ok(), "{qs}");
+        }
+
+        for qs in ["skip=10001", "skip=1000000000000", "skip=1&skip=20000"] {
+            assert!(
+                matches!(
+                    Skip::from_str_with(qs, &options),
+                    Err(FilterParseError::SkipTooLarge(10_000))
+                ),
+                "{qs}"
+            );
+        }
+
+        // without a maximum any offset is accepted
+        let v = Skip::from_str("skip=1000000000000").unwrap().unwrap();
+        assert_eq!(v.0, 1_000_000_000_000);
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    common::{FromStrFilter, QueryParams, from_str_with},
+    errors::FilterParseError,
+    filter_id::FilterId,
+    op::Op,
+    options::ParseOptions,
+    order_by::OrderBy,
+};
+
+/// An ordered list of sorting instructions.
+///
+/// Parses every `order_by[asc]=field` / `order_by[desc]=field` in the query string, in
+/// the order they appear. Earlier entries take precedence. The shorthand form accepts a
+/// comma list, with a leading `-` for descending: `order_by=-age,name`.
+///
+/// A column is only sorted on once: a repeated entry is dropped, and so is a later one
+/// in the other direction, unless [`ParseOptions::strict`] is set, which rejects it with
+/// `FilterParseError::Duplicate`.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::sort::Sort;
+///
+/// let query = "order_by[desc]=age&order_by[asc]=name";
+/// let sort = Sort::from_str(query).unwrap().with_tiebreak("id".to_string().into());
+///
+/// assert_eq!(sort.0.len(), 3);
+/// assert_eq!(sort.0[2].id().unwrap().id(), "id");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Sort(pub Vec<OrderBy>);
+
+impl FromStrFilter<String> for Sort {
+    fn from_str(op: &Op, value: String) -> Result<Self, FilterParseError> {
+        OrderBy::parse_list(op, &value).map(Self)
+    }
+}
+
+impl Sort {
+    // inherent like `from_str_with`, which takes the options `FromStr` has no room for
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Result<Self, FilterParseError> {
+        Self::from_str_with(value, &ParseOptions::default())
+    }
+
+    /// Like [`Sort::from_str`], reading the parameter named in `options.reserved`.
+    pub fn from_str_with<'a>(
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        let defaults = ParseOptions::default();
+        let all = from_str_with::<String, Sort>(options.reserved.order_by, value, &defaults)?
+            .into_iter()
+            .flat_map(|x| x.0);
+
+        let mut data: Vec<OrderBy> = Vec::new();
+        for order_by in all {
+            match data.iter().find(|x| x.id() == order_by.id()) {
+                None => data.push(order_by),
+                Some(seen) if options.strict && *seen != order_by => {
+                    return Err(FilterParseError::Duplicate);
+                }
+                Some(_) => {}
+            }
+        }
+
+        Ok(Self(data))
+    }
+
+    pub fn from_str_prefix(prefix: &str, value: &str) -> Result<Self, FilterParseError> {
+        let data = Self::from_str(value)?
+            .0
+            .into_iter()
+            .map(|x| x.with_prefix(prefix))
+            .collect();
+
+        Ok(Self(data))
+    }
+
+    /// Returns `true` if there are no sorting instructions.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Appends an ascending sort on `id` as the last key, so rows that compare equal on
+    /// every other key still come back in a deterministic order.
+    ///
+    /// Nothing is appended if `id` is already sorted on.
+    pub fn with_tiebreak(mut self, id: FilterId) -> Self {
+        if !self
+            .0
+            .iter()
+            .any(|x| x.id().is_some_and(|x| x.key() == id.key()))
+        {
+            self.0.push(OrderBy::Asc(id));
+        }
+
+        self
+    }
+}
+
+impl From<Option<OrderBy>> for Sort {
+    fn from(value: Option<OrderBy>) -> Self {
+        Self(value.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sort_from_str() {
+        let qs = "order_by[desc]=age&name=x&order_by[asc]=name";
+        let sort = Sort::from_str(qs).unwrap();
+        assert_eq!(
+            sort.0,
+            vec![
+                OrderBy::Desc("age".to_string().into()),
+                OrderBy::Asc("name".to_string().into())
+            ]
+        );
+
+        let sort = Sort::from_str_prefix("users", qs).unwrap();
+        assert_eq!(sort.0[0].id().and_then(|x| x.prefix()), Some("users"));
+    }
+
+    #[test]
+    fn test_sort_tiebreak() {
+        let sort = Sort::from_str("order_by[desc]=age")
+            .unwrap()
+            .with_tiebreak("id".to_string().into());
+        assert_eq!(
+            sort.0,
+            vec![
+                OrderBy::Desc("age".to_string().into()),
+                OrderBy::Asc("id".to_string().into())
+            ]
+        );
+
+        // already sorted on, so it is kept as requested and not repeated
+        let sort = Sort::from_str("order_by[desc]=id")
+            .unwrap()
+            .with_tiebreak("id".to_string().into())
+            .with_tiebreak("id".to_string().into());
+        assert_eq!(sort.0, vec![OrderBy::Desc("id".to_string().into())]);
+    }
+
+    #[test]
+    fn test_sort_repeated_columns() {
+        let sort =
+            Sort::from_str("order_by[asc]=a&order_by[asc]=a&order_by=-b,a&order_by=b").unwrap();
+        assert_eq!(
+            sort.0,
+            vec![
+                OrderBy::Asc("a".to_string().into()),
+                OrderBy::Desc("b".to_string().into())
+            ]
+        );
+
+        let sort = Sort::from_str("order_by[rand]=1&order_by[rand]=1").unwrap();
+        assert_eq!(sort.0, vec![OrderBy::Random]);
+
+        let options = ParseOptions {
+            strict: true,
+            ..Default::default()
+        };
+
+        // the same entry twice is harmless, even when strict
+        let sort = Sort::from_str_with("order_by[asc]=a&order_by=a", &options).unwrap();
+        assert_eq!(sort.0, vec![OrderBy::Asc("a".to_string().into())]);
+
+        assert!(matches!(
+            Sort::from_str_with("order_by[asc]=a&order_by[desc]=a", &options),
+            Err(FilterParseError::Duplicate)
+        ));
+        assert!(matches!(
+            Sort::from_str_with("order_by=a,-a", &options),
+            Err(FilterParseError::Duplicate)
+        ));
+    }
+
+    #[test]
+    fn test_sort_shorthand_list() {
+        let sort = Sort::from_str("order_by=-age,name").unwrap();
+        assert_eq!(
+            sort.0,
+            vec![
+                OrderBy::Desc("age".to_string().into()),
+                OrderBy::Asc("name".to_string().into())
+            ]
+        );
+
+        let sort = Sort::from_str("order_by=-age&order_by[desc]=name,id").unwrap();
+        assert_eq!(
+            sort.0,
+            vec![
+                OrderBy::Desc("age".to_string().into()),
+                OrderBy::Desc("name".to_string().into()),
+                OrderBy::Desc("id".to_string().into())
+            ]
+        );
+    }
+}
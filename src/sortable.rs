@@ -0,0 +1,172 @@
+use crate::{errors::FilterParseError, order_by::OrderBy};
+
+/// A closed set of columns a query may sort on, usually an enum.
+///
+/// Each variant is parsed from the name a client sends and mapped to the column it
+/// sorts on, so only those columns ever reach the SQL.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::sortable::{Sortable, TypedOrderBy};
+///
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// enum UserSort {
+///     Name,
+///     Age,
+/// }
+///
+/// impl Sortable for UserSort {
+///     fn from_name(name: &str) -> Option<Self> {
+///         match name {
+///             "name" => Some(UserSort::Name),
+///             "age" => Some(UserSort::Age),
+///             _ => None,
+///         }
+///     }
+///
+///     fn column(&self) -> &'static str {
+///         match self {
+///             UserSort::Name => "users.name",
+///             UserSort::Age => "users.age",
+///         }
+///     }
+/// }
+///
+/// let order = TypedOrderBy::<UserSort>::from_str("order_by=-age").unwrap();
+/// assert_eq!(order, Some(TypedOrderBy::Desc(UserSort::Age)));
+///
+/// assert!(TypedOrderBy::<UserSort>::from_str("order_by=password").is_err());
+/// ```
+pub trait Sortable: Sized {
+    /// Returns the variant for a name from the query, or `None` if it can't be sorted on.
+    fn from_name(name: &str) -> Option<Self>;
+
+    /// The column rendered in `ORDER BY`.
+    fn column(&self) -> &'static str;
+}
+
+/// An [`OrderBy`] whose column is one of the variants of `S`.
+///
+/// Parses the same `order_by` syntax, but fails with `FilterParseError::UnknownField`
+/// for any column `S` does not know.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypedOrderBy<S> {
+    /// Ascending order.
+    Asc(S),
+    /// Descending order.
+    Desc(S),
+    /// Random order, for shuffled results.
+    Random,
+}
+
+impl<S: Sortable> TypedOrderBy<S> {
+    // `None` when the parameter is absent, which `FromStr` can't express
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(value: &str) -> Result<Option<Self>, FilterParseError> {
+        OrderBy::from_str(value)?.map(Self::try_from).transpose()
+    }
+
+    /// Returns the sorted variant, or `None` for [`TypedOrderBy::Random`].
+    pub fn sortable(&self) -> Option<&S> {
+        match self {
+            TypedOrderBy::Asc(s) => Some(s),
+            TypedOrderBy::Desc(s) => Some(s),
+            TypedOrderBy::Random => None,
+        }
+    }
+}
+
+impl<S: Sortable> TryFrom<OrderBy> for TypedOrderBy<S> {
+    type Error = FilterParseError;
+
+    fn try_from(value: OrderBy) -> Result<Self, Self::Error> {
+        let parse = |id: &crate::filter_id::FilterId| {
+            S::from_name(id.id()).ok_or_else(|| FilterParseError::UnknownField(id.id().to_string()))
+        };
+
+        Ok(match value {
+            OrderBy::Asc(id) => TypedOrderBy::Asc(parse(&id)?),
+            OrderBy::Desc(id) => TypedOrderBy::Desc(parse(&id)?),
+            OrderBy::Random => TypedOrderBy::Random,
+        })
+    }
+}
+
+/// The untyped form, sorting on the mapped column.
+impl<S: Sortable> From<&TypedOrderBy<S>> for OrderBy {
+    fn from(value: &TypedOrderBy<S>) -> Self {
+        let column = |s: &S| s.column().to_string().into();
+
+        match value {
+            TypedOrderBy::Asc(s) => OrderBy::Asc(column(s)),
+            TypedOrderBy::Desc(s) => OrderBy::Desc(column(s)),
+            TypedOrderBy::Random => OrderBy::Random,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum UserSort {
+        Name,
+        CreatedAt,
+    }
+
+    impl Sortable for UserSort {
+        fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "name" => Some(UserSort::Name),
+                "created_at" => Some(UserSort::CreatedAt),
+                _ => None,
+            }
+        }
+
+        fn column(&self) -> &'static str {
+            match self {
+                UserSort::Name => "name",
+                UserSort::CreatedAt => "created",
+            }
+        }
+    }
+
+    #[test]
+    fn test_typed_order_by() {
+        let ob = TypedOrderBy::<UserSort>::from_str("order_by[desc]=created_at")
+            .unwrap()
+            .unwrap();
+        assert_eq!(ob, TypedOrderBy::Desc(UserSort::CreatedAt));
+        assert_eq!(ob.sortable(), Some(&UserSort::CreatedAt));
+        assert_eq!(
+            OrderBy::from(&ob),
+            OrderBy::Desc("created".to_string().into())
+        );
+
+        let ob = TypedOrderBy::<UserSort>::from_str("order_by=name").unwrap();
+        assert_eq!(ob, Some(TypedOrderBy::Asc(UserSort::Name)));
+
+        let ob = TypedOrderBy::<UserSort>::from_str("order_by[rand]=1").unwrap();
+        assert_eq!(ob, Some(TypedOrderBy::Random));
+
+        assert_eq!(TypedOrderBy::<UserSort>::from_str("limit=1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_typed_order_by_unknown_column() {
+        // the column name itself isn't accepted, only the names `from_name` knows
+        for qs in [
+            "order_by=created",
+            "order_by=-password",
+            "order_by[asc]=name;DROP TABLE users",
+        ] {
+            let err = TypedOrderBy::<UserSort>::from_str(qs).unwrap_err();
+            assert!(matches!(err, FilterParseError::UnknownField(_)), "{qs}");
+        }
+
+        let err = TypedOrderBy::<UserSort>::from_str("order_by=password").unwrap_err();
+        assert_eq!(err.to_string(), "unknown field `password`");
+    }
+}
@@ -1,20 +1,121 @@
-use sqlx::{Database, Encode, QueryBuilder, Type};
+pub use sqlx::{Database, QueryBuilder};
+use sqlx::{Encode, Type};
+use std::collections::{HashMap, HashSet};
 use std::fmt::Display;
 use std::str::FromStr;
+use std::sync::Arc;
 
+#[cfg(feature = "postgres")]
 use crate::{
+    array_filter::{ArrayFilter, ArrayFilters},
+    full_text_filter::FullTextFilter,
+};
+use crate::{
+    dynamic_filter::DynamicFilters,
     equal_filter::EqualFilter,
+    errors::FilterParseError,
+    filter_id::FilterId,
+    in_filter::{InFilter, InFilters},
     limit::Limit,
+    nullable_filter::{Nullable, NullableFilter},
     number_filter::{NumberFilter, NumberFilters},
     order_by::OrderBy,
     query_filter::FromQueryFilter,
+    search_filter::SearchFilter,
     skip::Skip,
+    sort::Sort,
+    sortable::{Sortable, TypedOrderBy},
     string_filter::{StringFilter, StringFilters},
 };
 
+/// The SQL flavour of a `sqlx::Database`, for the few operators whose syntax differs
+/// between databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dialect {
+    Postgres,
+    MySql,
+    Sqlite,
+    /// Any other database, which gets the most portable SQL available.
+    Other,
+}
+
+impl Dialect {
+    /// Returns the dialect of `DB`.
+    pub fn of<DB: Database>() -> Self {
+        match DB::NAME {
+            "PostgreSQL" => Dialect::Postgres,
+            "MySQL" => Dialect::MySql,
+            "SQLite" => Dialect::Sqlite,
+            _ => Dialect::Other,
+        }
+    }
+
+    /// Quotes `ident` as an identifier: with backticks on MySQL and double quotes
+    /// elsewhere. Quote characters inside `ident` are doubled.
+    pub fn quote_identifier(&self, ident: &str) -> String {
+        let quote = match self {
+            Dialect::MySql => '`',
+            _ => '"',
+        };
+
+        let mut quoted = String::with_capacity(ident.len() + 2);
+        quoted.push(quote);
+        for c in ident.chars() {
+            if c == quote {
+                quoted.push(quote);
+            }
+            quoted.push(c);
+        }
+        quoted.push(quote);
+        quoted
+    }
+
+    /// The NULL-safe equality operator, padded with spaces: ` <=> ` on MySQL and
+    /// ` IS NOT DISTINCT FROM ` elsewhere.
+    pub fn null_safe_eq(&self) -> &'static str {
+        match self {
+            Dialect::MySql => " <=> ",
+            _ => " IS NOT DISTINCT FROM ",
+        }
+    }
+
+    /// Reads the top-level `key` of the JSON in `column` as text: `column->>'key'` on
+    /// Postgres, `column->>'$.key'` on MySQL, and `json_extract(column, '$.key')`
+    /// elsewhere. Single quotes inside `key` are doubled, and so are backslashes on
+    /// MySQL, which reads them as escapes; `DynamicFilters` only parses keys made of
+    /// word characters anyway.
+    pub fn json_text(&self, column: &str, key: &str) -> String {
+        let mut key = key.replace('\'', "''");
+        if *self == Dialect::MySql {
+            key = key.replace('\\', "\\\\");
+        }
+
+        match self {
+            Dialect::Postgres => format!("{column}->>'{key}'"),
+            Dialect::MySql => format!("{column}->>'$.{key}'"),
+            Dialect::Sqlite | Dialect::Other => format!("json_extract({column}, '$.{key}')"),
+        }
+    }
+}
+
 /// A trait for applying filters to a `sqlx::QueryBuilder`.
 ///
 /// This trait allows converting structured filters into SQL clauses appended to a query builder.
+/// Columns with a prefix (e.g. from `#[filtrum(table = "users")]`) are emitted qualified, as
+/// `users.age`, so the same column name can be filtered on in joined tables.
+///
+/// The trait is object safe: filters of different types can be collected as
+/// `Vec<Box<dyn SqlxFilter<DB>>>` for one database and applied in turn.
+///
+/// # Condition order
+///
+/// Conditions are emitted in a fixed order, so the same filter always renders the same
+/// SQL: fields in declaration order for a derived struct (the order given to
+/// [`apply_all`] otherwise), and the conditions of each field in the order they appear
+/// in the query. The order of the fields in the query doesn't matter. A condition
+/// replaced by [`Merge`](crate::filter::Merge) moves to the end of its field. With
+/// [`ApplyOptions::canonical_order`], the conditions of each field are sorted by
+/// operator instead. `FromQueryFilter` adds `ORDER BY`, `LIMIT` and `OFFSET` after all of them.
 ///
 /// # Example
 ///
@@ -32,177 +133,1986 @@ use crate::{
 pub trait SqlxFilter<DB: Database> {
     /// Appends the filter conditions to the given query builder.
     fn apply<'a>(&self, query_builder: &mut QueryBuilder<'a, DB>);
+
+    /// Appends the filter conditions, rendered according to `options`. Filters that have
+    /// nothing to configure keep the default, which is the same as [`SqlxFilter::apply`].
+    fn apply_with<'a>(&self, query_builder: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        let _ = options;
+        self.apply(query_builder);
+    }
+
+    /// Pushes `separator` followed by this filter's conditions as a single parenthesized
+    /// group, `(a AND b)`. Returns `false`, pushing nothing, if there are no conditions.
+    ///
+    /// Used by [`apply_all`] to combine fields with OR. The default renders
+    /// [`SqlxFilter::apply_with`] inside the group, as `(1=1 AND a AND b)`. Filters that
+    /// don't add `WHERE` conditions, like [`Limit`] or [`OrderBy`], push nothing.
+    fn push_group<'a>(
+        &self,
+        query_builder: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        // rendered on its own first, so an empty filter pushes no group at all
+        let mut probe = QueryBuilder::<DB>::new("");
+        self.apply_with(&mut probe, options);
+        if probe.sql().is_empty() {
+            return false;
+        }
+
+        query_builder.push(separator);
+        query_builder.push("(1=1");
+        self.apply_with(query_builder, options);
+        query_builder.push(")");
+        true
+    }
+}
+
+impl<DB, F> SqlxFilter<DB> for Box<F>
+where
+    DB: Database,
+    F: SqlxFilter<DB> + ?Sized,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        (**self).apply(qb);
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        (**self).apply_with(qb, options);
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        (**self).push_group(qb, separator, options)
+    }
+}
+
+/// An optional filter applies the inner one when `Some` and adds nothing when `None`, so
+/// fields like `Option<StringFilters>` can be applied without matching on them first.
+impl<DB, F> SqlxFilter<DB> for Option<F>
+where
+    DB: Database,
+    F: SqlxFilter<DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        if let Some(filter) = self {
+            filter.apply(qb);
+        }
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if let Some(filter) = self {
+            filter.apply_with(qb, options);
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        self.as_ref()
+            .is_some_and(|filter| filter.push_group(qb, separator, options))
+    }
+}
+
+/// Options for [`SqlxFilter::apply_with`].
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::sqlx::{ApplyOptions, Conjunction};
+///
+/// let options = ApplyOptions {
+///     conjunction: Conjunction::Or,
+///     ..Default::default()
+/// };
+///
+/// assert_eq!(options.like_escape, '\\');
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApplyOptions {
+    /// How the conditions of different fields are combined. Defaults to AND.
+    pub conjunction: Conjunction,
+    /// The character used to escape `%` and `_` in `starts_with`, `ends_with` and
+    /// `contains` values, emitted in their `ESCAPE` clause. Defaults to `\`.
+    pub like_escape: char,
+    /// Quote column names with [`Dialect::quote_identifier`], e.g. `` `users`.`age` `` on
+    /// MySQL. Needed for columns named after reserved words. Defaults to `false`.
+    pub quote_identifiers: bool,
+    /// Columns to render instead of the default ones, by field. Empty by default.
+    pub columns: ColumnMap,
+    /// Functions to wrap the column of some fields in, by field. Empty by default.
+    pub transforms: Transforms,
+    /// The fields of a derived filter that are applied. Defaults to all of them.
+    pub fields: FieldAccess,
+    /// Aliases to qualify columns with instead of their table, by table. Empty by
+    /// default.
+    pub table_aliases: TableAliases,
+    /// Emit the conditions of each field sorted by operator key instead of in query
+    /// order, so `age[lt]=65&age[gte]=18` and `age[gte]=18&age[lt]=65` render the same
+    /// SQL text, which keeps database plan caches from filling with permutations. Fields
+    /// are always emitted in declaration order. Defaults to `false`.
+    pub canonical_order: bool,
+}
+
+impl Default for ApplyOptions {
+    fn default() -> Self {
+        Self {
+            conjunction: Conjunction::And,
+            like_escape: '\\',
+            quote_identifiers: false,
+            columns: ColumnMap::default(),
+            transforms: Transforms::default(),
+            fields: FieldAccess::default(),
+            table_aliases: TableAliases::default(),
+            canonical_order: false,
+        }
+    }
+}
+
+/// A table of the SQL column to render for each field, for a filter struct shared by
+/// several tables or views whose columns are named differently.
+///
+/// Fields are looked up by their name, e.g. `age` for a field `age` in a struct with
+/// `#[filtrum(table = "users")]`, and a mapped column replaces the whole qualified
+/// column. It is rendered as written, even with
+/// [`quote_identifiers`](ApplyOptions::quote_identifiers), so quote it yourself if
+/// needed. Fields that aren't mapped keep their default column.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = ApplyOptions {
+///     columns: ColumnMap::new().with("age", "v.age_years"),
+///     ..Default::default()
+/// };
+///
+/// // ... WHERE 1=1 AND v.age_years >= ?
+/// filter.apply_with(&mut qb, &options);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ColumnMap(HashMap<String, String>);
+
+impl ColumnMap {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Renders `column` for `field`.
+    pub fn with(mut self, field: &str, column: &str) -> Self {
+        self.0.insert(field.to_string(), column.to_string());
+        self
+    }
+
+    /// Returns the column mapped to `field`, if any.
+    pub fn get(&self, field: &str) -> Option<&str> {
+        self.0.get(field).map(String::as_str)
+    }
+}
+
+/// A table of the alias each table has in the query, for joins whose `FROM ... JOIN ...`
+/// aliases differ from the table names in `#[filtrum(table = "...")]`.
+///
+/// Columns prefixed with a mapped table are qualified with its alias instead, in
+/// conditions and in `ORDER BY`; other tables keep their name. A column mapped in
+/// [`ColumnMap`] is used as written.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = ApplyOptions {
+///     table_aliases: TableAliases::new().with("users", "u").with("orders", "o"),
+///     ..Default::default()
+/// };
+///
+/// // SELECT ... FROM users u JOIN orders o ON o.user_id = u.id WHERE 1=1 AND o.total >= ?
+/// filter.apply_with(&mut qb, &options);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TableAliases(HashMap<String, String>);
+
+impl TableAliases {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Qualifies the columns of `table` with `alias`.
+    pub fn with(mut self, table: &str, alias: &str) -> Self {
+        self.0.insert(table.to_string(), alias.to_string());
+        self
+    }
+
+    /// Returns the alias of `table`, if any.
+    pub fn get(&self, table: &str) -> Option<&str> {
+        self.0.get(table).map(String::as_str)
+    }
+}
+
+/// A SQL function a column is wrapped in before it is compared, e.g. `DATE(created_at) = ?`.
+///
+/// Only these functions are available, so a name coming from configuration can't inject
+/// SQL. They are parsed by name, ignoring case: `"date"` is [`Transform::Date`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transform {
+    /// `LOWER(column)`
+    Lower,
+    /// `UPPER(column)`
+    Upper,
+    /// `TRIM(column)`
+    Trim,
+    /// `DATE(column)`, the date part of a timestamp.
+    Date,
+}
+
+impl Transform {
+    /// Returns the SQL name of the function, e.g. `DATE`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Transform::Lower => "LOWER",
+            Transform::Upper => "UPPER",
+            Transform::Trim => "TRIM",
+            Transform::Date => "DATE",
+        }
+    }
+
+    fn wrap(&self, column: &str) -> String {
+        format!("{}({column})", self.name())
+    }
+}
+
+impl FromStr for Transform {
+    type Err = FilterParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_uppercase().as_str() {
+            "LOWER" => Ok(Transform::Lower),
+            "UPPER" => Ok(Transform::Upper),
+            "TRIM" => Ok(Transform::Trim),
+            "DATE" => Ok(Transform::Date),
+            _ => Err(FilterParseError::UnknownFilter),
+        }
+    }
+}
+
+/// A table of the [`Transform`] to wrap the column of each field in, the runtime
+/// counterpart of `#[filtrum(transform = "DATE")]`.
+///
+/// Fields are looked up by their name, like in [`ColumnMap`], and a mapped column is
+/// wrapped too. The transform applies to conditions only: `ORDER BY` keeps the plain
+/// column. A transform set here replaces the one from the derive attribute.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = ApplyOptions {
+///     transforms: Transforms::new().with("email", Transform::Lower),
+///     ..Default::default()
+/// };
+///
+/// // ... WHERE 1=1 AND LOWER(users.email) = ?
+/// filter.apply_with(&mut qb, &options);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Transforms(HashMap<String, Transform>);
+
+impl Transforms {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps the column of `field` in `transform`.
+    pub fn with(mut self, field: &str, transform: Transform) -> Self {
+        self.0.insert(field.to_string(), transform);
+        self
+    }
+
+    /// Returns the transform of `field`, if any.
+    pub fn get(&self, field: &str) -> Option<Transform> {
+        self.0.get(field).copied()
+    }
 }
 
+/// Which fields of a derived filter are applied, for turning filters off per tenant or
+/// behind a feature flag without recompiling. A field that isn't applied renders no
+/// conditions, even if the query has it.
+///
+/// Fields are named as declared in the struct. This only covers the conditions:
+/// `ORDER BY` is governed by `sortable_columns`.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let options = ApplyOptions {
+///     fields: FieldAccess::deny(&["salary"]),
+///     ..Default::default()
+/// };
+///
+/// // `salary[gte]=100` in the query is ignored
+/// filter.apply_with(&mut qb, &options);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum FieldAccess {
+    /// Every field is applied.
+    #[default]
+    All,
+    /// Only these fields are applied.
+    Allow(HashSet<String>),
+    /// Every field but these is applied.
+    Deny(HashSet<String>),
+}
+
+impl FieldAccess {
+    /// Applies only `fields`.
+    pub fn allow(fields: &[&str]) -> Self {
+        FieldAccess::Allow(fields.iter().map(|x| x.to_string()).collect())
+    }
+
+    /// Applies every field but `fields`.
+    pub fn deny(fields: &[&str]) -> Self {
+        FieldAccess::Deny(fields.iter().map(|x| x.to_string()).collect())
+    }
+
+    /// Returns `true` if `field` is applied.
+    pub fn allows(&self, field: &str) -> bool {
+        match self {
+            FieldAccess::All => true,
+            FieldAccess::Allow(fields) => fields.contains(field),
+            FieldAccess::Deny(fields) => !fields.contains(field),
+        }
+    }
+}
+
+/// Renders the column `id` refers to in a condition: [`sort_column`], wrapped in its
+/// transform from `options.transforms` if it has one.
+fn column<DB: Database>(id: &FilterId, options: &ApplyOptions) -> String {
+    let column = sort_column::<DB>(id, options);
+
+    match options.transforms.get(id.id()) {
+        Some(transform) => transform.wrap(&column),
+        None => column,
+    }
+}
+
+/// Renders the column `id` refers to: the one mapped in `options.columns`, or else the
+/// default column, qualified with its prefix (or the alias of that table in
+/// `options.table_aliases`) and quoted if `options.quote_identifiers` is set.
+fn sort_column<DB: Database>(id: &FilterId, options: &ApplyOptions) -> String {
+    if let Some(column) = options.columns.get(id.id()) {
+        return column.to_string();
+    }
+
+    let prefix = id
+        .prefix()
+        .map(|table| options.table_aliases.get(table).unwrap_or(table));
+
+    if !options.quote_identifiers {
+        return match prefix {
+            Some(prefix) => format!("{prefix}.{}", id.key()),
+            None => id.key().to_string(),
+        };
+    }
+
+    let dialect = Dialect::of::<DB>();
+    match prefix {
+        Some(prefix) => format!(
+            "{}.{}",
+            dialect.quote_identifier(prefix),
+            dialect.quote_identifier(id.key())
+        ),
+        None => dialect.quote_identifier(id.key()),
+    }
+}
+
+/// How the conditions of different fields are combined.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Conjunction {
+    /// Every field must match: `AND a AND b`.
+    #[default]
+    And,
+    /// Any field may match: `AND ((a) OR (b))`.
+    Or,
+}
+
+/// Applies `filters` in order, joining the conditions of each with `options.conjunction`.
+///
+/// With [`Conjunction::Or`] each filter's own conditions stay ANDed together, wrapped in
+/// parentheses, and the groups are ORed: `name=a&age[gte]=18&age[lt]=30` becomes
+/// ` AND ((name = ?) OR (age >= ? AND age < ?))`. Filters without conditions are skipped.
+pub fn apply_all<'a, DB: Database>(
+    qb: &mut QueryBuilder<'a, DB>,
+    filters: &[&dyn SqlxFilter<DB>],
+    options: &ApplyOptions,
+) {
+    match options.conjunction {
+        Conjunction::And => {
+            for filter in filters {
+                filter.apply_with(qb, options);
+            }
+        }
+        Conjunction::Or => {
+            let mut any = false;
+            for filter in filters {
+                any |= filter.push_group(qb, if any { " OR " } else { " AND (" }, options);
+            }
+
+            if any {
+                qb.push(")");
+            }
+        }
+    }
+}
+
+/// Like [`apply_all`], pushing `separator` and the conditions of every filter as a
+/// single parenthesized group, joined with `options.conjunction`: `(a AND b)`, or
+/// `((a) OR (b))`. Returns `false`, pushing nothing, if none has conditions.
+///
+/// This is the [`SqlxFilter::push_group`] of a derived filter, so it can be nested in an
+/// OR of other filters.
+pub fn push_group_all<'a, DB: Database>(
+    qb: &mut QueryBuilder<'a, DB>,
+    filters: &[&dyn SqlxFilter<DB>],
+    separator: &str,
+    options: &ApplyOptions,
+) -> bool {
+    let opening = format!("{separator}(");
+    let joiner = match options.conjunction {
+        Conjunction::And => " AND ",
+        Conjunction::Or => " OR ",
+    };
+
+    let mut any = false;
+    for filter in filters {
+        any |= filter.push_group(qb, if any { joiner } else { &opening }, options);
+    }
+
+    if any {
+        qb.push(")");
+    }
+    any
+}
+
+/// Returns `items` in the order their conditions are emitted: as given, or stably sorted
+/// by `key` with [`ApplyOptions::canonical_order`].
+fn emit_order<'f, I, K: Ord>(
+    items: &'f [I],
+    options: &ApplyOptions,
+    key: impl Fn(&I) -> K,
+) -> Vec<&'f I> {
+    let mut items = items.iter().collect::<Vec<_>>();
+    if options.canonical_order {
+        items.sort_by_cached_key(|x| key(x));
+    }
+
+    items
+}
+
+/// Pushes `separator` and `(c1 AND c2 ...)`, rendering each condition with `push`.
+/// Returns `false`, without pushing anything, if there are no `items`.
+fn push_grouped<'a, DB: Database, I>(
+    qb: &mut QueryBuilder<'a, DB>,
+    separator: &str,
+    items: &[I],
+    mut push: impl FnMut(&mut QueryBuilder<'a, DB>, &I),
+) -> bool {
+    if items.is_empty() {
+        return false;
+    }
+
+    qb.push(separator);
+    qb.push("(");
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            qb.push(" AND ");
+        }
+        push(qb, item);
+    }
+    qb.push(")");
+
+    true
+}
+
+/// Comparisons (`eq`, `ne`, `nseq` and `between`) bind the values as `T`, so a custom
+/// type is encoded by its own `Encode` impl. Patterns (the `LIKE` family, `regex` and
+/// `similar`) bind a `String` built from the `Display` of the value, as a pattern like
+/// `%al%` isn't a `T`. Both kinds can be mixed in one query on every database.
 impl<DB, T> SqlxFilter<DB> for StringFilters<T>
 where
     DB: Database,
     T: Clone + Display + Send + Sync + 'static + FromStr,
     String: Type<DB> + for<'q> Encode<'q, DB>,
     T: Type<DB> + for<'q> Encode<'q, DB>,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
 {
     fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
         if let Some(col_id) = &self.1 {
-            let col_name = col_id.key();
-            for filter in &self.0 {
+            let col_name = column::<DB>(col_id, options);
+            for filter in emit_order(&self.0, options, |x| x.operator().to_string()) {
                 qb.push(" AND ");
-                qb.push(col_name);
-                match filter {
-                    StringFilter::Eq(v) => {
-                        qb.push(" = ");
-                        qb.push_bind(v.clone());
-                    }
-                    StringFilter::Ne(v) => {
-                        qb.push(" <> ");
-                        qb.push_bind(v.clone());
-                    }
-                    StringFilter::Like(v) => {
-                        qb.push(" LIKE ");
-                        qb.push_bind(format!("{}", v));
-                    }
-                    StringFilter::NotLike(v) => {
-                        qb.push(" NOT LIKE ");
-                        qb.push_bind(format!("{}", v));
-                    }
-                    StringFilter::StartsWith(v) => {
-                        qb.push(" LIKE ");
-                        qb.push_bind(format!("{}%", v));
-                    }
-                    StringFilter::EndsWith(v) => {
-                        qb.push(" LIKE ");
-                        qb.push_bind(format!("%{}", v));
-                    }
-                    StringFilter::Contains(v) => {
-                        qb.push(" LIKE ");
-                        qb.push_bind(format!("%{}%", v));
-                    }
-                }
+                push_string_filter(qb, &col_name, filter, options);
             }
         }
     }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = &self.1 else {
+            return false;
+        };
+
+        let col_name = column::<DB>(col_id, options);
+        let filters = emit_order(&self.0, options, |x| x.operator().to_string());
+        push_grouped(qb, separator, &filters, |qb, filter| {
+            push_string_filter(qb, &col_name, filter, options)
+        })
+    }
 }
 
-impl<DB, T> SqlxFilter<DB> for NumberFilters<T>
+/// Each attribute is read from the field's JSON column with [`Dialect::json_text`].
+impl<DB, T> SqlxFilter<DB> for DynamicFilters<T>
 where
     DB: Database,
-    T: Clone + Send + Sync + 'static,
+    T: Clone + Display + Send + Sync + 'static + FromStr,
+    String: Type<DB> + for<'q> Encode<'q, DB>,
     T: Type<DB> + for<'q> Encode<'q, DB>,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
 {
     fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
         if let Some(col_id) = &self.1 {
-            let col_name = col_id.key();
-            for filter in &self.0 {
+            let col_name = column::<DB>(col_id, options);
+            for (name, filter) in emit_order(&self.0, options, dynamic_key) {
                 qb.push(" AND ");
-                qb.push(col_name);
-                match filter {
-                    NumberFilter::Eq(v) => {
-                        qb.push(" = ");
-                        qb.push_bind(v.clone());
-                    }
-                    NumberFilter::Ne(v) => {
-                        qb.push(" <> ");
-                        qb.push_bind(v.clone());
-                    }
-                    NumberFilter::Gt(v) => {
-                        qb.push(" > ");
-                        qb.push_bind(v.clone());
-                    }
-                    NumberFilter::Lt(v) => {
-                        qb.push(" < ");
-                        qb.push_bind(v.clone());
-                    }
-                    NumberFilter::Gte(v) => {
-                        qb.push(" >= ");
-                        qb.push_bind(v.clone());
-                    }
-                    NumberFilter::Lte(v) => {
-                        qb.push(" <= ");
-                        qb.push_bind(v.clone());
-                    }
-                }
+                let attribute = Dialect::of::<DB>().json_text(&col_name, name);
+                push_string_filter(qb, &attribute, filter, options);
             }
         }
     }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = &self.1 else {
+            return false;
+        };
+
+        let col_name = column::<DB>(col_id, options);
+        let filters = emit_order(&self.0, options, dynamic_key);
+        push_grouped(qb, separator, &filters, |qb, (name, filter)| {
+            let attribute = Dialect::of::<DB>().json_text(&col_name, name);
+            push_string_filter(qb, &attribute, filter, options)
+        })
+    }
 }
 
-impl<DB, T> SqlxFilter<DB> for EqualFilter<T>
+fn dynamic_key<T>((name, filter): &(String, StringFilter<T>)) -> (String, String) {
+    (name.clone(), filter.operator().to_string())
+}
+
+impl<DB, T> SqlxFilter<DB> for InFilters<T>
 where
-    DB: Database,
-    T: Clone + Send + Sync + 'static,
-    T: Type<DB> + for<'q> Encode<'q, DB>,
+    DB: BindList<T>,
 {
     fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
-        if let Some(val) = &self.0 {
-            if let Some(col_id) = &self.1 {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if let Some(col_id) = &self.1 {
+            let col_name = column::<DB>(col_id, options);
+            for filter in emit_order(&self.0, options, in_key) {
                 qb.push(" AND ");
-                qb.push(col_id.key());
-                qb.push(" = ");
-                qb.push_bind(val.clone());
+                DB::push_in_filter(qb, &col_name, filter);
             }
         }
     }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = &self.1 else {
+            return false;
+        };
+
+        let col_name = column::<DB>(col_id, options);
+        let filters = emit_order(&self.0, options, in_key);
+        push_grouped(qb, separator, &filters, |qb, filter| {
+            DB::push_in_filter(qb, &col_name, filter)
+        })
+    }
 }
 
-impl<DB> SqlxFilter<DB> for Limit
+// the number of values is part of the SQL where each gets a placeholder
+fn in_key<T>(filter: &InFilter<T>) -> (String, usize) {
+    (filter.operator().to_string(), filter.values().len())
+}
+
+/// How a database binds the values of an [`InFilter`].
+///
+/// Postgres binds the whole list as a single array parameter, `= ANY(?)` or
+/// `<> ALL(?)`, however many values it has. MySQL and SQLite have no array parameters,
+/// and get a placeholder per value, `IN (?, ?)` or `NOT IN (?, ?)`.
+pub trait BindList<T>: Database {
+    /// Pushes the condition of `filter` on `column`.
+    fn push_in_filter(qb: &mut QueryBuilder<'_, Self>, column: &str, filter: &InFilter<T>);
+}
+
+#[cfg(feature = "postgres")]
+impl<T> BindList<T> for sqlx::Postgres
 where
-    DB: Database,
-    i64: Type<DB> + for<'q> Encode<'q, DB>,
+    T: Clone + Send + 'static,
+    Vec<T>: Type<sqlx::Postgres> + for<'q> Encode<'q, sqlx::Postgres>,
 {
-    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
-        qb.push(" LIMIT ");
-        qb.push_bind(self.0 as i64);
+    fn push_in_filter(qb: &mut QueryBuilder<'_, Self>, column: &str, filter: &InFilter<T>) {
+        let operator = match filter {
+            InFilter::In(_) => " = ANY(",
+            InFilter::NotIn(_) => " <> ALL(",
+        };
+
+        qb.push(column)
+            .push(operator)
+            .push_bind(filter.values().to_vec())
+            .push(")");
     }
 }
 
-impl<DB> SqlxFilter<DB> for Skip
+#[cfg(feature = "mysql")]
+impl<T> BindList<T> for sqlx::MySql
 where
-    DB: Database,
-    i64: Type<DB> + for<'q> Encode<'q, DB>,
+    T: Clone + Send + 'static,
+    T: Type<sqlx::MySql> + for<'q> Encode<'q, sqlx::MySql>,
 {
-    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
-        qb.push(" OFFSET ");
-        qb.push_bind(self.0 as i64);
+    fn push_in_filter(qb: &mut QueryBuilder<'_, Self>, column: &str, filter: &InFilter<T>) {
+        push_in_list(qb, column, filter);
     }
 }
 
-impl<DB> SqlxFilter<DB> for OrderBy
+#[cfg(feature = "sqlite")]
+impl<T> BindList<T> for sqlx::Sqlite
 where
-    DB: Database,
+    T: Clone + Send + 'static,
+    T: Type<sqlx::Sqlite> + for<'q> Encode<'q, sqlx::Sqlite>,
 {
-    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
-        qb.push(" ORDER BY ");
-        match self {
-            OrderBy::Asc(id) => {
-                qb.push(id.key());
-                qb.push(" ASC");
-            }
-            OrderBy::Desc(id) => {
-                qb.push(id.key());
-                qb.push(" DESC");
-            }
-        }
+    fn push_in_filter(qb: &mut QueryBuilder<'_, Self>, column: &str, filter: &InFilter<T>) {
+        push_in_list(qb, column, filter);
     }
 }
 
-impl<DB, T> SqlxFilter<DB> for FromQueryFilter<T>
+/// Pushes `column IN (?, ?)` or `column NOT IN (?, ?)`, with a placeholder per value.
+/// `IN ()` isn't valid SQL, so an empty list is pushed as a constant: no row is in it,
+/// and every row is not.
+#[cfg(any(feature = "mysql", feature = "sqlite"))]
+fn push_in_list<DB, T>(qb: &mut QueryBuilder<'_, DB>, column: &str, filter: &InFilter<T>)
 where
     DB: Database,
-    T: SqlxFilter<DB> + Default + crate::common::WithFilterId + std::str::FromStr,
-    i64: Type<DB> + for<'q> Encode<'q, DB>,
+    T: Clone + Send + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
 {
-    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
-        self.inner.apply(qb);
+    let (operator, empty) = match filter {
+        InFilter::In(_) => (" IN (", "1=0"),
+        InFilter::NotIn(_) => (" NOT IN (", "1=1"),
+    };
 
-        if let Some(order_by) = &self.order_by {
-            order_by.apply(qb);
-        }
+    if filter.values().is_empty() {
+        qb.push(empty);
+        return;
+    }
 
-        if let Some(limit) = &self.limit {
-            limit.apply(qb);
-        }
+    qb.push(column).push(operator);
+    let mut values = qb.separated(", ");
+    for value in filter.values() {
+        values.push_bind(value.clone());
+    }
+    values.push_unseparated(")");
+}
 
-        if let Some(skip) = &self.skip {
-            skip.apply(qb);
+/// Escapes the `LIKE` wildcards `%` and `_`, and `escape` itself, so `value` matches
+/// literally.
+fn escape_like(value: &str, escape: char) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if c == '%' || c == '_' || c == escape {
+            escaped.push(escape);
         }
+        escaped.push(c);
     }
-}
\ No newline at end of file
+    escaped
+}
+
+/// Pushes `LIKE ? ESCAPE '<escape>'`, binding `pattern`.
+fn push_like<DB>(qb: &mut QueryBuilder<'_, DB>, pattern: String, escape: char)
+where
+    DB: Database,
+    String: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    qb.push(" LIKE ");
+    qb.push_bind(pattern);
+    push_escape(qb, escape);
+}
+
+/// Pushes the ` ESCAPE '<escape>'` clause of a `LIKE`.
+fn push_escape<DB: Database>(qb: &mut QueryBuilder<'_, DB>, escape: char) {
+    qb.push(" ESCAPE '");
+    match escape {
+        '\'' => qb.push("''"),
+        // MySQL string literals treat a backslash as an escape of their own.
+        '\\' if Dialect::of::<DB>() == Dialect::MySql => qb.push("\\\\"),
+        c => qb.push(c),
+    };
+    qb.push("'");
+}
+
+impl<DB> SqlxFilter<DB> for SearchFilter
+where
+    DB: Database,
+    String: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if !self.is_empty() {
+            qb.push(" AND ");
+            self.push_match(qb, options);
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        qb.push(separator);
+        self.push_match(qb, options);
+        true
+    }
+}
+
+impl SearchFilter {
+    /// Pushes `(a ILIKE ? OR b ILIKE ?)`, or `LOWER(a) LIKE LOWER(?)` for each column on
+    /// databases without `ILIKE`.
+    fn push_match<DB>(&self, qb: &mut QueryBuilder<'_, DB>, options: &ApplyOptions)
+    where
+        DB: Database,
+        String: Type<DB> + for<'q> Encode<'q, DB>,
+    {
+        let Some(query) = &self.query else {
+            return;
+        };
+
+        let pattern = format!("%{}%", escape_like(query, options.like_escape));
+        let prefix = self.id.as_ref().and_then(|x| x.prefix());
+
+        qb.push("(");
+        for (i, name) in self.columns().iter().enumerate() {
+            if i > 0 {
+                qb.push(" OR ");
+            }
+
+            let id = match prefix {
+                Some(prefix) => FilterId::WithPrefix(prefix.to_string(), name.clone()),
+                None => FilterId::Alone(name.clone()),
+            };
+            let col_name = column::<DB>(&id, options);
+
+            match Dialect::of::<DB>() {
+                Dialect::Postgres => {
+                    qb.push(col_name);
+                    qb.push(" ILIKE ");
+                    qb.push_bind(pattern.clone());
+                }
+                _ => {
+                    qb.push(format!("LOWER({col_name}) LIKE LOWER("));
+                    qb.push_bind(pattern.clone());
+                    qb.push(")");
+                }
+            }
+            push_escape(qb, options.like_escape);
+        }
+        qb.push(")");
+    }
+}
+
+fn push_string_filter<DB, T>(
+    qb: &mut QueryBuilder<'_, DB>,
+    col_name: &str,
+    filter: &StringFilter<T>,
+    options: &ApplyOptions,
+) where
+    DB: Database,
+    T: Clone + Display + Send + Sync + 'static + FromStr,
+    String: Type<DB> + for<'q> Encode<'q, DB>,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    let dialect = Dialect::of::<DB>();
+    let escape = options.like_escape;
+
+    // MySQL only has a function form for regex flags, which wraps the column.
+    if dialect == Dialect::MySql
+        && let StringFilter::Regex(v) | StringFilter::IRegex(v) = filter
+    {
+        qb.push("REGEXP_LIKE(");
+        qb.push(col_name);
+        qb.push(", ");
+        qb.push_bind(format!("{}", v));
+        qb.push(match filter {
+            StringFilter::IRegex(_) => ", 'i')",
+            _ => ", 'c')",
+        });
+        return;
+    }
+
+    // parenthesized, so the negation can't bind to only part of the inner condition
+    if let StringFilter::Not(inner) = filter {
+        qb.push("NOT (");
+        push_string_filter(qb, col_name, inner, options);
+        qb.push(")");
+        return;
+    }
+
+    // `unaccent` comes from a Postgres extension. Other databases get the same SQL, which
+    // they reject, rather than a match that silently keeps the accents.
+    if let StringFilter::ContainsUnaccent(v) = filter {
+        let v = escape_like(&v.to_string(), escape);
+        qb.push(format!("unaccent({col_name}) ILIKE unaccent("));
+        qb.push_bind(format!("%{}%", v));
+        qb.push(")");
+        push_escape(qb, escape);
+        return;
+    }
+
+    // MySQL's LENGTH counts bytes, CHAR_LENGTH counts characters like the others.
+    if let Some(len) = filter.length() {
+        qb.push(match dialect {
+            Dialect::MySql => "CHAR_LENGTH(",
+            _ => "LENGTH(",
+        });
+        qb.push(col_name);
+        qb.push(match filter {
+            StringFilter::LenGt(_) => ") > ",
+            StringFilter::LenLt(_) => ") < ",
+            _ => ") = ",
+        });
+        qb.push_bind(i64::try_from(len).unwrap_or(i64::MAX));
+        return;
+    }
+
+    // only Postgres has ILIKE, so elsewhere both sides are lowercased.
+    if dialect != Dialect::Postgres
+        && let StringFilter::ILike(v) = filter
+    {
+        qb.push("LOWER(");
+        qb.push(col_name);
+        qb.push(") LIKE LOWER(");
+        qb.push_bind(format!("{}", v));
+        qb.push(")");
+        return;
+    }
+
+    // `%` is the modulo elsewhere, so other databases get the `pg_trgm` function form,
+    // which they reject, rather than a condition that silently matches something else.
+    if dialect != Dialect::Postgres
+        && let StringFilter::Similar(v) = filter
+    {
+        qb.push("similarity(");
+        qb.push(col_name);
+        qb.push(", ");
+        qb.push_bind(format!("{}", v));
+        qb.push(") >= show_limit()");
+        return;
+    }
+
+    qb.push(col_name);
+    match filter {
+        StringFilter::Eq(v) => {
+            qb.push(" = ");
+            qb.push_bind(v.clone());
+        }
+        StringFilter::Ne(v) => {
+            qb.push(" <> ");
+            qb.push_bind(v.clone());
+        }
+        StringFilter::NullSafeEq(Some(v)) => {
+            qb.push(Dialect::of::<DB>().null_safe_eq());
+            qb.push_bind(v.clone());
+        }
+        StringFilter::NullSafeEq(None) => {
+            qb.push(" IS NULL");
+        }
+        StringFilter::Like(v) => {
+            qb.push(" LIKE ");
+            qb.push_bind(format!("{}", v));
+        }
+        StringFilter::NotLike(v) => {
+            qb.push(" NOT LIKE ");
+            qb.push_bind(format!("{}", v));
+        }
+        StringFilter::ILike(v) => {
+            qb.push(" ILIKE ");
+            qb.push_bind(format!("{}", v));
+        }
+        StringFilter::StartsWith(v) => {
+            let v = escape_like(&v.to_string(), escape);
+            push_like(qb, format!("{}%", v), escape);
+        }
+        StringFilter::EndsWith(v) => {
+            let v = escape_like(&v.to_string(), escape);
+            push_like(qb, format!("%{}", v), escape);
+        }
+        StringFilter::Contains(v) => {
+            let v = escape_like(&v.to_string(), escape);
+            push_like(qb, format!("%{}%", v), escape);
+        }
+        StringFilter::Regex(v) => {
+            qb.push(match dialect {
+                Dialect::Postgres => " ~ ",
+                _ => " REGEXP ",
+            });
+            qb.push_bind(format!("{}", v));
+        }
+        StringFilter::IRegex(v) => match dialect {
+            Dialect::Postgres => {
+                qb.push(" ~* ");
+                qb.push_bind(format!("{}", v));
+            }
+            // sqlx's sqlite REGEXP uses the `regex` crate, which takes inline flags.
+            _ => {
+                qb.push(" REGEXP ");
+                qb.push_bind(format!("(?i){}", v));
+            }
+        },
+        StringFilter::Similar(v) => {
+            qb.push(" % ");
+            qb.push_bind(format!("{}", v));
+        }
+        StringFilter::Between(low, high) => {
+            qb.push(" BETWEEN ");
+            qb.push_bind(low.clone());
+            qb.push(" AND ");
+            qb.push_bind(high.clone());
+        }
+        // pushed above, before the column
+        StringFilter::ContainsUnaccent(_)
+        | StringFilter::Not(_)
+        | StringFilter::LenGt(_)
+        | StringFilter::LenLt(_)
+        | StringFilter::LenEq(_) => {}
+    }
+}
+
+impl<T> StringFilters<T>
+where
+    T: Display + FromStr,
+{
+    /// Appends a single `ORDER BY`, led by `similarity(column, ?) DESC` for the first
+    /// `similar` filter so the closest trigram matches come first, and followed by the
+    /// keys of `then`, e.g. the [`FromQueryFilter::sort`] of the query. Only `then` is
+    /// applied when there is no such filter or `DB` is not Postgres.
+    ///
+    /// Use it in place of the sort of a `FromQueryFilter`, e.g. after applying its
+    /// `inner` filter, since a second `ORDER BY` is invalid SQL.
+    pub fn apply_similarity_order<DB>(&self, qb: &mut QueryBuilder<'_, DB>, then: &Sort)
+    where
+        DB: Database,
+        String: Type<DB> + for<'q> Encode<'q, DB>,
+    {
+        self.apply_similarity_order_with(qb, then, &ApplyOptions::default());
+    }
+
+    /// Like [`StringFilters::apply_similarity_order`], rendering the column as the
+    /// conditions do with `options`, e.g. mapped in `options.columns` or quoted, and the
+    /// keys of `then` with them too.
+    pub fn apply_similarity_order_with<DB>(
+        &self,
+        qb: &mut QueryBuilder<'_, DB>,
+        then: &Sort,
+        options: &ApplyOptions,
+    ) where
+        DB: Database,
+        String: Type<DB> + for<'q> Encode<'q, DB>,
+    {
+        let similar = self.0.iter().find_map(|x| match x {
+            StringFilter::Similar(v) => Some(v),
+            _ => None,
+        });
+
+        if Dialect::of::<DB>() == Dialect::Postgres
+            && let Some(v) = similar
+            && let Some(col_id) = &self.1
+        {
+            qb.push(" ORDER BY similarity(");
+            qb.push(column::<DB>(col_id, options));
+            qb.push(", ");
+            qb.push_bind(format!("{}", v));
+            qb.push(") DESC");
+
+            for order_by in &then.0 {
+                qb.push(", ");
+                push_order_by(order_by, qb, options);
+            }
+        } else {
+            then.apply_with(qb, options);
+        }
+    }
+}
+
+impl<DB, T> SqlxFilter<DB> for NumberFilters<T>
+where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if let Some(col_id) = &self.1 {
+            let col_name = column::<DB>(col_id, options);
+            for filter in emit_order(&self.0, options, |x| x.operator().to_string()) {
+                qb.push(" AND ");
+                push_number_filter(qb, &col_name, filter);
+            }
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = &self.1 else {
+            return false;
+        };
+
+        let col_name = column::<DB>(col_id, options);
+        let filters = emit_order(&self.0, options, |x| x.operator().to_string());
+        push_grouped(qb, separator, &filters, |qb, filter| {
+            push_number_filter(qb, &col_name, filter)
+        })
+    }
+}
+
+fn push_number_filter<DB, T>(
+    qb: &mut QueryBuilder<'_, DB>,
+    col_name: &str,
+    filter: &NumberFilter<T>,
+) where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    qb.push(col_name);
+    match filter {
+        NumberFilter::Eq(v) => {
+            qb.push(" = ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::Ne(v) => {
+            qb.push(" <> ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::NullSafeEq(Some(v)) => {
+            qb.push(Dialect::of::<DB>().null_safe_eq());
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::NullSafeEq(None) => {
+            qb.push(" IS NULL");
+        }
+        NumberFilter::Gt(v) => {
+            qb.push(" > ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::Lt(v) => {
+            qb.push(" < ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::Gte(v) => {
+            qb.push(" >= ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::Lte(v) => {
+            qb.push(" <= ");
+            qb.push_bind(v.clone());
+        }
+        NumberFilter::Between(low, high) => {
+            qb.push(" BETWEEN ");
+            qb.push_bind(low.clone());
+            qb.push(" AND ");
+            qb.push_bind(high.clone());
+        }
+        NumberFilter::Range(low, high) => {
+            qb.push(if low.is_inclusive() { " >= " } else { " > " });
+            qb.push_bind(low.value().clone());
+            qb.push(" AND ");
+            qb.push(col_name);
+            qb.push(if high.is_inclusive() { " <= " } else { " < " });
+            qb.push_bind(high.value().clone());
+        }
+    }
+}
+
+impl<DB, T> SqlxFilter<DB> for EqualFilter<T>
+where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if !self.0.is_empty()
+            && let Some(col_id) = &self.1
+        {
+            qb.push(" AND ");
+            push_equal_filter(qb, &column::<DB>(col_id, options), self);
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = self.1.as_ref().filter(|_| !self.0.is_empty()) else {
+            return false;
+        };
+
+        push_grouped(qb, separator, std::slice::from_ref(self), |qb, filter| {
+            push_equal_filter(qb, &column::<DB>(col_id, options), filter)
+        })
+    }
+}
+
+/// Pushes `column = ?`, or `column IN (?, ?)` for a list of values.
+fn push_equal_filter<DB, T>(qb: &mut QueryBuilder<'_, DB>, col_name: &str, filter: &EqualFilter<T>)
+where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    let is_list = filter.0.len() > 1;
+
+    qb.push(col_name);
+    qb.push(if is_list { " IN (" } else { " = " });
+
+    let mut values = qb.separated(", ");
+    for value in filter.values() {
+        values.push_bind(value.clone());
+    }
+
+    if is_list {
+        qb.push(")");
+    }
+}
+
+impl<DB, T> SqlxFilter<DB> for NullableFilter<T>
+where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        if let Some(val) = &self.0
+            && let Some(col_id) = &self.1
+        {
+            qb.push(" AND ");
+            push_nullable(qb, &column::<DB>(col_id, options), val);
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let (Some(val), Some(col_id)) = (&self.0, &self.1) else {
+            return false;
+        };
+
+        push_grouped(qb, separator, std::slice::from_ref(val), |qb, val| {
+            push_nullable(qb, &column::<DB>(col_id, options), val)
+        })
+    }
+}
+
+fn push_nullable<DB, T>(qb: &mut QueryBuilder<'_, DB>, col_name: &str, val: &Nullable<T>)
+where
+    DB: Database,
+    T: Clone + Send + Sync + 'static,
+    T: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    qb.push(col_name);
+    match val {
+        Nullable::Null => {
+            qb.push(" IS NULL");
+        }
+        Nullable::NotNull => {
+            qb.push(" IS NOT NULL");
+        }
+        Nullable::Value(v) => {
+            qb.push(" = ");
+            qb.push_bind(v.clone());
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl<T> SqlxFilter<sqlx::Postgres> for ArrayFilters<T>
+where
+    T: Clone + Send + 'static,
+    Vec<T>: Type<sqlx::Postgres> + for<'q> Encode<'q, sqlx::Postgres>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, sqlx::Postgres>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, sqlx::Postgres>, options: &ApplyOptions) {
+        if let Some(col_id) = &self.1 {
+            let col_name = column::<sqlx::Postgres>(col_id, options);
+            for filter in emit_order(&self.0, options, |x| x.operator().to_string()) {
+                qb.push(" AND ");
+                push_array_filter(qb, &col_name, filter);
+            }
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, sqlx::Postgres>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let Some(col_id) = &self.1 else {
+            return false;
+        };
+
+        let col_name = column::<sqlx::Postgres>(col_id, options);
+        let filters = emit_order(&self.0, options, |x| x.operator().to_string());
+        push_grouped(qb, separator, &filters, |qb, filter| {
+            push_array_filter(qb, &col_name, filter)
+        })
+    }
+}
+
+/// Pushes `column @> ?`, binding the values of `filter` as one array.
+#[cfg(feature = "postgres")]
+fn push_array_filter<T>(
+    qb: &mut QueryBuilder<'_, sqlx::Postgres>,
+    column: &str,
+    filter: &ArrayFilter<T>,
+) where
+    T: Clone + Send + 'static,
+    Vec<T>: Type<sqlx::Postgres> + for<'q> Encode<'q, sqlx::Postgres>,
+{
+    match filter {
+        ArrayFilter::Contains(values) => {
+            qb.push(column).push(" @> ").push_bind(values.clone());
+        }
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl SqlxFilter<sqlx::Postgres> for FullTextFilter {
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, sqlx::Postgres>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, sqlx::Postgres>, options: &ApplyOptions) {
+        if self.query.is_some() && self.column().is_some() {
+            qb.push(" AND ");
+            self.push_match(qb, options);
+        }
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, sqlx::Postgres>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        if self.query.is_none() || self.column().is_none() {
+            return false;
+        }
+
+        qb.push(separator);
+        qb.push("(");
+        self.push_match(qb, options);
+        qb.push(")");
+        true
+    }
+}
+
+#[cfg(feature = "postgres")]
+impl FullTextFilter {
+    fn push_match(&self, qb: &mut QueryBuilder<'_, sqlx::Postgres>, options: &ApplyOptions) {
+        if let Some(query) = &self.query
+            && let Some(column) = self.column()
+        {
+            qb.push("to_tsvector('");
+            qb.push(self.language());
+            qb.push("', ");
+            if let Some(column) = options.columns.get(column) {
+                qb.push(column);
+            } else if options.quote_identifiers {
+                qb.push(Dialect::Postgres.quote_identifier(column));
+            } else {
+                qb.push(column);
+            }
+            qb.push(") @@ plainto_tsquery('");
+            qb.push(self.language());
+            qb.push("', ");
+            qb.push_bind(query.clone());
+            qb.push(")");
+        }
+    }
+}
+
+impl<DB> SqlxFilter<DB> for Limit
+where
+    DB: Database,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        qb.push(" LIMIT ");
+        qb.push_bind(self.0 as i64);
+    }
+
+    fn push_group<'a>(&self, _: &mut QueryBuilder<'a, DB>, _: &str, _: &ApplyOptions) -> bool {
+        // a limit is no `WHERE` condition
+        false
+    }
+}
+
+// `LIMIT ? OFFSET ?` is valid on every supported database, MySQL included, so the
+// MySQL-only `LIMIT ?, ?` form is never needed. A bare `OFFSET ?` is not valid on MySQL
+// or SQLite; `FromQueryFilter` adds the missing `LIMIT` when applying a lone `skip`.
+impl<DB> SqlxFilter<DB> for Skip
+where
+    DB: Database,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        qb.push(" OFFSET ");
+        qb.push_bind(self.0 as i64);
+    }
+
+    fn push_group<'a>(&self, _: &mut QueryBuilder<'a, DB>, _: &str, _: &ApplyOptions) -> bool {
+        // an offset is no `WHERE` condition
+        false
+    }
+}
+
+fn push_order_by<DB: Database>(
+    order_by: &OrderBy,
+    qb: &mut QueryBuilder<'_, DB>,
+    options: &ApplyOptions,
+) {
+    match order_by {
+        OrderBy::Asc(id) => {
+            qb.push(sort_column::<DB>(id, options));
+            qb.push(" ASC");
+        }
+        OrderBy::Desc(id) => {
+            qb.push(sort_column::<DB>(id, options));
+            qb.push(" DESC");
+        }
+        OrderBy::Random => {
+            qb.push(match Dialect::of::<DB>() {
+                Dialect::MySql => "RAND()",
+                _ => "RANDOM()",
+            });
+        }
+    }
+}
+
+impl<DB> SqlxFilter<DB> for OrderBy
+where
+    DB: Database,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        qb.push(" ORDER BY ");
+        push_order_by(self, qb, options);
+    }
+
+    fn push_group<'a>(&self, _: &mut QueryBuilder<'a, DB>, _: &str, _: &ApplyOptions) -> bool {
+        // a sort is no `WHERE` condition
+        false
+    }
+}
+
+/// Renders the column mapped by `S`, never a name taken from the query.
+impl<DB, S> SqlxFilter<DB> for TypedOrderBy<S>
+where
+    DB: Database,
+    S: Sortable,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        OrderBy::from(self).apply_with(qb, options);
+    }
+
+    fn push_group<'a>(&self, _: &mut QueryBuilder<'a, DB>, _: &str, _: &ApplyOptions) -> bool {
+        // a sort is no `WHERE` condition
+        false
+    }
+}
+
+impl<DB> SqlxFilter<DB> for Sort
+where
+    DB: Database,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        for (i, order_by) in self.0.iter().enumerate() {
+            qb.push(if i == 0 { " ORDER BY " } else { ", " });
+            push_order_by(order_by, qb, options);
+        }
+    }
+
+    fn push_group<'a>(&self, _: &mut QueryBuilder<'a, DB>, _: &str, _: &ApplyOptions) -> bool {
+        // a sort is no `WHERE` condition
+        false
+    }
+}
+
+impl<DB, T> SqlxFilter<DB> for FromQueryFilter<T>
+where
+    DB: Database,
+    T: SqlxFilter<DB> + Default + crate::common::WithFilterId + std::str::FromStr,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        self.inner.apply_with(qb, options);
+        push_pagination(self, qb, options);
+    }
+
+    /// Groups the conditions of the inner filter only, as a sort or limit can't be part
+    /// of a `WHERE` clause.
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        self.inner.push_group(qb, separator, options)
+    }
+}
+
+/// Pushes the sort, limit and skip of `filter`, which follow its `WHERE` conditions.
+fn push_pagination<DB, T>(
+    filter: &FromQueryFilter<T>,
+    qb: &mut QueryBuilder<'_, DB>,
+    options: &ApplyOptions,
+) where
+    DB: Database,
+    T: Default + crate::common::WithFilterId + std::str::FromStr,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    filter.sort().apply_with(qb, options);
+
+    match (&filter.limit, &filter.skip) {
+        (Some(limit), _) => limit.apply(qb),
+        (None, Some(_)) => push_unbounded_limit(qb),
+        (None, None) => {}
+    }
+
+    if let Some(skip) = &filter.skip {
+        skip.apply(qb);
+    }
+}
+
+/// Pushes a `LIMIT` that keeps every row, for a `skip` without a `limit`.
+///
+/// MySQL and SQLite only accept `OFFSET` after a `LIMIT`, so they get the largest limit
+/// they support (the sentinel both document for "no limit"). Postgres and other
+/// databases accept a bare `OFFSET`, and get nothing.
+fn push_unbounded_limit<DB: Database>(qb: &mut QueryBuilder<'_, DB>) {
+    match Dialect::of::<DB>() {
+        Dialect::MySql => {
+            qb.push(" LIMIT 18446744073709551615");
+        }
+        Dialect::Sqlite => {
+            qb.push(" LIMIT -1");
+        }
+        Dialect::Postgres | Dialect::Other => {}
+    }
+}
+
+impl<T> FromQueryFilter<T>
+where
+    T: Default + crate::common::WithFilterId + std::str::FromStr,
+{
+    /// Adds a server-side condition, e.g. a soft-delete check or tenant scoping, that is
+    /// always ANDed at the top level, even when the parsed filters are ORed together.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let tenant_id = 7i64;
+    /// filter
+    ///     .and_raw(|qb| {
+    ///         qb.push("tenant_id = ").push_bind(tenant_id);
+    ///     })
+    ///     .apply(&mut qb);
+    /// // ... WHERE 1=1 AND (tenant_id = $1) AND name LIKE $2 ... ORDER BY ...
+    /// ```
+    pub fn and_raw<DB, C>(&self, condition: C) -> AndRaw<'_, Self, C, DB>
+    where
+        DB: Database,
+        C: Fn(&mut QueryBuilder<'_, DB>),
+    {
+        AndRaw::new(self, condition)
+    }
+
+    /// Inverts the parsed filters, for an "exclude" toggle in a search UI: their
+    /// conditions are wrapped in `NOT (...)`, while the sort, limit and skip apply as
+    /// usual. With no conditions, nothing is negated and every row matches.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// let filter = FromQueryFilter::<UserFilter>::from_str("name=ana&age[gte]=18&limit=10")?;
+    /// filter.negated().apply(&mut qb);
+    /// // ... WHERE 1=1 AND NOT (1=1 AND name = $1 AND age >= $2) LIMIT $3
+    /// ```
+    pub fn negated(&self) -> Negated<'_, T> {
+        Negated { filter: self }
+    }
+
+    /// Captures the `WHERE` conditions of the parsed filters, without the sort, limit
+    /// or skip, so they can be rendered into several queries. See [`Conditions`].
+    pub fn conditions<DB>(&self) -> Conditions<DB>
+    where
+        DB: Database,
+        T: SqlxFilter<DB> + Clone + Send + Sync + 'static,
+    {
+        Conditions::new(self.inner.clone())
+    }
+}
+
+impl<T> FromQueryFilter<T>
+where
+    T: Default + crate::common::WithFilterId + std::str::FromStr,
+{
+    /// Builds the two queries of a paginated endpoint from one parsed request: `select`
+    /// with every filter, the sort, limit and skip, and `count` with the same `WHERE`
+    /// conditions only, for the total. Both bases should end in a `WHERE` clause, e.g.
+    /// `... WHERE 1=1`.
+    ///
+    /// # Example
+    ///
+    /// ```rust,ignore
+    /// async fn list(filter: FromQueryFilter<UserFilter>) -> ... {
+    ///     let PageQueries { mut data, mut count } = filter.page_queries::<Postgres>(
+    ///         "SELECT * FROM users WHERE 1=1",
+    ///         "SELECT COUNT(*) FROM users WHERE 1=1",
+    ///     );
+    ///
+    ///     let users = data.build_query_as::<User>().fetch_all(&pool).await?;
+    ///     let total: i64 = count.build_query_scalar().fetch_one(&pool).await?;
+    /// }
+    /// ```
+    pub fn page_queries<'args, DB>(
+        &self,
+        select: impl Into<String>,
+        count: impl Into<String>,
+    ) -> PageQueries<'args, DB>
+    where
+        DB: Database,
+        T: SqlxFilter<DB>,
+        i64: Type<DB> + for<'q> Encode<'q, DB>,
+    {
+        self.page_queries_with(select, count, &ApplyOptions::default())
+    }
+
+    /// Like [`FromQueryFilter::page_queries`], rendering both queries according to
+    /// `options`.
+    pub fn page_queries_with<'args, DB>(
+        &self,
+        select: impl Into<String>,
+        count: impl Into<String>,
+        options: &ApplyOptions,
+    ) -> PageQueries<'args, DB>
+    where
+        DB: Database,
+        T: SqlxFilter<DB>,
+        i64: Type<DB> + for<'q> Encode<'q, DB>,
+    {
+        let mut data = QueryBuilder::new(select);
+        self.apply_with(&mut data, options);
+
+        let mut count = QueryBuilder::new(count);
+        self.inner.apply_with(&mut count, options);
+
+        PageQueries { data, count }
+    }
+}
+
+/// The queries of a paginated endpoint, built by [`FromQueryFilter::page_queries`].
+pub struct PageQueries<'args, DB: Database> {
+    /// The page of rows: every filter, the sort, limit and skip.
+    pub data: QueryBuilder<'args, DB>,
+    /// The total: the same `WHERE` conditions, without sort, limit or skip.
+    pub count: QueryBuilder<'args, DB>,
+}
+
+/// A filter with an extra hand-written condition ANDed in front of its own.
+///
+/// The condition is wrapped in parentheses and pushed before the filter, so it stays
+/// in the `WHERE` clause ahead of any `ORDER BY` or `LIMIT` the filter appends.
+pub struct AndRaw<'f, F, C, DB> {
+    filter: &'f F,
+    condition: C,
+    _db: std::marker::PhantomData<DB>,
+}
+
+impl<'f, F, C, DB> AndRaw<'f, F, C, DB>
+where
+    DB: Database,
+    C: Fn(&mut QueryBuilder<'_, DB>),
+{
+    /// Wraps `filter`, adding the condition pushed by `condition`.
+    pub fn new(filter: &'f F, condition: C) -> Self {
+        Self {
+            filter,
+            condition,
+            _db: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<F, C, DB> SqlxFilter<DB> for AndRaw<'_, F, C, DB>
+where
+    DB: Database,
+    F: SqlxFilter<DB>,
+    C: Fn(&mut QueryBuilder<'_, DB>),
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        qb.push(" AND (");
+        (self.condition)(qb);
+        qb.push(")");
+
+        self.filter.apply_with(qb, options);
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        qb.push(separator);
+        qb.push("((");
+        (self.condition)(qb);
+        qb.push(")");
+        self.filter.push_group(qb, " AND ", options);
+        qb.push(")");
+        true
+    }
+}
+
+/// A [`FromQueryFilter`] with its conditions inverted, built by
+/// [`FromQueryFilter::negated`].
+pub struct Negated<'f, T: Default + crate::common::WithFilterId + std::str::FromStr> {
+    filter: &'f FromQueryFilter<T>,
+}
+
+impl<DB, T> SqlxFilter<DB> for Negated<'_, T>
+where
+    DB: Database,
+    T: SqlxFilter<DB> + Default + crate::common::WithFilterId + std::str::FromStr,
+    i64: Type<DB> + for<'q> Encode<'q, DB>,
+{
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.apply_with(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        let inner = &self.filter.inner;
+
+        // rendered on its own first, so an empty filter doesn't become `NOT (1=1)`,
+        // which would match no rows
+        let mut probe = QueryBuilder::<DB>::new("");
+        inner.apply_with(&mut probe, options);
+
+        if !probe.sql().is_empty() {
+            qb.push(" AND NOT (1=1");
+            inner.apply_with(qb, options);
+            qb.push(")");
+        }
+
+        push_pagination(self.filter, qb, options);
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let inner = &self.filter.inner;
+
+        let mut probe = QueryBuilder::<DB>::new("");
+        inner.apply_with(&mut probe, options);
+        if probe.sql().is_empty() {
+            return false;
+        }
+
+        qb.push(separator);
+        qb.push("(NOT (1=1");
+        inner.apply_with(qb, options);
+        qb.push("))");
+        true
+    }
+}
+
+/// The `WHERE` conditions of a filter, detached from any `QueryBuilder`.
+///
+/// Parse a request once, build its conditions, and render them into as many builders as
+/// needed, e.g. a page query and the `COUNT(*)` query for its total. Values are bound
+/// again on every render. A `Conditions` is itself a [`SqlxFilter`], so it can be
+/// combined with [`apply_all`] or [`FromQueryFilter::and_raw`].
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let conditions = filter.conditions::<Postgres>();
+///
+/// let mut count = QueryBuilder::<Postgres>::new("SELECT COUNT(*) FROM users WHERE 1=1");
+/// conditions.render(&mut count, &ApplyOptions::default());
+///
+/// let mut page = QueryBuilder::<Postgres>::new("SELECT * FROM users WHERE 1=1");
+/// conditions.render(&mut page, &ApplyOptions::default());
+/// filter.sort().apply(&mut page);
+/// ```
+#[derive(Clone)]
+pub enum Conditions<DB: Database> {
+    /// The conditions of a single filter.
+    Filter(Arc<dyn SqlxFilter<DB> + Send + Sync>),
+    /// Every one of these must hold.
+    All(Vec<Conditions<DB>>),
+    /// At least one of these must hold. Members without conditions are left out, and if
+    /// none has any, nothing is rendered.
+    Any(Vec<Conditions<DB>>),
+}
+
+impl<DB: Database> Conditions<DB> {
+    /// Captures the conditions of `filter`.
+    pub fn new<F>(filter: F) -> Self
+    where
+        F: SqlxFilter<DB> + Send + Sync + 'static,
+    {
+        Conditions::Filter(Arc::new(filter))
+    }
+
+    /// Appends the conditions to `qb`, as ` AND a AND b`.
+    pub fn render<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        match self {
+            Conditions::Filter(filter) => filter.apply_with(qb, options),
+            Conditions::All(all) => {
+                for conditions in all {
+                    conditions.render(qb, options);
+                }
+            }
+            Conditions::Any(_) => {
+                self.push_group(qb, " AND ", options);
+            }
+        }
+    }
+}
+
+impl<DB: Database> SqlxFilter<DB> for Conditions<DB> {
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, DB>) {
+        self.render(qb, &ApplyOptions::default());
+    }
+
+    fn apply_with<'a>(&self, qb: &mut QueryBuilder<'a, DB>, options: &ApplyOptions) {
+        self.render(qb, options);
+    }
+
+    fn push_group<'a>(
+        &self,
+        qb: &mut QueryBuilder<'a, DB>,
+        separator: &str,
+        options: &ApplyOptions,
+    ) -> bool {
+        let (members, joiner) = match self {
+            Conditions::Filter(filter) => return filter.push_group(qb, separator, options),
+            Conditions::All(all) => (all, " AND "),
+            Conditions::Any(any) => (any, " OR "),
+        };
+
+        let opening = format!("{separator}(");
+        let mut any = false;
+        for conditions in members {
+            any |= conditions.push_group(qb, if any { joiner } else { &opening }, options);
+        }
+
+        if any {
+            qb.push(")");
+        }
+        any
+    }
+}
+
+/// Applies `filter` to a SQLite `QueryBuilder` started with `base`, and returns the SQL
+/// and the `Debug` form of every bound value, e.g. `Text("Ali%")` or `Integer(18)`.
+///
+/// Meant for tests, to assert on the exact binds instead of searching the SQL with
+/// `contains`. SQLite keeps its bound values private, so the binds are the ones the
+/// filter pushes when applied to [`sqlx::Any`], listed like [`render_sql`] lists them;
+/// the filter must apply to both.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::number_filter::NumberFilters;
+/// use filtrum::sqlx::debug_sql;
+///
+/// let age = NumberFilters::<i32>::from_str("age", "age[gte]=18").unwrap();
+/// let (sql, binds) = debug_sql("SELECT * FROM users WHERE 1=1", &age);
+///
+/// assert_eq!(sql, "SELECT * FROM users WHERE 1=1 AND age >= ?");
+/// assert_eq!(binds, vec!["Integer(18)"]);
+/// ```
+#[cfg(feature = "sqlite")]
+pub fn debug_sql<F>(base: &str, filter: &F) -> (String, Vec<String>)
+where
+    F: SqlxFilter<sqlx::Sqlite> + SqlxFilter<sqlx::Any> + ?Sized,
+{
+    debug_sql_with(base, filter, &ApplyOptions::default())
+}
+
+/// Like [`debug_sql`], applying `filter` with `options`.
+#[cfg(feature = "sqlite")]
+pub fn debug_sql_with<F>(base: &str, filter: &F, options: &ApplyOptions) -> (String, Vec<String>)
+where
+    F: SqlxFilter<sqlx::Sqlite> + SqlxFilter<sqlx::Any> + ?Sized,
+{
+    let mut qb = QueryBuilder::<sqlx::Sqlite>::new(base);
+    SqlxFilter::<sqlx::Sqlite>::apply_with(filter, &mut qb, options);
+
+    let (_, binds) = render_sql_with(base, filter, options);
+
+    (qb.into_sql(), binds)
+}
+
+/// Like [`debug_sql`], rendering with SQLx's driver-agnostic [`sqlx::Any`] database
+/// instead of SQLite, so it needs no driver feature. Bound values are listed as
+/// `Text("Ali%")`, `Integer(18)` or `BigInt(10)`.
+///
+/// `Any` uses `?` placeholders and none of the database-specific SQL, like `ILIKE` or
+/// `<=>`, and only binds booleans, integers, floats, strings and bytes. Filters on
+/// other types, e.g. arrays or decimals, don't apply to it.
+///
+/// # Example
+///
+/// ```rust
+/// use filtrum::number_filter::NumberFilters;
+/// use filtrum::sqlx::render_sql;
+///
+/// let age = NumberFilters::<i32>::from_str("age", "age[range]=[18,65)").unwrap();
+/// let (sql, binds) = render_sql("SELECT * FROM users WHERE 1=1", &age);
+///
+/// assert_eq!(sql, "SELECT * FROM users WHERE 1=1 AND age >= ? AND age < ?");
+/// assert_eq!(binds, vec!["Integer(18)", "Integer(65)"]);
+/// ```
+pub fn render_sql<F>(base: &str, filter: &F) -> (String, Vec<String>)
+where
+    F: SqlxFilter<sqlx::Any> + ?Sized,
+{
+    render_sql_with(base, filter, &ApplyOptions::default())
+}
+
+/// Like [`render_sql`], applying `filter` with `options`.
+///
+/// # Panics
+///
+/// If a bound value fails to encode, as it is meant for tests.
+pub fn render_sql_with<F>(base: &str, filter: &F, options: &ApplyOptions) -> (String, Vec<String>)
+where
+    F: SqlxFilter<sqlx::Any> + ?Sized,
+{
+    use sqlx::Execute;
+
+    let mut qb = QueryBuilder::<sqlx::Any>::new(base);
+    filter.apply_with(&mut qb, options);
+
+    let mut query = qb.build();
+    let sql = query.sql().to_string();
+
+    // an empty list would pass for a filter that binds nothing
+    let binds = match query.take_arguments() {
+        Ok(arguments) => arguments.map_or_else(Vec::new, |arguments| {
+            arguments
+                .values
+                .0
+                .iter()
+                .map(|x| format!("{x:?}"))
+                .collect()
+        }),
+        Err(err) => panic!("a bound value failed to encode: {err}"),
+    };
+
+    (sql, binds)
+}
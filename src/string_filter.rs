@@ -1,11 +1,17 @@
 use std::{fmt::Display, str::FromStr};
 
-use serde::{de, Deserialize};
+use serde::{de, Deserialize, Serialize};
 
 use crate::{
-    common::{from_str, FromStrFilter},
-    errors::FilterParseError,
+    common::{
+        from_str_with, parse_pair, parse_pair_with, FromParam, FromStrFilter, QueryParams,
+    },
+    errors::{BoxError, FilterParseError},
+    filter::{visit_filter, Filter, Merge, VisitConditions},
     filter_id::FilterId,
+    filter_value::{FilterValue, ToFilterValue},
+    op::Op,
+    options::ParseOptions,
 };
 
 /// Represents various string comparison operations.
@@ -17,38 +23,114 @@ pub enum StringFilter<T = String> {
     Eq(T),
     /// Not equal (`<>` or `!=`). Query param: `field[ne]=value`.
     Ne(T),
+    /// NULL-safe equal (`<=>` on MySQL, `IS NOT DISTINCT FROM` elsewhere), which also
+    /// matches when both sides are `NULL`. Query param: `field[nseq]=value`, or
+    /// `field[nseq]=null` for `None`, which renders `IS NULL`. A value of `null` always
+    /// means `NULL` here, never the text `"null"`.
+    NullSafeEq(Option<T>),
     /// SQL LIKE match. Query param: `field[like]=value` or `field[l]=value`.
     Like(T),
     /// SQL NOT LIKE match. Query param: `field[not_like]=value` or `field[nl]=value`.
     NotLike(T),
+    /// Case-insensitive LIKE match (`ILIKE` on Postgres, `LOWER(col) LIKE LOWER(?)` elsewhere). Query param: `field[ilike]=value`.
+    ILike(T),
     /// Starts with match (`LIKE 'value%'`). Query param: `field[starts_with]=value` or `field[sw]=value`.
     StartsWith(T),
     /// Ends with match (`LIKE '%value'`). Query param: `field[ends_with]=value` or `field[ew]=value`.
     EndsWith(T),
     /// Contains match (`LIKE '%value%'`). Query param: `field[contains]=value` or `field[c]=value`.
     Contains(T),
+    /// POSIX regex match (`~` on Postgres, `REGEXP` elsewhere). Query param: `field[regex]=pattern`.
+    Regex(T),
+    /// Case-insensitive regex match (`~*` on Postgres). Query param: `field[iregex]=pattern`.
+    IRegex(T),
+    /// Trigram similarity (`%`, Postgres with `pg_trgm`). Query param: `field[similar]=value` or `field[sim]=value`,
+    /// only parsed with the `postgres` feature.
+    Similar(T),
+    /// Accent- and case-insensitive contains (`unaccent(col) ILIKE unaccent(?)`, Postgres
+    /// with `unaccent`). Query param: `field[contains_unaccent]=value`, only parsed with the
+    /// `postgres` feature.
+    ContainsUnaccent(T),
+    /// Inclusive lexical range (`BETWEEN ? AND ?`), e.g. of ISO dates. Query param:
+    /// `field[between]=A,M`; escape a literal comma as `\,`.
+    Between(T, T),
+    /// Length greater than (`LENGTH(col) > ?`, `CHAR_LENGTH` on MySQL). Query param: `field[len_gt]=0`.
+    LenGt(usize),
+    /// Length less than (`LENGTH(col) < ?`, `CHAR_LENGTH` on MySQL). Query param: `field[len_lt]=10`.
+    LenLt(usize),
+    /// Length equal to (`LENGTH(col) = ?`, `CHAR_LENGTH` on MySQL). Query param: `field[len_eq]=5`.
+    LenEq(usize),
+    /// Negates another filter (`NOT (...)`). Query param: the operator prefixed with `!`, e.g. `field[!contains]=value`.
+    ///
+    /// Like any SQL condition, it doesn't match rows where the column is `NULL`.
+    Not(Box<StringFilter<T>>),
 }
 
 impl<T> FromStrFilter<T> for StringFilter<T>
 where
     T: FromStr,
 {
-    fn from_str(id: &str, value: T) -> Result<Self, FilterParseError> {
-        match id {
-            "eq" => Ok(StringFilter::Eq(value)),
-            "ne" => Ok(StringFilter::Ne(value)),
+    fn from_str(op: &Op, value: T) -> Result<Self, FilterParseError> {
+        match op {
+            Op::Eq => Ok(StringFilter::Eq(value)),
+            Op::Ne => Ok(StringFilter::Ne(value)),
+            Op::NullSafeEq => Ok(StringFilter::NullSafeEq(Some(value))),
+            Op::Like => Ok(StringFilter::Like(value)),
+            Op::NotLike => Ok(StringFilter::NotLike(value)),
+            Op::ILike => Ok(StringFilter::ILike(value)),
+            Op::StartsWith => Ok(StringFilter::StartsWith(value)),
+            Op::EndsWith => Ok(StringFilter::EndsWith(value)),
+            Op::Contains => Ok(StringFilter::Contains(value)),
+            Op::Regex => Ok(StringFilter::Regex(value)),
+            Op::IRegex => Ok(StringFilter::IRegex(value)),
+            #[cfg(feature = "postgres")]
+            Op::Similar => Ok(StringFilter::Similar(value)),
+            #[cfg(feature = "postgres")]
+            Op::ContainsUnaccent => Ok(StringFilter::ContainsUnaccent(value)),
 
-            "like" | "l" => Ok(StringFilter::Like(value)),
+            _ => Err(FilterParseError::UnknownFilter),
+        }
+    }
+}
 
-            "not_like" | "nl" => Ok(StringFilter::NotLike(value)),
+/// The raw value of a string filter, which is only parsed once the operator is known,
+/// since the length operators take a number instead of a `T`. It is read straight from
+/// the query, so only the parsed `T` is allocated.
+pub(crate) struct StringOperand<T>(std::marker::PhantomData<T>);
 
-            "starts_with" | "sw" => Ok(StringFilter::StartsWith(value)),
+impl<T> FromParam<StringOperand<T>> for StringFilter<T>
+where
+    T: FromStr,
+    T::Err: Into<BoxError>,
+{
+    fn from_param(op: &Op, value: &str) -> Result<Self, FilterParseError> {
+        // a single `!` negates the operator after it
+        if let Op::Not(op) = op {
+            if let Op::Not(_) = **op {
+                return Err(FilterParseError::UnknownFilter);
+            }
 
-            "ends_with" | "ew" => Ok(StringFilter::EndsWith(value)),
+            return <Self as FromParam<StringOperand<T>>>::from_param(op, value)
+                .map(|x| StringFilter::Not(Box::new(x)));
+        }
 
-            "contains" | "c" => Ok(StringFilter::Contains(value)),
+        let len = || value.parse::<usize>().map_err(FilterParseError::value);
 
-            _ => Err(FilterParseError::UnknownFilter),
+        match op {
+            Op::LenGt => Ok(StringFilter::LenGt(len()?)),
+            Op::LenLt => Ok(StringFilter::LenLt(len()?)),
+            Op::LenEq => Ok(StringFilter::LenEq(len()?)),
+            Op::NullSafeEq if value.eq_ignore_ascii_case("null") => {
+                Ok(StringFilter::NullSafeEq(None))
+            }
+            Op::Between => {
+                let (low, high) = parse_pair(value)?;
+                Ok(StringFilter::Between(low, high))
+            }
+            _ => {
+                let value: T = value.parse().map_err(FilterParseError::value)?;
+                <Self as FromStrFilter<T>>::from_str(op, value)
+            }
         }
     }
 }
@@ -78,26 +160,342 @@ impl<T> StringFilters<T>
 where
     T: FromStr + Display,
 {
+    /// Creates a collection of `filters` applied to the field `search_id`.
+    pub fn new(filters: Vec<StringFilter<T>>, search_id: FilterId) -> Self {
+        Self(filters, Some(search_id))
+    }
+
     /// Parses string filters from a query string for a specific search ID.
-    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError> {
+    pub fn from_str(search_id: &str, value: &str) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
         Self::from_id_value(search_id.to_string().into(), value)
     }
 
     /// Parses string filters from a query string for a specific `FilterId`.
-    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError> {
-        from_str(search_id.id(), value).map(|x| Self(x, Some(search_id)))
+    pub fn from_id_value(search_id: FilterId, value: &str) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
+        Self::from_id_value_with(search_id, value, &ParseOptions::default())
+    }
+
+    /// Parses string filters from a query string for a specific `FilterId`, using `options`.
+    pub fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError>
+    where
+        T::Err: Into<BoxError>,
+    {
+        from_str_with::<StringOperand<T>, _>(search_id.id(), value, options)
+            .map(|x| Self(x, Some(search_id)))
+    }
+
+    /// Returns `true` if no filters were parsed.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Sets the `FilterId` the filters apply to.
+    pub fn with_id(self, search_id: FilterId) -> Self {
+        Self(self.0, Some(search_id))
+    }
+
+    /// Starts an empty collection for `column`, to add conditions to fluently.
+    ///
+    /// ```rust
+    /// use filtrum::string_filter::{StringFilter, StringFilters};
+    ///
+    /// let name = StringFilters::<String>::for_column("name").contains("al").not_equal_to("Alan");
+    ///
+    /// assert_eq!(
+    ///     name.0,
+    ///     vec![
+    ///         StringFilter::Contains("al".to_string()),
+    ///         StringFilter::Ne("Alan".to_string())
+    ///     ]
+    /// );
+    /// ```
+    pub fn for_column(column: &str) -> Self {
+        Self(Vec::new(), Some(column.to_string().into()))
+    }
+
+    /// Adds `filter` to the collection.
+    pub fn and(mut self, filter: StringFilter<T>) -> Self {
+        self.0.push(filter);
+        self
+    }
+
+    /// Adds an exact match condition.
+    pub fn equal_to(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Eq(value.into()))
+    }
+
+    /// Adds a not equal condition.
+    pub fn not_equal_to(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Ne(value.into()))
+    }
+
+    /// Adds a NULL-safe exact match condition; `None` matches the `NULL` rows.
+    pub fn null_safe_eq<V: Into<T>>(self, value: impl Into<Option<V>>) -> Self {
+        self.and(StringFilter::NullSafeEq(value.into().map(Into::into)))
+    }
+
+    /// Adds a `LIKE` condition.
+    pub fn like(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Like(value.into()))
+    }
+
+    /// Adds a `NOT LIKE` condition.
+    pub fn not_like(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::NotLike(value.into()))
+    }
+
+    /// Adds a case-insensitive `LIKE` condition.
+    pub fn ilike(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::ILike(value.into()))
+    }
+
+    /// Adds a starts with condition.
+    pub fn starts_with(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::StartsWith(value.into()))
+    }
+
+    /// Adds an ends with condition.
+    pub fn ends_with(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::EndsWith(value.into()))
+    }
+
+    /// Adds a contains condition.
+    pub fn contains(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Contains(value.into()))
+    }
+
+    /// Adds a regex condition.
+    pub fn regex(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Regex(value.into()))
+    }
+
+    /// Adds a case-insensitive regex condition.
+    pub fn iregex(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::IRegex(value.into()))
+    }
+
+    /// Adds a trigram similarity condition.
+    pub fn similar(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::Similar(value.into()))
+    }
+
+    /// Adds an accent-insensitive contains condition.
+    pub fn contains_unaccent(self, value: impl Into<T>) -> Self {
+        self.and(StringFilter::ContainsUnaccent(value.into()))
+    }
+
+    /// Adds a `BETWEEN low AND high` condition.
+    pub fn between(self, low: impl Into<T>, high: impl Into<T>) -> Self {
+        self.and(StringFilter::Between(low.into(), high.into()))
+    }
+
+    /// Adds a length greater than `len` condition.
+    pub fn len_gt(self, len: usize) -> Self {
+        self.and(StringFilter::LenGt(len))
+    }
+
+    /// Adds a length less than `len` condition.
+    pub fn len_lt(self, len: usize) -> Self {
+        self.and(StringFilter::LenLt(len))
+    }
+
+    /// Adds a length equal to `len` condition.
+    pub fn len_eq(self, len: usize) -> Self {
+        self.and(StringFilter::LenEq(len))
+    }
+
+    /// Adds the negation of `filter`.
+    pub fn negate(self, filter: StringFilter<T>) -> Self {
+        self.and(StringFilter::Not(Box::new(filter)))
+    }
+}
+
+/// Wraps the filters without a `FilterId`; attach one with [`StringFilters::with_id`].
+impl<T> From<Vec<StringFilter<T>>> for StringFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn from(value: Vec<StringFilter<T>>) -> Self {
+        Self(value, None)
+    }
+}
+
+impl<T> From<StringFilters<T>> for Vec<StringFilter<T>>
+where
+    T: FromStr + Display,
+{
+    fn from(value: StringFilters<T>) -> Self {
+        value.0
+    }
+}
+
+impl<T> Filter for StringFilters<T>
+where
+    T: FromStr + Display + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn from_id_value_with<'a>(
+        search_id: FilterId,
+        value: impl Into<QueryParams<'a>>,
+        options: &ParseOptions,
+    ) -> Result<Self, FilterParseError> {
+        StringFilters::from_id_value_with(search_id, value, options)
+    }
+
+    fn filter_id(&self) -> Option<&FilterId> {
+        self.1.as_ref()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    fn conditions(&self) -> Vec<(Op, FilterValue)> {
+        self.0
+            .iter()
+            .map(|x| (x.operator(), x.filter_value()))
+            .collect()
+    }
+}
+
+impl<T> VisitConditions for StringFilters<T>
+where
+    T: FromStr + Display + ToFilterValue,
+    T::Err: Into<BoxError>,
+{
+    fn visit_conditions(&self, visit: &mut dyn FnMut(String, Op, FilterValue)) {
+        visit_filter(self, visit);
+    }
+}
+
+impl<T> Merge for StringFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn merge(&mut self, other: Self) {
+        // a condition from `other` replaces the one with the same operator
+        self.0
+            .retain(|x| !other.0.iter().any(|y| y.operator() == x.operator()));
+        self.0.extend(other.0);
+
+        if self.1.is_none() {
+            self.1 = other.1;
+        }
+    }
+}
+
+impl<T> StringFilter<T> {
+    /// Returns the operator (e.g. [`Op::StartsWith`]), which displays as its canonical
+    /// query key. A negated filter returns the [`Op::Not`] of the operator it negates.
+    pub fn operator(&self) -> Op {
+        match self {
+            StringFilter::Eq(_) => Op::Eq,
+            StringFilter::Ne(_) => Op::Ne,
+            StringFilter::NullSafeEq(_) => Op::NullSafeEq,
+            StringFilter::Like(_) => Op::Like,
+            StringFilter::NotLike(_) => Op::NotLike,
+            StringFilter::ILike(_) => Op::ILike,
+            StringFilter::StartsWith(_) => Op::StartsWith,
+            StringFilter::EndsWith(_) => Op::EndsWith,
+            StringFilter::Contains(_) => Op::Contains,
+            StringFilter::Regex(_) => Op::Regex,
+            StringFilter::IRegex(_) => Op::IRegex,
+            StringFilter::Similar(_) => Op::Similar,
+            StringFilter::ContainsUnaccent(_) => Op::ContainsUnaccent,
+            StringFilter::Between(_, _) => Op::Between,
+            StringFilter::LenGt(_) => Op::LenGt,
+            StringFilter::LenLt(_) => Op::LenLt,
+            StringFilter::LenEq(_) => Op::LenEq,
+            StringFilter::Not(inner) => Op::Not(Box::new(inner.operator())),
+        }
+    }
+
+    /// Returns a reference to the value the filter compares against, or `None` for the
+    /// length operators, which compare against [`StringFilter::length`] instead. For
+    /// `Between` this is the lower bound.
+    pub fn value(&self) -> Option<&T> {
+        match self {
+            StringFilter::Eq(v)
+            | StringFilter::Ne(v)
+            | StringFilter::Like(v)
+            | StringFilter::NotLike(v)
+            | StringFilter::ILike(v)
+            | StringFilter::StartsWith(v)
+            | StringFilter::EndsWith(v)
+            | StringFilter::Contains(v)
+            | StringFilter::Regex(v)
+            | StringFilter::IRegex(v)
+            | StringFilter::Similar(v)
+            | StringFilter::ContainsUnaccent(v)
+            | StringFilter::Between(v, _) => Some(v),
+            StringFilter::NullSafeEq(v) => v.as_ref(),
+            StringFilter::LenGt(_) | StringFilter::LenLt(_) | StringFilter::LenEq(_) => None,
+            StringFilter::Not(inner) => inner.value(),
+        }
+    }
+
+    /// Returns the length a length operator compares against, or `None` for the others.
+    pub fn length(&self) -> Option<usize> {
+        match self {
+            StringFilter::LenGt(n) | StringFilter::LenLt(n) | StringFilter::LenEq(n) => Some(*n),
+            StringFilter::Not(inner) => inner.length(),
+            _ => None,
+        }
+    }
+
+    /// Returns both bounds of a `Between`, or `None` for the others.
+    pub fn bounds(&self) -> Option<(&T, &T)> {
+        match self {
+            StringFilter::Between(low, high) => Some((low, high)),
+            StringFilter::Not(inner) => inner.bounds(),
+            _ => None,
+        }
+    }
+
+    /// Returns the value the filter compares against as a [`FilterValue`]. For `Between`
+    /// this is a list of both bounds.
+    pub fn filter_value(&self) -> FilterValue
+    where
+        T: ToFilterValue,
+    {
+        if let Some((low, high)) = self.bounds() {
+            return FilterValue::List(vec![low.to_filter_value(), high.to_filter_value()]);
+        }
+
+        match (self.value(), self.length()) {
+            (Some(v), _) => v.to_filter_value(),
+            (None, Some(n)) => n.to_filter_value(),
+            // a NULL-safe comparison with `NULL`
+            (None, None) => FilterValue::Null,
+        }
     }
 }
 
-impl<'de> Deserialize<'de> for StringFilter {
+impl<'de, T> Deserialize<'de> for StringFilter<T>
+where
+    T: FromStr,
+{
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: serde::Deserializer<'de>,
     {
-        struct StringFilterVisitor;
+        struct StringFilterVisitor<T>(std::marker::PhantomData<T>);
 
-        impl<'de> de::Visitor<'de> for StringFilterVisitor {
-            type Value = StringFilter;
+        impl<'de, T> de::Visitor<'de> for StringFilterVisitor<T>
+        where
+            T: FromStr,
+        {
+            type Value = StringFilter<T>;
 
             fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
                 formatter.write_str("a string filter")
@@ -107,35 +505,125 @@ impl<'de> Deserialize<'de> for StringFilter {
             where
                 E: de::Error,
             {
-                let parts = v.split('=').collect::<Vec<_>>();
+                let parse = |value: &str| {
+                    value.parse::<T>().map_err(|_| {
+                        de::Error::invalid_value(
+                            de::Unexpected::Str(value),
+                            &"a string filter value",
+                        )
+                    })
+                };
 
-                if parts.len() != 2 {
+                // only the first `=` splits, so a value may contain more of them
+                let Some((key, value)) = v.split_once('=') else {
                     // we infer is equals
-                    return Ok(StringFilter::Eq(v.to_string()));
-                }
+                    return Ok(StringFilter::Eq(parse(v)?));
+                };
 
-                let (key, value) = (parts[0], parts[1]);
+                if let Some(key) = key.strip_prefix('!')
+                    && !key.starts_with('!')
+                {
+                    return StringFilterVisitor(std::marker::PhantomData)
+                        .visit_str(&format!("{}={}", key, value))
+                        .map(|x| StringFilter::Not(Box::new(x)));
+                }
 
-                match key {
-                    "eq" => Ok(StringFilter::Eq(value.to_string())),
-                    "ne" => Ok(StringFilter::Ne(value.to_string())),
+                let len = || {
+                    value.parse::<usize>().map_err(|_| {
+                        de::Error::invalid_value(de::Unexpected::Str(value), &"a length")
+                    })
+                };
 
-                    "like" | "l" => Ok(StringFilter::Like(value.to_string())),
+                let op = Op::from(key);
+                match op {
+                    Op::LenGt => return Ok(StringFilter::LenGt(len()?)),
+                    Op::LenLt => return Ok(StringFilter::LenLt(len()?)),
+                    Op::LenEq => return Ok(StringFilter::LenEq(len()?)),
+                    Op::NullSafeEq if value.eq_ignore_ascii_case("null") => {
+                        return Ok(StringFilter::NullSafeEq(None));
+                    }
+                    Op::Between => {
+                        return parse_pair_with(value, |x| {
+                            x.parse().map_err(|_| FilterParseError::Value(None))
+                        })
+                        .map(|(low, high)| StringFilter::Between(low, high))
+                            .map_err(|_| {
+                                de::Error::invalid_value(
+                                    de::Unexpected::Str(value),
+                                    &"a `low,high` pair",
+                                )
+                            });
+                    }
+                    _ => {}
+                }
 
-                    "not_like" | "nl" => Ok(StringFilter::NotLike(value.to_string())),
+                let value = parse(value)?;
 
-                    "starts_with" | "sw" => Ok(StringFilter::StartsWith(value.to_string())),
+                // the operators that take a single `T` are built like in a query string
+                <StringFilter<T> as FromStrFilter<T>>::from_str(&op, value)
+                    .map_err(|_| de::Error::custom("unknown string filter"))
+            }
+        }
 
-                    "ends_with" | "ew" => Ok(StringFilter::EndsWith(value.to_string())),
+        deserializer.deserialize_str(StringFilterVisitor(std::marker::PhantomData))
+    }
+}
 
-                    "contains" | "c" => Ok(StringFilter::Contains(value.to_string())),
+impl<T> Serialize for StringFilter<T>
+where
+    T: Display,
+{
+    // always emits the explicit `key=value` form, so it reads back the same.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        // a comma in a bound is escaped, so the pair splits back the same way
+        if let Some((low, high)) = self.bounds() {
+            let escape = |x: &T| x.to_string().replace(',', "\\,");
+            return serializer.collect_str(&format_args!(
+                "{}={},{}",
+                self.operator(),
+                escape(low),
+                escape(high)
+            ));
+        }
 
-                    _ => Err(de::Error::custom("unknown string filter")),
-                }
-            }
+        match (self.value(), self.length()) {
+            (Some(v), _) => serializer.collect_str(&format_args!("{}={}", self.operator(), v)),
+            (None, Some(n)) => serializer.collect_str(&format_args!("{}={}", self.operator(), n)),
+            // a NULL-safe comparison with `NULL`
+            (None, None) => serializer.collect_str(&format_args!("{}=null", self.operator())),
         }
+    }
+}
+
+/// Deserializes from a sequence of filters, e.g. `["sw=Al", "ne=Alice"]`.
+///
+/// The field the filters belong to is not part of the serialized form, so the
+/// resulting collection has no `FilterId`; attach one with [`StringFilters::with_id`].
+impl<'de, T> Deserialize<'de> for StringFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Vec::<StringFilter<T>>::deserialize(deserializer).map(|x| Self(x, None))
+    }
+}
 
-        deserializer.deserialize_str(StringFilterVisitor)
+/// Serializes into a sequence of filters, e.g. `["starts_with=Al", "ne=Alice"]`.
+impl<T> Serialize for StringFilters<T>
+where
+    T: FromStr + Display,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.0.serialize(serializer)
     }
 }
 
@@ -153,6 +641,63 @@ mod tests {
         assert!(filters.contains(&StringFilter::Ne("doe".to_string())));
     }
 
+    #[test]
+    fn test_string_filter_null_safe_eq() {
+        let query = "name[nseq]=Al&name[!nseq]=Bo&name[nseq]=null";
+        let f = StringFilters::<String>::from_str("name", query).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::NullSafeEq(Some("Al".to_string())),
+                StringFilter::Not(Box::new(StringFilter::NullSafeEq(Some("Bo".to_string())))),
+                StringFilter::NullSafeEq(None),
+            ]
+        );
+        assert_eq!(f.0[1].operator().to_string(), "!nseq");
+        assert_eq!(f.0[2].value(), None);
+        assert_eq!(f.0[2].filter_value(), FilterValue::Null);
+
+        let json = serde_json::to_string(&f.0).unwrap();
+        assert_eq!(json, r#"["nseq=Al","!nseq=Bo","nseq=null"]"#);
+        assert_eq!(
+            serde_json::from_str::<Vec<StringFilter>>(&json).unwrap(),
+            f.0
+        );
+    }
+
+    #[test]
+    fn test_string_filter_bracketed_values() {
+        // only the key, before the first `=`, is matched for `field[op]`
+        let qs = "note[eq]=see [ref]&note=[x]&note[sw]=tag[x]=1&other[eq]=note[eq]";
+        let f = StringFilters::<String>::from_str("note", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Eq("see [ref]".to_string()),
+                StringFilter::Eq("[x]".to_string()),
+                StringFilter::StartsWith("tag[x]=1".to_string())
+            ]
+        );
+
+        let f = StringFilters::<String>::from_str("other", qs).unwrap();
+        assert_eq!(f.0, vec![StringFilter::Eq("note[eq]".to_string())]);
+    }
+
+    #[test]
+    fn test_string_filter_operator_case() {
+        let qs = "name[LIKE]=John&name[Sw]=Jo&name[!CONTAINS]=X&name[Len_Gt]=2";
+        let f = StringFilters::<String>::from_str("name", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Like("John".to_string()),
+                StringFilter::StartsWith("Jo".to_string()),
+                StringFilter::Not(Box::new(StringFilter::Contains("X".to_string()))),
+                StringFilter::LenGt(2)
+            ]
+        );
+    }
+
     #[test]
     fn test_string_deserialization() {
         let f: StringFilter = serde_json::from_str("\"like=john\"").unwrap();
@@ -160,5 +705,275 @@ mod tests {
 
         let f: StringFilter = serde_json::from_str("\"john\"").unwrap();
         assert_eq!(f, StringFilter::Eq("john".to_string()));
+
+        let f: StringFilter = serde_json::from_str("\"ilike=%John%\"").unwrap();
+        assert_eq!(f, StringFilter::ILike("%John%".to_string()));
+    }
+
+    #[test]
+    fn test_string_deserialization_value_with_equals() {
+        let f: StringFilter = serde_json::from_str("\"like=a=b\"").unwrap();
+        assert_eq!(f, StringFilter::Like("a=b".to_string()));
+
+        let f: StringFilter = serde_json::from_str("\"!eq=x==y\"").unwrap();
+        assert_eq!(
+            f,
+            StringFilter::Not(Box::new(StringFilter::Eq("x==y".to_string())))
+        );
+
+        // it reads back what it serializes
+        let f = StringFilter::Contains("expr=a=b".to_string());
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, "\"contains=expr=a=b\"");
+        assert_eq!(serde_json::from_str::<StringFilter>(&json).unwrap(), f);
+    }
+
+    #[test]
+    fn test_string_filters_round_trip() {
+        let f: StringFilters = serde_json::from_str(r#"["sw=Al", "ne=Alice"]"#).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::StartsWith("Al".to_string()),
+                StringFilter::Ne("Alice".to_string())
+            ]
+        );
+        assert_eq!(f.1, None);
+
+        let json = serde_json::to_string(&f).unwrap();
+        assert_eq!(json, r#"["starts_with=Al","ne=Alice"]"#);
+
+        let back: StringFilters = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, f);
+
+        let f = back.with_id("name".to_string().into());
+        assert_eq!(f.1, Some(FilterId::Alone("name".to_string())));
+    }
+
+    #[test]
+    fn test_string_filter_empty_values() {
+        let f = StringFilters::<String>::from_str("name", "name[eq]=&name[ne]=doe&").unwrap();
+        assert_eq!(f.0, vec![StringFilter::Ne("doe".to_string())]);
+
+        let options = ParseOptions {
+            keep_empty_values: true,
+            ..Default::default()
+        };
+        let f = StringFilters::<String>::from_id_value_with(
+            "name".to_string().into(),
+            "name[eq]=&name[ne]=doe&",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Eq("".to_string()),
+                StringFilter::Ne("doe".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_filter_regex() {
+        let qs = "name[regex]=^Al.*&name[iregex]=son$";
+        let f = StringFilters::<String>::from_str("name", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Regex("^Al.*".to_string()),
+                StringFilter::IRegex("son$".to_string())
+            ]
+        );
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_string_filter_contains_unaccent() {
+        let f = StringFilters::<String>::from_str("name", "name[contains_unaccent]=jose").unwrap();
+        assert_eq!(
+            f.0,
+            vec![StringFilter::ContainsUnaccent("jose".to_string())]
+        );
+        assert_eq!(f.0[0].operator().to_string(), "contains_unaccent");
+
+        let f: StringFilter = serde_json::from_str("\"contains_unaccent=jose\"").unwrap();
+        assert_eq!(f, StringFilter::ContainsUnaccent("jose".to_string()));
+    }
+
+    #[test]
+    fn test_string_filter_not() {
+        let f = StringFilters::<String>::from_str("name", "name[!contains]=spam&name[!len_lt]=3")
+            .unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Not(Box::new(StringFilter::Contains("spam".to_string()))),
+                StringFilter::Not(Box::new(StringFilter::LenLt(3)))
+            ]
+        );
+        assert_eq!(f.0[0].operator().to_string(), "!contains");
+        assert_eq!(f.0[0].value(), Some(&"spam".to_string()));
+        assert_eq!(f.0[1].length(), Some(3));
+
+        for qs in ["name[!!contains]=spam", "name[!nope]=spam"] {
+            assert!(
+                matches!(
+                    StringFilters::<String>::from_str("name", qs),
+                    Err(FilterParseError::UnknownFilter)
+                ),
+                "{qs}"
+            );
+        }
+
+        let json = serde_json::to_string(&f.0[0]).unwrap();
+        assert_eq!(json, "\"!contains=spam\"");
+        assert_eq!(serde_json::from_str::<StringFilter>(&json).unwrap(), f.0[0]);
+    }
+
+    #[test]
+    fn test_string_filter_aliases() {
+        let options = ParseOptions {
+            aliases: crate::options::OperatorAliases::new().with("$regex", "regex"),
+            ..Default::default()
+        };
+
+        let f = StringFilters::<String>::from_id_value_with(
+            "name".to_string().into(),
+            "name[$regex]=^Al&name[!$regex]=son$",
+            &options,
+        )
+        .unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Regex("^Al".to_string()),
+                StringFilter::Not(Box::new(StringFilter::Regex("son$".to_string())))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_string_filter_length() {
+        let qs = "bio[len_gt]=0&bio[len_lt]=280&bio[len_eq]=5&bio[sw]=5";
+        let f = StringFilters::<String>::from_str("bio", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::LenGt(0),
+                StringFilter::LenLt(280),
+                StringFilter::LenEq(5),
+                StringFilter::StartsWith("5".to_string())
+            ]
+        );
+        assert_eq!(f.0[0].value(), None);
+        assert_eq!(f.0[0].length(), Some(0));
+        assert_eq!(f.0[1].filter_value(), FilterValue::Int(280));
+
+        for qs in ["bio[len_gt]=-1", "bio[len_lt]=abc", "bio[len_eq]=1.5"] {
+            assert!(
+                matches!(
+                    StringFilters::<String>::from_str("bio", qs),
+                    Err(FilterParseError::Value(_))
+                ),
+                "{qs}"
+            );
+        }
+
+        let f: StringFilter = serde_json::from_str("\"len_gt=3\"").unwrap();
+        assert_eq!(f, StringFilter::LenGt(3));
+        assert_eq!(serde_json::to_string(&f).unwrap(), "\"len_gt=3\"");
+        assert!(serde_json::from_str::<StringFilter>("\"len_gt=x\"").is_err());
+    }
+
+    #[test]
+    fn test_string_filter_between() {
+        let qs = "name[between]=A,M&created[between]=2024-01-01,2024-06-30&tag[!between]=a\\,b,c";
+        let f = StringFilters::<String>::from_str("name", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![StringFilter::Between("A".to_string(), "M".to_string())]
+        );
+        assert_eq!(f.0[0].value(), Some(&"A".to_string()));
+        assert_eq!(
+            f.0[0].filter_value(),
+            FilterValue::List(vec!["A".to_filter_value(), "M".to_filter_value()])
+        );
+
+        let f = StringFilters::<String>::from_str("created", qs).unwrap();
+        assert_eq!(
+            f.0,
+            vec![StringFilter::Between(
+                "2024-01-01".to_string(),
+                "2024-06-30".to_string()
+            )]
+        );
+
+        let f = StringFilters::<String>::from_str("tag", qs).unwrap();
+        assert_eq!(f.0[0].operator().to_string(), "!between");
+        assert_eq!(
+            f.0[0].bounds(),
+            Some((&"a,b".to_string(), &"c".to_string()))
+        );
+        let json = serde_json::to_string(&f.0[0]).unwrap();
+        assert_eq!(json, r#""!between=a\\,b,c""#);
+        assert_eq!(serde_json::from_str::<StringFilter>(&json).unwrap(), f.0[0]);
+
+        for qs in ["name[between]=A", "name[between]=A,B,C"] {
+            assert!(
+                matches!(
+                    StringFilters::<String>::from_str("name", qs),
+                    Err(FilterParseError::Value(_))
+                ),
+                "{qs}"
+            );
+        }
     }
-}
\ No newline at end of file
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_string_filter_similar() {
+        let f =
+            StringFilters::<String>::from_str("name", "name[similar]=jon&name[sim]=doe").unwrap();
+        assert_eq!(
+            f.0,
+            vec![
+                StringFilter::Similar("jon".to_string()),
+                StringFilter::Similar("doe".to_string())
+            ]
+        );
+    }
+
+    #[cfg(not(feature = "postgres"))]
+    #[test]
+    fn test_string_filter_postgres_ops_need_postgres() {
+        // no other database has `pg_trgm` or `unaccent`, so these aren't filters there
+        for qs in ["name[similar]=jon", "name[contains_unaccent]=jose"] {
+            assert!(matches!(
+                StringFilters::<String>::from_str("name", qs),
+                Err(FilterParseError::UnknownFilter)
+            ));
+        }
+        assert!(serde_json::from_str::<StringFilter>("\"sim=jon\"").is_err());
+        assert!(serde_json::from_str::<StringFilter>("\"contains_unaccent=jose\"").is_err());
+    }
+
+    #[test]
+    fn test_string_filters_conversions() {
+        let id = FilterId::WithPrefix("users".to_string(), "name".to_string());
+        let f = StringFilters::new(vec![StringFilter::Eq("a".to_string())], id.clone());
+        assert_eq!(f.1, Some(id.clone()));
+
+        let filters: Vec<StringFilter> = f.into();
+        assert_eq!(filters, vec![StringFilter::Eq("a".to_string())]);
+
+        let f = StringFilters::from(filters).with_id(id.clone());
+        assert_eq!(
+            f,
+            StringFilters(vec![StringFilter::Eq("a".to_string())], Some(id))
+        );
+
+        let f: StringFilters = Vec::new().into();
+        assert_eq!(f.1, None);
+    }
+}
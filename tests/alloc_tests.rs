@@ -0,0 +1,76 @@
+//! Counts the heap allocations of parsing, so the parse pipeline keeps borrowing from
+//! the query instead of copying it.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use filtrum::in_filter::InFilters;
+use filtrum::string_filter::StringFilters;
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|x| x.set(x.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+/// Returns the allocations `f` makes on this thread, after a first run to warm up.
+fn allocations<T>(f: impl Fn() -> T) -> usize {
+    drop(f());
+
+    let before = ALLOCATIONS.with(Cell::get);
+    drop(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn string_allocations(query: &str) -> usize {
+    allocations(|| StringFilters::<String>::from_str("name", query).unwrap())
+}
+
+#[test]
+fn test_other_fields_allocate_nothing() {
+    let query = "name[sw]=al";
+    let noisy = "age=1&age[gte]=2&name[sw]=al&active=true&tags[in]=a,b&q=x";
+
+    assert_eq!(string_allocations(noisy), string_allocations(query));
+    assert_eq!(string_allocations("age=1&age=2"), string_allocations(""));
+}
+
+#[test]
+fn test_string_filters_copy_only_the_values() {
+    // at most the filters `Vec` and the parsed value, as the value is read straight
+    // from the query
+    let most = string_allocations("") + 2;
+    for query in [
+        "name=al",
+        "name[sw]=al",
+        "name[not_like]=al",
+        "name[len_gt]=2",
+    ] {
+        assert!(string_allocations(query) <= most, "{query}");
+    }
+
+    // lowercasing the operator is the only extra copy
+    assert!(string_allocations("name[SW]=al") <= most + 1);
+}
+
+#[test]
+fn test_list_values_are_borrowed() {
+    let ids = |query: &str| allocations(|| InFilters::<i32>::from_str("id", query).unwrap());
+
+    // the values are parsed from slices of the query, not from a copy of each
+    assert_eq!(ids("id[in]=1,2,3"), ids("id[in]=1"));
+}
@@ -7,6 +7,7 @@ mod tests {
     use filtrum::query_filter::FromQueryFilter;
     use filtrum::common::WithFilterId;
     use filtrum::equal_filter::EqualFilter;
+    use filtrum::number_filter::NumberFilters;
     use filtrum::errors::FilterParseError;
     use std::str::FromStr;
 
@@ -27,7 +28,7 @@ mod tests {
     }
 
     async fn handler(filter: FromQueryFilter<MyFilter>) -> String {
-        format!("age: {:?}", filter.inner.age.into_inner())
+        format!("age: {:?}", filter.inner.age.as_ref())
     }
 
     #[tokio::test]
@@ -66,4 +67,556 @@ mod tests {
 
         assert_eq!(response.status(), StatusCode::BAD_REQUEST);
     }
+
+    async fn raw_query_handler(
+        query: axum::extract::RawQuery,
+    ) -> Result<String, filtrum::axum::FilterRejection> {
+        let filter =
+            FromQueryFilter::<MyFilter>::try_from(query).map_err(filtrum::axum::FilterRejection)?;
+
+        Ok(format!(
+            "{:?} {:?}",
+            filter.inner.age.into_inner(),
+            filter.raw
+        ))
+    }
+
+    #[tokio::test]
+    async fn test_axum_try_from_raw_query() {
+        let app = Router::new().route("/", get(raw_query_handler));
+
+        for (uri, status, expected) in [
+            (
+                "/?age=25&limit=5",
+                StatusCode::OK,
+                r#"Some(25) Some("age=25&limit=5")"#,
+            ),
+            ("/", StatusCode::OK, "None None"),
+            ("/?age=x", StatusCode::BAD_REQUEST, "invalid filter value"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), status, "{uri}");
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes(), "{uri}");
+        }
+    }
+
+    async fn raw_handler(filter: FromQueryFilter<MyFilter>) -> String {
+        format!("{:?}", filter.raw)
+    }
+
+    #[tokio::test]
+    async fn test_axum_raw_query() {
+        let app = Router::new().route("/", get(raw_handler));
+
+        for (uri, expected) in [
+            ("/?age=25&limit=5", "Some(\"age=25&limit=5\")"),
+            ("/", "None"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    async fn lenient_handler(
+        filtrum::axum::Lenient(filter): filtrum::axum::Lenient<FromQueryFilter<MyFilter>>,
+    ) -> String {
+        format!(
+            "age: {:?}, limit: {:?}, raw: {:?}",
+            filter.inner.age.into_inner(),
+            filter.limit,
+            filter.raw
+        )
+    }
+
+    #[tokio::test]
+    async fn test_axum_lenient_extractor() {
+        let app = Router::new().route("/", get(lenient_handler));
+
+        for (uri, expected) in [
+            (
+                "/?age=25&limit=5",
+                "age: Some(25), limit: Some(Limit(5)), raw: Some(\"age=25&limit=5\")",
+            ),
+            // a malformed query falls back to the empty filter instead of a 400
+            (
+                "/?age=x&limit=5",
+                "age: None, limit: None, raw: Some(\"age=x&limit=5\")",
+            ),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), StatusCode::OK);
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    async fn form_handler(
+        filtrum::axum::FilterForm(filter): filtrum::axum::FilterForm<FromQueryFilter<MyFilter>>,
+    ) -> String {
+        format!(
+            "age: {:?}, limit: {:?}, raw: {:?}",
+            filter.inner.age.into_inner(),
+            filter.limit,
+            filter.raw
+        )
+    }
+
+    #[tokio::test]
+    async fn test_axum_form_extractor() {
+        let app = Router::new().route("/", axum::routing::post(form_handler));
+
+        for (content_type, body, status, expected) in [
+            (
+                "application/x-www-form-urlencoded",
+                "age=25&limit=5",
+                StatusCode::OK,
+                "age: Some(25), limit: Some(Limit(5)), raw: Some(\"age=25&limit=5\")",
+            ),
+            (
+                "application/x-www-form-urlencoded; charset=utf-8",
+                "age=30",
+                StatusCode::OK,
+                "age: Some(30), limit: None, raw: Some(\"age=30\")",
+            ),
+            // the body is kept as posted, before it is decoded
+            (
+                "application/x-www-form-urlencoded",
+                "age%5Beq%5D=7",
+                StatusCode::OK,
+                "age: Some(7), limit: None, raw: Some(\"age%5Beq%5D=7\")",
+            ),
+            (
+                "application/x-www-form-urlencoded",
+                "age=x",
+                StatusCode::BAD_REQUEST,
+                "invalid filter value",
+            ),
+            (
+                "application/json",
+                "{\"age\":25}",
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "expected an `application/x-www-form-urlencoded` body",
+            ),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/")
+                        .header("content-type", content_type)
+                        .body(Body::from(body))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status, "{body}");
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    #[derive(Default, Debug)]
+    struct PersonFilter {
+        age: NumberFilters<i32>,
+        name: EqualFilter<String>,
+    }
+
+    // parses the decoded pairs as they are, like the derive, so a value can hold `&`
+    impl WithFilterId for PersonFilter {
+        fn filter_id() -> Option<&'static str> { None }
+
+        fn from_str_with<'a>(
+            s: impl Into<filtrum::QueryParams<'a>>,
+            options: &filtrum::ParseOptions,
+        ) -> Result<Self, FilterParseError> {
+            let s = s.into();
+            Ok(PersonFilter {
+                age: NumberFilters::from_id_value_with("age".to_string().into(), s, options)?,
+                name: EqualFilter::from_id_value_with("name".to_string().into(), s, options)?,
+            })
+        }
+    }
+
+    impl FromStr for PersonFilter {
+        type Err = FilterParseError;
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Self::from_str_with(s, &Default::default())
+        }
+    }
+
+    async fn person_form_handler(
+        filtrum::axum::FilterForm(filter): filtrum::axum::FilterForm<FromQueryFilter<PersonFilter>>,
+    ) -> String {
+        format!("{:?} {:?}", filter.inner.age.0, filter.inner.name.into_inner())
+    }
+
+    async fn person_handler(filter: FromQueryFilter<PersonFilter>) -> String {
+        format!("{:?} {:?}", filter.inner.age.0, filter.inner.name.into_inner())
+    }
+
+    #[tokio::test]
+    async fn test_axum_query_is_decoded() {
+        let app = Router::new().route("/", get(person_handler));
+
+        for (uri, status, expected) in [
+            ("/?age%5Bgte%5D=18&name=John%20Doe", StatusCode::OK, r#"[Gte(18)] Some("John Doe")"#),
+            ("/?name=100%25+sure", StatusCode::OK, r#"[] Some("100% sure")"#),
+            // split before it is decoded, so an encoded `&` is part of the value
+            ("/?name=Tom%20%26%20Jerry", StatusCode::OK, r#"[] Some("Tom & Jerry")"#),
+            ("/?name=a%3Db&age=1", StatusCode::OK, r#"[Eq(1)] Some("a=b")"#),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status, "{uri}");
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_axum_form_is_decoded() {
+        let app = Router::new()
+            .route("/", axum::routing::post(person_form_handler))
+            .layer(axum::extract::DefaultBodyLimit::max(64));
+
+        for (body, status, expected) in [
+            // as a browser posts `age[gte]=18` and `name=John Doe`
+            ("age%5Bgte%5D=18&name=John+Doe", StatusCode::OK, r#"[Gte(18)] Some("John Doe")"#),
+            ("name=100%25+sure", StatusCode::OK, r#"[] Some("100% sure")"#),
+            ("name=50%", StatusCode::OK, r#"[] Some("50%")"#),
+            ("name=Tom+%26+Jerry", StatusCode::OK, r#"[] Some("Tom & Jerry")"#),
+            (
+                &*format!("name={}", "x".repeat(100)),
+                StatusCode::PAYLOAD_TOO_LARGE,
+                "invalid request body",
+            ),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(
+                    Request::builder()
+                        .method("POST")
+                        .uri("/")
+                        .header("content-type", "application/x-www-form-urlencoded")
+                        .body(Body::from(body.to_string()))
+                        .unwrap(),
+                )
+                .await
+                .unwrap();
+
+            assert_eq!(response.status(), status, "{body}");
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    /// The unified error type of an app, with its own response shape.
+    struct AppError {
+        code: &'static str,
+        message: String,
+    }
+
+    impl From<FilterParseError> for AppError {
+        fn from(err: FilterParseError) -> Self {
+            AppError {
+                code: "invalid_filter",
+                message: err.to_string(),
+            }
+        }
+    }
+
+    impl axum::response::IntoResponse for AppError {
+        fn into_response(self) -> axum::response::Response {
+            let body = format!(r#"{{"code":"{}","message":"{}"}}"#, self.code, self.message);
+            (StatusCode::UNPROCESSABLE_ENTITY, body).into_response()
+        }
+    }
+
+    type Custom<T> = filtrum::axum::WithRejection<T, AppError>;
+
+    async fn custom_handler(filter: Custom<FromQueryFilter<MyFilter>>) -> String {
+        format!("age: {:?}", filter.inner.age.as_ref())
+    }
+
+    async fn custom_form_handler(
+        filter: Custom<filtrum::axum::FilterForm<FromQueryFilter<MyFilter>>>,
+    ) -> String {
+        format!("age: {:?}", filter.into_inner().0.inner.age.into_inner())
+    }
+
+    #[tokio::test]
+    async fn test_axum_custom_rejection() {
+        let app = Router::new()
+            .route("/", get(custom_handler))
+            .route("/form", axum::routing::post(custom_form_handler));
+
+        for (request, status, expected) in [
+            (
+                Request::builder().uri("/?age=25").body(Body::empty()),
+                StatusCode::OK,
+                "age: Some(25)",
+            ),
+            (
+                Request::builder().uri("/?age=x").body(Body::empty()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                r#"{"code":"invalid_filter","message":"invalid filter value"}"#,
+            ),
+            (
+                Request::builder()
+                    .method("POST")
+                    .uri("/form")
+                    .header("content-type", "application/json")
+                    .body(Body::from("{}")),
+                StatusCode::UNPROCESSABLE_ENTITY,
+                r#"{"code":"invalid_filter","message":"expected an `application/x-www-form-urlencoded` body"}"#,
+            ),
+            (
+                Request::builder()
+                    .method("POST")
+                    .uri("/form")
+                    .header("content-type", "application/x-www-form-urlencoded")
+                    .body(Body::from("age=30")),
+                StatusCode::OK,
+                "age: Some(30)",
+            ),
+        ] {
+            let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+            assert_eq!(response.status(), status);
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_status_code_from_error() {
+        let cases = [
+            (FilterParseError::FilterStructure, StatusCode::BAD_REQUEST),
+            (FilterParseError::Value(None), StatusCode::BAD_REQUEST),
+            (FilterParseError::value("bad"), StatusCode::BAD_REQUEST),
+            (FilterParseError::UnknownFilter, StatusCode::BAD_REQUEST),
+            (FilterParseError::Duplicate, StatusCode::BAD_REQUEST),
+            (FilterParseError::UnknownField("x".to_string()), StatusCode::BAD_REQUEST),
+            (FilterParseError::InvalidCursor, StatusCode::BAD_REQUEST),
+            (FilterParseError::SkipTooLarge(100), StatusCode::BAD_REQUEST),
+            (FilterParseError::ContentType, StatusCode::UNSUPPORTED_MEDIA_TYPE),
+            (FilterParseError::Body("bad".into()), StatusCode::BAD_REQUEST),
+        ];
+
+        for (err, status) in cases {
+            assert_eq!(StatusCode::from(&err), status, "{err}");
+        }
+    }
+
+    #[cfg(feature = "sqlite")]
+    impl filtrum::sqlx::SqlxFilter<sqlx::Sqlite> for MyFilter {
+        fn apply<'a>(&self, qb: &mut sqlx::QueryBuilder<'a, sqlx::Sqlite>) {
+            self.age.apply(qb);
+        }
+    }
+
+    async fn paged_handler(filter: FromQueryFilter<MyFilter>) -> String {
+        format!(
+            "age: {:?}, limit: {:?}, skip: {:?}",
+            filter.inner.age.as_ref(),
+            filter.limit_value(),
+            filter.skip_value()
+        )
+    }
+
+    async fn raw_config_handler(
+        axum::Extension(config): axum::Extension<filtrum::axum::FilterConfig>,
+        query: axum::extract::RawQuery,
+    ) -> Result<String, filtrum::axum::FilterRejection> {
+        let filter = FromQueryFilter::<MyFilter>::from_raw_query(query, &config)
+            .map_err(filtrum::axum::FilterRejection)?;
+
+        Ok(paged_handler(filter).await)
+    }
+
+    #[tokio::test]
+    async fn test_axum_filter_config() {
+        use axum::Extension;
+        use filtrum::axum::FilterConfig;
+        use filtrum::options::{ParseOptions, ReservedParams};
+
+        let config = FilterConfig {
+            options: ParseOptions {
+                reserved: ReservedParams {
+                    limit: "size",
+                    skip: "offset",
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            default_limit: Some(20),
+            max_limit: Some(100),
+        };
+        let app = Router::new()
+            .route("/", get(paged_handler))
+            .route("/lenient", get(lenient_handler))
+            .route("/raw", get(raw_config_handler))
+            .layer(Extension(config));
+
+        for (uri, expected) in [
+            (
+                "/?age=25&size=500",
+                "age: Some(25), limit: Some(100), skip: None",
+            ),
+            (
+                "/?size=50&offset=10",
+                "age: None, limit: Some(50), skip: Some(10)",
+            ),
+            ("/", "age: None, limit: Some(20), skip: None"),
+            (
+                "/lenient?age=x&size=500",
+                r#"age: None, limit: Some(Limit(20)), raw: Some("age=x&size=500")"#,
+            ),
+            // a `RawQuery` is decoded and parsed with the config too
+            (
+                "/raw?age=2%35&size=500",
+                "age: Some(25), limit: Some(100), skip: None",
+            ),
+            ("/raw", "age: None, limit: Some(20), skip: None"),
+        ] {
+            let response = app
+                .clone()
+                .oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{uri}");
+
+            let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+                .await
+                .unwrap();
+            assert_eq!(&body_bytes[..], expected.as_bytes(), "{uri}");
+        }
+    }
+
+    #[cfg(feature = "derive")]
+    #[derive(filtrum::Filterable, Debug, Default)]
+    struct DerivedFilter {
+        age: filtrum::number_filter::NumberFilters<i32>,
+        name: filtrum::string_filter::StringFilters,
+    }
+
+    #[cfg(feature = "derive")]
+    async fn derived_handler(filter: FromQueryFilter<DerivedFilter>) -> String {
+        format!("{:?} {:?}", filter.inner.age.0, filter.inner.name.0)
+    }
+
+    #[cfg(feature = "derive")]
+    #[tokio::test]
+    async fn test_axum_filter_config_reaches_the_fields() {
+        use axum::Extension;
+        use filtrum::axum::FilterConfig;
+        use filtrum::options::{OperatorAliases, ParseOptions};
+
+        let config = FilterConfig {
+            options: ParseOptions {
+                aliases: OperatorAliases::new().with("$gte", "gte"),
+                explicit_eq_overrides_bare: true,
+                keep_empty_values: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let app = Router::new()
+            .route("/", get(derived_handler))
+            .layer(Extension(config));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/?age%5B%24gte%5D=18&age=1&age%5Beq%5D=2&name%5Beq%5D=")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(&body_bytes[..], br#"[Gte(18), Eq(2)] [Eq("")]"#);
+    }
+
+    #[cfg(feature = "sqlite")]
+    async fn page_handler(filter: FromQueryFilter<MyFilter>) -> String {
+        let queries = filter.page_queries::<sqlx::Sqlite>(
+            "SELECT * FROM users WHERE 1=1",
+            "SELECT COUNT(*) FROM users WHERE 1=1",
+        );
+
+        format!("{}\n{}", queries.data.sql(), queries.count.sql())
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_axum_page_queries() {
+        let app = Router::new().route("/", get(page_handler));
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/?age=25&order_by=-age&limit=10&skip=20")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body_bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(
+            std::str::from_utf8(&body_bytes).unwrap(),
+            "SELECT * FROM users WHERE 1=1 AND age = ? ORDER BY age DESC LIMIT ? OFFSET ?\n\
+             SELECT COUNT(*) FROM users WHERE 1=1 AND age = ?"
+        );
+    }
 }
@@ -7,7 +7,7 @@ mod tests {
     };
     use std::str::FromStr;
 
-    #[derive(Filterable, Debug)]
+    #[derive(Filterable, Debug, Default)]
     #[filtrum(table = "users")]
     struct UserFilter {
         #[filtrum(alias = "n")]
@@ -48,5 +48,547 @@ mod tests {
     fn test_filter_id_impl() {
         use filtrum::common::WithFilterId;
         assert_eq!(UserFilter::filter_id(), Some("users"));
+        assert_eq!(UserFilter::default_tiebreak(), None);
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(tiebreak = "id")]
+    struct TiebreakFilter {
+        age: NumberFilters<i32>,
+    }
+
+    #[test]
+    fn test_tiebreak_attribute() {
+        assert_eq!(TiebreakFilter::default_tiebreak(), Some("id"));
+
+        let filter =
+            filtrum::FromQueryFilter::<TiebreakFilter>::from_str("order_by[desc]=age").unwrap();
+        assert_eq!(filter.sort().0.len(), 2);
+        assert!(filter.inner.age.0.is_empty());
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "posts", order_by = "-created_at", tiebreak = "id")]
+    struct PostFilter {
+        title: StringFilters,
+        created_at: NumberFilters<i64>,
+    }
+
+    #[test]
+    fn test_default_order_by_attribute() {
+        use filtrum::{FilterId, FromQueryFilter, OrderBy};
+
+        let column = |x: &str| FilterId::WithPrefix("posts".to_string(), x.to_string());
+
+        assert_eq!(
+            PostFilter::default_order_by(),
+            Some(OrderBy::Desc("created_at".to_string().into()))
+        );
+
+        let filter = FromQueryFilter::<PostFilter>::from_str("title[sw]=A").unwrap();
+        assert_eq!(filter.order_by, None);
+        assert_eq!(
+            filter.sort().0,
+            vec![
+                OrderBy::Desc(column("created_at")),
+                OrderBy::Asc(column("id"))
+            ]
+        );
+
+        let filter = FromQueryFilter::<PostFilter>::from_str("order_by=title").unwrap();
+        assert_eq!(
+            filter.sort().0,
+            vec![OrderBy::Asc(column("title")), OrderBy::Asc(column("id"))]
+        );
+
+        assert_eq!(UserFilter::default_order_by(), None);
+    }
+
+    #[test]
+    fn test_merge_query() {
+        use filtrum::{FromQueryFilter, NumberFilter, OrderBy};
+
+        // a saved search, with live parameters overlaid on top
+        let mut filter =
+            FromQueryFilter::<UserFilter>::from_str("name[sw]=Al&age[gte]=18&age[lt]=65&limit=10")
+                .unwrap();
+        filter
+            .merge_query("age[gte]=21&is_active=true&order_by=-age&limit=5")
+            .unwrap();
+
+        assert_eq!(filter.inner.name.0.len(), 1);
+        assert_eq!(
+            filter.inner.age.0,
+            vec![NumberFilter::Lt(65), NumberFilter::Gte(21)]
+        );
+        assert_eq!(filter.inner.is_active.as_ref(), Some(&true));
+        assert_eq!(filter.limit.map(|x| x.0), Some(5));
+        assert!(matches!(filter.order_by, Some(OrderBy::Desc(_))));
+
+        // the overlay is validated like any other query
+        assert!(filter.merge_query("order_by=ignored").is_err());
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    struct ActiveFilter {
+        #[filtrum(default_value = "true")]
+        is_active: EqualFilter<bool>,
+        #[filtrum(default_value = "18")]
+        age: Option<NumberFilters<i32>>,
+        name: StringFilters,
+    }
+
+    #[test]
+    fn test_default_value() {
+        use filtrum::NumberFilter;
+
+        // left out: the defaults apply
+        let filter = ActiveFilter::from_str("name[sw]=Al").unwrap();
+        assert_eq!(filter.is_active.as_ref(), Some(&true));
+        assert_eq!(filter.age.map(|x| x.0), Some(vec![NumberFilter::Eq(18)]));
+        assert_eq!(filter.name.0.len(), 1);
+
+        // overridden by the client
+        let filter = ActiveFilter::from_str("is_active=false&age[gte]=21").unwrap();
+        assert_eq!(filter.is_active.as_ref(), Some(&false));
+        assert_eq!(filter.age.map(|x| x.0), Some(vec![NumberFilter::Gte(21)]));
+
+        // cleared by the client, which is not the same as left out
+        let filter = ActiveFilter::from_str("is_active=&age=").unwrap();
+        assert_eq!(filter.is_active.as_ref(), None);
+        assert!(filter.age.is_none());
+
+        assert_eq!(
+            ActiveFilter::default_values(),
+            [("age", "18"), ("is_active", "true")]
+        );
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    struct TitleFilter {
+        #[filtrum(default_value = "Tom & Jerry")]
+        title: StringFilters,
+        #[filtrum(default_value = "a=b[c]")]
+        tag: EqualFilter<String>,
+    }
+
+    #[test]
+    fn test_default_value_is_taken_as_it_is() {
+        use filtrum::StringFilter;
+
+        // an `&`, `=` or bracket in the default is part of the value
+        let filter = TitleFilter::from_str("").unwrap();
+        assert_eq!(filter.title.0, vec![StringFilter::Eq("Tom & Jerry".to_string())]);
+        assert_eq!(filter.tag.as_ref().map(String::as_str), Some("a=b[c]"));
+
+        let filter = TitleFilter::from_str("title[sw]=Al").unwrap();
+        assert_eq!(filter.title.0, vec![StringFilter::StartsWith("Al".to_string())]);
+        assert_eq!(filter.tag.as_ref().map(String::as_str), Some("a=b[c]"));
+    }
+
+    #[test]
+    fn test_merge_query_keeps_fields_over_defaults() {
+        use filtrum::{FromQueryFilter, NumberFilter};
+
+        // the overlay leaves the defaulted fields out, so the saved ones stay
+        let mut filter =
+            FromQueryFilter::<ActiveFilter>::from_str("is_active=false&age[gte]=21").unwrap();
+        filter.merge_query("limit=5").unwrap();
+
+        assert_eq!(filter.inner.is_active.as_ref(), Some(&false));
+        assert_eq!(
+            filter.inner.age.as_ref().map(|x| x.0.clone()),
+            Some(vec![NumberFilter::Gte(21)])
+        );
+        assert_eq!(filter.limit.map(|x| x.0), Some(5));
+
+        // and set in the overlay, they still win
+        filter.merge_query("is_active=true").unwrap();
+        assert_eq!(filter.inner.is_active.as_ref(), Some(&true));
+    }
+
+    mod private {
+        use filtrum::{Filterable, number_filter::NumberFilters, string_filter::StringFilters};
+
+        #[derive(Filterable, Debug, Default)]
+        #[filtrum(getters)]
+        pub struct PrivateFilter {
+            name: StringFilters,
+            age: NumberFilters<i32>,
+            #[filtrum(skip)]
+            #[allow(dead_code)]
+            secret: String,
+        }
+    }
+
+    #[test]
+    fn test_getters() {
+        use filtrum::{number_filter::NumberFilter, string_filter::StringFilter};
+
+        let filter = private::PrivateFilter::from_str("name[sw]=Al&age[lt]=30").unwrap();
+
+        assert_eq!(
+            filter.name().0,
+            vec![StringFilter::StartsWith("Al".to_string())]
+        );
+        assert_eq!(filter.age().0, vec![NumberFilter::Lt(30)]);
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    struct OptionalFilter {
+        name: Option<StringFilters>,
+        #[filtrum(table = "users", alias = "years")]
+        age: Option<NumberFilters<i32>>,
+        is_active: Option<EqualFilter<bool>>,
+    }
+
+    #[test]
+    fn test_optional_fields() {
+        let filter = OptionalFilter::from_str("name[sw]=Al&is_active=true").unwrap();
+        assert_eq!(filter.name.map(|x| x.0.len()), Some(1));
+        assert!(filter.age.is_none());
+        assert_eq!(filter.is_active.and_then(|x| x.into_inner()), Some(true));
+
+        let filter = OptionalFilter::from_str("age[gte]=18").unwrap();
+        assert!(filter.name.is_none());
+        assert_eq!(filter.age.map(|x| x.0.len()), Some(1));
+        assert!(filter.is_active.is_none());
+    }
+
+    #[test]
+    fn test_sortable_columns() {
+        use filtrum::{FilterParseError, FromQueryFilter};
+
+        assert_eq!(
+            UserFilter::sortable_columns(),
+            Some(&["name", "age", "is_active"][..])
+        );
+
+        let filter = FromQueryFilter::<UserFilter>::from_str("order_by[desc]=age").unwrap();
+        assert_eq!(
+            filter.order_by.and_then(|x| x.id().map(|x| x.qualified())),
+            Some("users.age".to_string())
+        );
+
+        // skipped fields and unknown columns can't be sorted on
+        for query in ["order_by[asc]=password", "order_by=ignored"] {
+            let err = FromQueryFilter::<UserFilter>::from_str(query).unwrap_err();
+            assert!(matches!(err, FilterParseError::UnknownField(_)), "{query}");
+        }
+
+        let filter = FromQueryFilter::<UserFilter>::from_str("order_by[rand]=1").unwrap();
+        assert_eq!(filter.order_by, Some(filtrum::OrderBy::Random));
+
+        // an aliased field is sorted on by its name, on its aliased column
+        let filter = FromQueryFilter::<OptionalFilter>::from_str("order_by=age").unwrap();
+        assert_eq!(
+            filter.order_by.and_then(|x| x.id().map(|x| x.qualified())),
+            Some("users.years".to_string())
+        );
+
+        let err = FromQueryFilter::<OptionalFilter>::from_str("order_by=years").unwrap_err();
+        assert!(matches!(err, FilterParseError::UnknownField(_)));
+    }
+
+    #[test]
+    fn test_to_filter_map() {
+        use filtrum::{FilterValue, FromQueryFilter, Op};
+
+        let query = "name[sw]=Al&name[!eq]=Alan&age[gte]=18&age[between]=1,2&limit=5";
+        let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+        let map = filter.to_filter_map();
+
+        // fields without conditions are left out, and so are `limit` and friends
+        assert_eq!(map.len(), 2);
+        assert_eq!(
+            map["name"],
+            vec![
+                (Op::StartsWith, FilterValue::String("Al".to_string())),
+                (
+                    Op::Not(Box::new(Op::Eq)),
+                    FilterValue::String("Alan".to_string())
+                )
+            ]
+        );
+        assert_eq!(
+            map["age"],
+            vec![
+                (Op::Gte, FilterValue::Int(18)),
+                (
+                    Op::Between,
+                    FilterValue::List(vec![FilterValue::Int(1), FilterValue::Int(2)])
+                )
+            ]
+        );
+
+        let filter = FromQueryFilter::<UserFilter>::from_str("is_active=false").unwrap();
+        assert_eq!(
+            filter.to_filter_map()["is_active"],
+            vec![(Op::Eq, FilterValue::Bool(false))]
+        );
+    }
+
+    #[test]
+    fn test_iter_conditions() {
+        use filtrum::{FilterValue, FromQueryFilter, Op};
+
+        let query = "age[lt]=65&name[sw]=Al&is_active=true&age[gte]=18&order_by=age&limit=5";
+        let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+
+        // in field order, then query order within a field, on the aliased columns
+        let column = |x: &str| x.to_string();
+        assert_eq!(
+            filter.iter_conditions().collect::<Vec<_>>(),
+            vec![
+                (
+                    column("users.n"),
+                    Op::StartsWith,
+                    FilterValue::String("Al".to_string())
+                ),
+                (column("users.age"), Op::Lt, FilterValue::Int(65)),
+                (column("users.age"), Op::Gte, FilterValue::Int(18)),
+                (column("users.is_active"), Op::Eq, FilterValue::Bool(true)),
+            ]
+        );
+
+        let filter = FromQueryFilter::<UserFilter>::from_str("limit=5").unwrap();
+        assert_eq!(filter.iter_conditions().count(), 0);
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    struct AccountFilter {
+        #[filtrum(allowed_ops = "eq, starts_with, !eq")]
+        username: StringFilters,
+        #[filtrum(allowed_ops = "")]
+        password: EqualFilter<String>,
+        age: NumberFilters<i32>,
+        // every key the derive accepts, which must all parse into an operator of its own
+        #[filtrum(
+            allowed_ops = "eq, ne, nseq, gt, lt, gte, lte, between, range, like, l, not_like, \
+                           nl, ilike, starts_with, sw, ends_with, ew, contains, c, regex, \
+                           iregex, similar, sim, contains_unaccent, len_gt, len_lt, len_eq, \
+                           in, not_in, nin, array_contains, fts, asc, desc, rand, !sw"
+        )]
+        email: StringFilters,
+    }
+
+    #[test]
+    fn test_allowed_ops_attribute() {
+        use filtrum::{FilterParseError, FromQueryFilter, Op};
+
+        assert_eq!(
+            AccountFilter::allowed_ops("username"),
+            Some(&[Op::Eq, Op::StartsWith, Op::Not(Box::new(Op::Eq))][..])
+        );
+        assert_eq!(AccountFilter::allowed_ops("password"), Some(&[][..]));
+        assert_eq!(AccountFilter::allowed_ops("age"), None);
+
+        fn is_known(op: &Op) -> bool {
+            match op {
+                Op::Not(op) => is_known(op),
+                op => !matches!(op, Op::Other(_)),
+            }
+        }
+        let ops = AccountFilter::allowed_ops("email").unwrap();
+        assert_eq!(ops.len(), 37);
+        assert!(ops.iter().all(is_known), "{ops:?}");
+
+        let query = "username[sw]=al&username[!eq]=alice&username=bob&age[gt]=18";
+        let filter = AccountFilter::from_str(query).unwrap();
+        assert_eq!(filter.username.0.len(), 3);
+
+        for query in ["username[like]=al", "username[!sw]=al", "password=x"] {
+            assert!(
+                matches!(
+                    AccountFilter::from_str(query),
+                    Err(FilterParseError::UnknownFilter)
+                ),
+                "{query}"
+            );
+            assert!(
+                matches!(
+                    FromQueryFilter::<AccountFilter>::from_str(query),
+                    Err(FilterParseError::UnknownFilter)
+                ),
+                "{query}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_options_reach_the_fields() {
+        use filtrum::{
+            FilterParseError, FromQueryFilter, NumberFilter, OperatorAliases, ParseOptions,
+        };
+
+        let options = ParseOptions {
+            aliases: OperatorAliases::new()
+                .with("$gte", "gte")
+                .with("$sw", "starts_with"),
+            ..Default::default()
+        };
+
+        let mut filter =
+            FromQueryFilter::<UserFilter>::from_str_with("age[$gte]=18", &options).unwrap();
+        assert_eq!(filter.inner.age.0, vec![NumberFilter::Gte(18)]);
+        assert!(matches!(
+            FromQueryFilter::<UserFilter>::from_str("age[$gte]=18"),
+            Err(FilterParseError::UnknownFilter)
+        ));
+
+        filter.merge_query_with("age[$gte]=21", &options).unwrap();
+        assert_eq!(filter.inner.age.0, vec![NumberFilter::Gte(21)]);
+
+        // an alias is checked against `allowed_ops` once resolved
+        let filter =
+            FromQueryFilter::<AccountFilter>::from_str_with("username[$sw]=al", &options).unwrap();
+        assert_eq!(filter.inner.username.0.len(), 1);
+    }
+
+    #[test]
+    fn test_explicit_eq_overrides_bare_through_the_derive() {
+        use filtrum::{FromQueryFilter, NumberFilter, ParseOptions};
+
+        let options = ParseOptions {
+            explicit_eq_overrides_bare: true,
+            ..Default::default()
+        };
+
+        let query = "age=1&age[eq]=2&age[gte]=0";
+        let filter = FromQueryFilter::<UserFilter>::from_str_with(query, &options).unwrap();
+        assert_eq!(
+            filter.inner.age.0,
+            vec![NumberFilter::Eq(2), NumberFilter::Gte(0)]
+        );
+
+        let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+        assert_eq!(
+            filter.inner.age.0,
+            vec![
+                NumberFilter::Eq(1),
+                NumberFilter::Eq(2),
+                NumberFilter::Gte(0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_keep_empty_values_through_the_derive() {
+        use filtrum::{FromQueryFilter, ParseOptions, StringFilter};
+
+        let options = ParseOptions {
+            keep_empty_values: true,
+            ..Default::default()
+        };
+
+        let filter = FromQueryFilter::<UserFilter>::from_str_with("name[eq]=", &options).unwrap();
+        assert_eq!(filter.inner.name.0, vec![StringFilter::Eq(String::new())]);
+
+        let filter = FromQueryFilter::<UserFilter>::from_str("name[eq]=").unwrap();
+        assert!(filter.inner.name.0.is_empty());
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "articles")]
+    struct ArticleFilter {
+        parent_id: filtrum::NullableFilter<i32>,
+        #[filtrum(search(title, body))]
+        q: filtrum::SearchFilter,
+        attr: filtrum::DynamicFilters,
+        tag: Option<filtrum::InFilters<String>>,
+    }
+
+    #[test]
+    fn test_to_filter_map_field_types() {
+        use filtrum::{FilterValue, FromQueryFilter, Op};
+
+        let query = "parent_id=!null&q=rust&attr.color=red&tag[in]=a,b";
+        let map = FromQueryFilter::<ArticleFilter>::from_str(query)
+            .unwrap()
+            .to_filter_map();
+
+        assert_eq!(map["parent_id"], vec![(Op::Ne, FilterValue::Null)]);
+        assert_eq!(
+            map["q"],
+            vec![(Op::Eq, FilterValue::String("rust".to_string()))]
+        );
+        assert_eq!(
+            map["attr"],
+            vec![(Op::Eq, FilterValue::String("red".to_string()))]
+        );
+        assert_eq!(
+            map["tag"],
+            vec![(
+                Op::In,
+                FilterValue::List(vec![
+                    FilterValue::String("a".to_string()),
+                    FilterValue::String("b".to_string())
+                ])
+            )]
+        );
+    }
+
+    #[test]
+    fn test_iter_conditions_field_types() {
+        use filtrum::{FilterValue, FromQueryFilter, Op};
+
+        let query = "tag[in]=a&attr.color=red&q=rust&parent_id=null&attr.size[ne]=XL";
+        let filter = FromQueryFilter::<ArticleFilter>::from_str(query).unwrap();
+        let value = |x: &str| FilterValue::String(x.to_string());
+
+        // the search term on each of its columns, and each attribute on its own
+        assert_eq!(
+            filter.iter_conditions().collect::<Vec<_>>(),
+            vec![
+                ("articles.parent_id".to_string(), Op::Eq, FilterValue::Null),
+                ("articles.title".to_string(), Op::Contains, value("rust")),
+                ("articles.body".to_string(), Op::Contains, value("rust")),
+                ("articles.attr.color".to_string(), Op::Eq, value("red")),
+                ("articles.attr.size".to_string(), Op::Ne, value("XL")),
+                (
+                    "articles.tag".to_string(),
+                    Op::In,
+                    FilterValue::List(vec![value("a")])
+                ),
+            ]
+        );
+
+        let filter = FromQueryFilter::<OptionalFilter>::from_str("age[gte]=18").unwrap();
+        assert_eq!(
+            filter.iter_conditions().collect::<Vec<_>>(),
+            vec![("users.years".to_string(), Op::Gte, FilterValue::Int(18))]
+        );
+    }
+
+    type Ages = NumberFilters<i32>;
+    type Names = StringFilters;
+
+    #[derive(Filterable, Debug, Default)]
+    struct AliasedFilter {
+        name: filtrum::StringFilters,
+        age: filtrum::number_filter::NumberFilters<i32>,
+        #[filtrum(kind = "number")]
+        min_age: Ages,
+        #[filtrum(kind = "String")]
+        nickname: Option<Names>,
+    }
+
+    #[test]
+    fn test_filter_kind() {
+        use filtrum::{number_filter::NumberFilter, string_filter::StringFilter};
+
+        let query = "name[sw]=Al&age[gte]=18&min_age[lt]=65&nickname[ne]=Bo";
+        let filter = AliasedFilter::from_str(query).unwrap();
+
+        assert_eq!(
+            filter.name.0,
+            vec![StringFilter::StartsWith("Al".to_string())]
+        );
+        assert_eq!(filter.age.0, vec![NumberFilter::Gte(18)]);
+        assert_eq!(filter.min_age.0, vec![NumberFilter::Lt(65)]);
+        assert_eq!(
+            filter.nickname.unwrap().0,
+            vec![StringFilter::Ne("Bo".to_string())]
+        );
     }
 }
@@ -0,0 +1,199 @@
+#![cfg(feature = "graphql")]
+
+use async_graphql::value;
+use filtrum::{graphql::to_query, FilterParseError};
+
+#[test]
+fn test_graphql_to_query() {
+    let input = value!({
+        "name": { "sw": "Al", "ne": "Alan" },
+        "age": { "between": [18, 65] },
+        "active": true,
+        "parent_id": null,
+        "order_by": "-age",
+        "limit": 10,
+    });
+    assert_eq!(
+        to_query(&input).unwrap(),
+        "name[sw]=Al&name[ne]=Alan&age[between]=18,65&active=true&order_by=-age&limit=10"
+    );
+
+    // only `nseq` compares with `NULL`, any other `null` is left out
+    let input = value!({ "age": { "nseq": null, "gte": null }, "name": { "eq": null } });
+    assert_eq!(to_query(&input).unwrap(), "age[nseq]=null");
+
+    let input = value!({ "name": { "in": ["a,b", "c"] } });
+    assert_eq!(to_query(&input).unwrap(), r"name[in]=a\,b,c");
+    assert!(matches!(
+        to_query(&value!({ "name": { "in": ["a", null] } })),
+        Err(FilterParseError::Value(Some(_)))
+    ));
+
+    assert!(matches!(
+        to_query(&value!([1, 2])),
+        Err(FilterParseError::FilterStructure)
+    ));
+    assert!(matches!(
+        to_query(&value!({ "age": { "gte": { "lt": 1 } } })),
+        Err(FilterParseError::FilterStructure)
+    ));
+    assert!(matches!(
+        to_query(&value!({ "name": "a&limit=1" })),
+        Err(FilterParseError::Value(Some(_)))
+    ));
+}
+
+#[cfg(feature = "derive")]
+mod derived {
+    use super::*;
+    use async_graphql::{EmptyMutation, EmptySubscription, InputType, Object, Schema};
+    use filtrum::{
+        equal_filter::EqualFilter,
+        nullable_filter::{Nullable, NullableFilter},
+        number_filter::{NumberFilter, NumberFilters},
+        string_filter::{StringFilter, StringFilters},
+        FilterId, Filterable, FromQueryFilter, InFilters, Limit, OrderBy,
+    };
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "users")]
+    struct UserFilter {
+        name: StringFilters,
+        age: NumberFilters<i32>,
+        active: EqualFilter<bool>,
+    }
+
+    #[test]
+    fn test_graphql_same_as_query_string() {
+        let input = value!({
+            "name": { "sw": "Al" },
+            "age": { "gte": 18, "lte": 65 },
+            "active": true,
+            "order_by": "-age",
+            "limit": 10,
+        });
+
+        let from_graphql = FromQueryFilter::<UserFilter>::from_graphql(&input).unwrap();
+        let from_query = FromQueryFilter::<UserFilter>::from_str(
+            "name[sw]=Al&age[gte]=18&age[lte]=65&active=true&order_by=-age&limit=10",
+        )
+        .unwrap();
+
+        assert_eq!(from_graphql.inner.name.0, from_query.inner.name.0);
+        assert_eq!(from_graphql.inner.age.0, from_query.inner.age.0);
+        assert_eq!(from_graphql.inner.active.as_ref(), Some(&true));
+        assert_eq!(from_graphql.order_by, from_query.order_by);
+        assert_eq!(from_graphql.limit, Some(Limit(10)));
+        assert_eq!(from_graphql.raw, from_query.raw);
+
+        assert!(matches!(
+            FromQueryFilter::<UserFilter>::from_graphql(&value!({ "age": { "gte": "x" } })),
+            Err(FilterParseError::Value(_))
+        ));
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    struct PostFilter {
+        parent_id: NullableFilter<i32>,
+        tag: InFilters<String>,
+    }
+
+    #[test]
+    fn test_graphql_null_and_lists() {
+        // the `eq` of a `NullableFilter` takes a `null`
+        let filter =
+            FromQueryFilter::<PostFilter>::from_graphql(&value!({ "parent_id": { "eq": null } }))
+                .unwrap();
+        assert_eq!(filter.inner.parent_id.as_ref(), Some(&Nullable::Null));
+
+        let filter =
+            FromQueryFilter::<PostFilter>::from_graphql(&value!({ "parent_id": null })).unwrap();
+        assert_eq!(filter.inner.parent_id.as_ref(), Some(&Nullable::Null));
+
+        // a comma inside an item stays part of it
+        let filter =
+            FromQueryFilter::<PostFilter>::from_graphql(&value!({ "tag": { "in": ["a,b", "c"] } }))
+                .unwrap();
+        assert_eq!(filter.inner.tag.0[0].values(), ["a,b", "c"]);
+    }
+
+    #[test]
+    fn test_graphql_to_value() {
+        let mut filter = FromQueryFilter::<UserFilter>::from_str(
+            "name[sw]=Al&name[!eq]=Bob&age[between]=18,65&active=true&order_by=-age&limit=10",
+        )
+        .unwrap();
+
+        // the current state is written, not the query it was parsed from
+        filter.inner.age.0.push(NumberFilter::Lt(30));
+        let filter = filter.with_skip(20);
+
+        assert_eq!(
+            filter.to_value(),
+            value!({
+                "name": { "starts_with": "Al" },
+                "age": { "between": [18, 65], "lt": 30 },
+                "active": { "eq": true },
+                "order_by": "-age",
+                "limit": 10,
+                "skip": 20,
+            })
+        );
+    }
+
+    struct Query;
+
+    #[Object]
+    impl Query {
+        async fn users(&self, filter: FromQueryFilter<UserFilter>) -> String {
+            let name = &filter.inner.name.0;
+            let age = &filter.inner.age.0;
+            assert_eq!(name, &[StringFilter::StartsWith("Al".to_string())]);
+            assert_eq!(age, &[NumberFilter::Gte(18)]);
+            assert_eq!(
+                filter.order_by,
+                Some(OrderBy::Desc(FilterId::WithPrefix(
+                    "users".to_string(),
+                    "age".to_string()
+                )))
+            );
+
+            filter.raw.unwrap_or_default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_graphql_argument() {
+        let schema = Schema::new(Query, EmptyMutation, EmptySubscription);
+        let sdl = schema.sdl();
+        assert!(sdl.contains("input UserFilterInput"), "{sdl}");
+        assert!(sdl.contains("age: UserFilterAgeInput"), "{sdl}");
+        assert!(sdl.contains("order_by: String"), "{sdl}");
+        assert!(sdl.contains("input UserFilterAgeInput"), "{sdl}");
+        assert!(sdl.contains("gte: Int"), "{sdl}");
+        assert!(sdl.contains("between: [Int!]"), "{sdl}");
+        assert!(sdl.contains("starts_with: String"), "{sdl}");
+
+        let res = schema
+            .execute(
+                r#"{ users(filter: { name: { starts_with: "Al" }, age: { gte: 18 }, order_by: "-age" }) }"#,
+            )
+            .await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data,
+            value!({ "users": "name[starts_with]=Al&age[gte]=18&order_by=-age" })
+        );
+
+        // the operators are part of the schema, so an unknown one fails validation
+        let res = schema
+            .execute(r#"{ users(filter: { age: { like: "1" } }) }"#)
+            .await;
+        assert_eq!(res.errors.len(), 1);
+
+        let res = schema
+            .execute(r#"{ users(filter: { age: { gte: "x" } }) }"#)
+            .await;
+        assert_eq!(res.errors.len(), 1);
+    }
+}
@@ -84,3 +84,14 @@ fn test_prefixed_filters() {
     assert_eq!(name_filters.len(), 1);
     assert!(name_filters.contains(&StringFilter::Eq("Bob".to_string())));
 }
+
+#[test]
+fn test_bare_order_by_is_ascending() {
+    let filter = FromQueryFilter::<UserFilter>::from_str("order_by=age&limit=5").unwrap();
+
+    assert_eq!(
+        filter.order_by,
+        Some(filtrum::order_by::OrderBy::Asc("age".to_string().into()))
+    );
+    assert_eq!(filter.limit.map(|l| l.0), Some(5));
+}
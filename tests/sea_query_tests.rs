@@ -0,0 +1,277 @@
+#![cfg(feature = "sea-query")]
+
+use filtrum::{
+    array_filter::ArrayFilters,
+    dynamic_filter::DynamicFilters,
+    equal_filter::EqualFilter,
+    filter_id::FilterId,
+    full_text_filter::FullTextFilter,
+    in_filter::InFilters,
+    nullable_filter::NullableFilter,
+    number_filter::NumberFilters,
+    sea_query::{any_of, SeaQueryFilter},
+    search_filter::SearchFilter,
+    string_filter::StringFilters,
+};
+use sea_query::{
+    Alias, Asterisk, Condition, MysqlQueryBuilder, PostgresQueryBuilder, Query, SqliteQueryBuilder,
+};
+
+fn where_sql(cond: Condition) -> String {
+    Query::select()
+        .column(Asterisk)
+        .from(Alias::new("users"))
+        .cond_where(cond)
+        .to_string(PostgresQueryBuilder)
+}
+
+fn string_sql(query: &str) -> String {
+    where_sql(
+        StringFilters::<String>::from_str("name", query)
+            .unwrap()
+            .condition(),
+    )
+}
+
+#[test]
+fn test_sea_query_number() {
+    let age =
+        NumberFilters::<i32>::from_str("age", "age[eq]=1&age[ne]=2&age[gt]=3&age[lt]=4").unwrap();
+    assert_eq!(
+        where_sql(age.condition()),
+        r#"SELECT * FROM "users" WHERE "age" = 1 AND "age" <> 2 AND "age" > 3 AND "age" < 4"#
+    );
+
+    let age =
+        NumberFilters::<i32>::from_str("age", "age[gte]=5&age[lte]=6&age[between]=7,8").unwrap();
+    assert_eq!(
+        where_sql(age.condition()),
+        r#"SELECT * FROM "users" WHERE "age" >= 5 AND "age" <= 6 AND ("age" BETWEEN 7 AND 8)"#
+    );
+
+    let age = NumberFilters::<i32>::from_str("age", "age[range]=(1,9]").unwrap();
+    assert_eq!(
+        where_sql(age.condition()),
+        r#"SELECT * FROM "users" WHERE "age" > 1 AND "age" <= 9"#
+    );
+
+    let age = NumberFilters::<i32>::from_str("age", "age[nseq]=1&age[nseq]=null").unwrap();
+    assert_eq!(
+        where_sql(age.condition()),
+        r#"SELECT * FROM "users" WHERE ("age" IS NOT DISTINCT FROM 1) AND "age" IS NULL"#
+    );
+}
+
+#[test]
+fn test_sea_query_string() {
+    assert_eq!(
+        string_sql("name[eq]=a&name[ne]=b&name[like]=c%25&name[nl]=d"),
+        r#"SELECT * FROM "users" WHERE "name" = 'a' AND "name" <> 'b' AND "name" LIKE 'c%25' AND "name" NOT LIKE 'd'"#
+    );
+    assert_eq!(
+        string_sql("name[ilike]=Al%&name[sw]=a_&name[ew]=b&name[c]=c"),
+        r#"SELECT * FROM "users" WHERE LOWER("name") LIKE LOWER('Al%') AND "name" LIKE E'a\\_%' ESCAPE E'\\' AND "name" LIKE '%b' ESCAPE E'\\' AND "name" LIKE '%c%' ESCAPE E'\\'"#
+    );
+    // `similar` only parses with the `postgres` feature, the builder always has it
+    let name = StringFilters::<String>::from_str("name", "name[regex]=^A&name[iregex]=b$")
+        .unwrap()
+        .similar("c")
+        .contains_unaccent("e");
+    assert_eq!(
+        where_sql(name.condition()),
+        r#"SELECT * FROM "users" WHERE ("name" ~ '^A') AND ("name" ~* 'b$') AND "name" % 'c' AND (unaccent("name") ILIKE unaccent('%e%') ESCAPE '\')"#
+    );
+    assert_eq!(
+        string_sql("name[between]=A,M&name[len_gt]=0&name[len_lt]=9&name[len_eq]=5"),
+        r#"SELECT * FROM "users" WHERE ("name" BETWEEN 'A' AND 'M') AND CHAR_LENGTH("name") > 0 AND CHAR_LENGTH("name") < 9 AND CHAR_LENGTH("name") = 5"#
+    );
+    assert_eq!(
+        string_sql("name[!contains]=spam"),
+        r#"SELECT * FROM "users" WHERE NOT "name" LIKE '%spam%' ESCAPE E'\\'"#
+    );
+}
+
+#[test]
+fn test_sea_query_equal_nullable_in() {
+    let active = EqualFilter::<bool>::from_str("active", "active=true").unwrap();
+    assert_eq!(
+        where_sql(active.condition()),
+        r#"SELECT * FROM "users" WHERE "active" = TRUE"#
+    );
+    let role = EqualFilter::<String>::from_str("role", "role[in]=admin,editor").unwrap();
+    assert_eq!(
+        where_sql(role.condition()),
+        r#"SELECT * FROM "users" WHERE "role" IN ('admin', 'editor')"#
+    );
+
+    let parent = NullableFilter::<i32>::from_str("parent_id", "parent_id=null").unwrap();
+    assert_eq!(
+        where_sql(parent.condition()),
+        r#"SELECT * FROM "users" WHERE "parent_id" IS NULL"#
+    );
+    let parent = NullableFilter::<i32>::from_str("parent_id", "parent_id=!null").unwrap();
+    assert_eq!(
+        where_sql(parent.condition()),
+        r#"SELECT * FROM "users" WHERE "parent_id" IS NOT NULL"#
+    );
+
+    let id = InFilters::<i32>::from_str("id", "id[in]=1,2&id[nin]=3").unwrap();
+    assert_eq!(
+        where_sql(id.condition()),
+        r#"SELECT * FROM "users" WHERE "id" IN (1, 2) AND "id" NOT IN (3)"#
+    );
+}
+
+#[test]
+fn test_sea_query_empty_filters() {
+    let age = NumberFilters::<i32>::from_str("age", "name=x").unwrap();
+    let active = EqualFilter::<bool>::from_str("active", "").unwrap();
+    assert!(age.condition().is_empty());
+    assert!(active.condition().is_empty());
+    assert!(any_of(&[&age, &active]).is_empty());
+}
+
+#[test]
+fn test_sea_query_search() {
+    let q = SearchFilter::from_str("q", "q=Al_")
+        .unwrap()
+        .on_columns(&["name", "email"]);
+    assert_eq!(
+        where_sql(q.condition()),
+        r#"SELECT * FROM "users" WHERE LOWER("name") LIKE E'%al\\_%' ESCAPE E'\\' OR LOWER("email") LIKE E'%al\\_%' ESCAPE E'\\'"#
+    );
+}
+
+#[test]
+fn test_sea_query_dynamic_array() {
+    let attr =
+        DynamicFilters::<String>::from_str("attr", "attr.color=red&attr.size[sw]=x").unwrap();
+    assert_eq!(
+        where_sql(attr.condition()),
+        r#"SELECT * FROM "users" WHERE ("attr" ->> 'color') = 'red' AND ("attr" ->> 'size') LIKE 'x%' ESCAPE E'\\'"#
+    );
+
+    let tags = ArrayFilters::<String>::from_str("tags", "tags[contains]=a,b").unwrap();
+    assert_eq!(
+        where_sql(tags.condition()),
+        r#"SELECT * FROM "users" WHERE "tags" @> (ARRAY[ 'a', 'b' ])"#
+    );
+
+    let missing: Option<ArrayFilters<String>> = None;
+    assert!(missing.condition().is_empty());
+}
+
+#[test]
+fn test_sea_query_full_text_prefix() {
+    let id = FilterId::WithPrefix("posts".to_string(), "body".to_string());
+    let body = FullTextFilter::from_id_value(id, "body=rust").unwrap();
+    assert_eq!(
+        where_sql(body.condition()),
+        r#"SELECT * FROM "users" WHERE to_tsvector('english', "posts"."body") @@ plainto_tsquery('english', 'rust')"#
+    );
+}
+
+#[test]
+fn test_sea_query_any_of() {
+    let qs = "name=a&age[gte]=18&age[lt]=30";
+    let name = StringFilters::<String>::from_str("name", qs).unwrap();
+    let age = NumberFilters::<i32>::from_str("age", qs).unwrap();
+
+    assert_eq!(
+        where_sql(any_of(&[&name, &age])),
+        r#"SELECT * FROM "users" WHERE "name" = 'a' OR ("age" >= 18 AND "age" < 30)"#
+    );
+}
+
+#[test]
+fn test_sea_query_backends() {
+    let cond = || {
+        StringFilters::<String>::from_str("name", "name[len_gt]=3")
+            .unwrap()
+            .condition()
+    };
+    let select = |cond| {
+        Query::select()
+            .column(Asterisk)
+            .from(Alias::new("users"))
+            .cond_where(cond)
+            .to_owned()
+    };
+
+    assert_eq!(
+        select(cond()).to_string(MysqlQueryBuilder),
+        "SELECT * FROM `users` WHERE CHAR_LENGTH(`name`) > 3"
+    );
+    assert_eq!(
+        select(cond()).to_string(SqliteQueryBuilder),
+        r#"SELECT * FROM "users" WHERE LENGTH("name") > 3"#
+    );
+}
+
+#[cfg(feature = "derive")]
+mod derived {
+    use super::*;
+    use filtrum::{query_filter::FromQueryFilter, Filterable};
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "users")]
+    struct UserFilter {
+        name: StringFilters,
+        age: NumberFilters<i32>,
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "items")]
+    struct ItemFilter {
+        attr: DynamicFilters,
+        tags: ArrayFilters<String>,
+        price: Option<NumberFilters<i32>>,
+    }
+
+    #[test]
+    fn test_sea_query_field_types() {
+        let filter =
+            FromQueryFilter::<ItemFilter>::from_str("attr.color=red&tags[contains]=a&price[lt]=10")
+                .unwrap();
+
+        let mut select = Query::select();
+        select.column(Asterisk).from(Alias::new("items"));
+        filter.apply_to_select(&mut select);
+
+        assert_eq!(
+            select.to_string(PostgresQueryBuilder),
+            r#"SELECT * FROM "items" WHERE ("items"."attr" ->> 'color') = 'red' AND "items"."tags" @> (ARRAY[ 'a' ]) AND "items"."price" < 10"#
+        );
+    }
+
+    #[test]
+    fn test_sea_query_apply_to_select() {
+        let filter = FromQueryFilter::<UserFilter>::from_str(
+            "name[sw]=Al&age[gte]=18&order_by=-age&limit=10&skip=20",
+        )
+        .unwrap();
+
+        let mut select = Query::select();
+        select.column(Asterisk).from(Alias::new("users"));
+        filter.apply_to_select(&mut select);
+
+        assert_eq!(
+            select.to_string(PostgresQueryBuilder),
+            r#"SELECT * FROM "users" WHERE "users"."name" LIKE 'Al%' ESCAPE E'\\' AND "users"."age" >= 18 ORDER BY "users"."age" DESC LIMIT 10 OFFSET 20"#
+        );
+    }
+
+    #[test]
+    fn test_sea_query_random_order() {
+        let filter = FromQueryFilter::<UserFilter>::from_str("order_by[rand]=1").unwrap();
+
+        let mut select = Query::select();
+        select.column(Asterisk).from(Alias::new("users"));
+        filter.apply_to_select(&mut select);
+
+        assert_eq!(
+            select.to_string(MysqlQueryBuilder),
+            "SELECT * FROM `users` ORDER BY RAND() ASC"
+        );
+    }
+}
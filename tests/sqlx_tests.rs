@@ -44,6 +44,15 @@ impl SqlxFilter<Sqlite> for UserFilter {
     }
 }
 
+// no driver needed, for `render_sql`
+impl SqlxFilter<sqlx::Any> for UserFilter {
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, sqlx::Any>) {
+        self.name.apply(qb);
+        self.age.apply(qb);
+        self.active.apply(qb);
+    }
+}
+
 #[test]
 fn test_sqlx_query_builder() {
     let query = "name[sw]=Ali&age[gte]=18&active=true&limit=10&skip=5&order_by[desc]=age";
@@ -71,3 +80,2129 @@ fn test_sqlx_query_builder() {
     assert!(sql.contains("LIMIT"));
     assert!(sql.contains("OFFSET"));
 }
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlx_debug_sql() {
+    use filtrum::sqlx::debug_sql;
+
+    let query = "name[sw]=Ali&age[gte]=18&active=true&limit=10&skip=5&order_by[desc]=age";
+    let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+
+    let (sql, binds) = debug_sql("SELECT * FROM users WHERE 1=1", &filter);
+    assert_eq!(
+        sql,
+        "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND age >= ? AND active = ? ORDER BY age DESC LIMIT ? OFFSET ?"
+    );
+    assert_eq!(
+        binds,
+        vec!["Text(\"Ali%\")", "Integer(18)", "Bool(true)", "BigInt(10)", "BigInt(5)"]
+    );
+
+    // commas, quotes and brackets inside a value stay in one bind
+    let name = StringFilters::<String>::from_str("name", r#"name=a, "b" (c]&name[ne]=d"#).unwrap();
+    let (_, binds) = debug_sql("", &name);
+    assert_eq!(binds, vec![r#"Text("a, \"b\" (c]")"#, r#"Text("d")"#]);
+
+    let (sql, binds) = debug_sql("SELECT 1", &NumberFilters::<i32>::default());
+    assert_eq!((sql.as_str(), binds.len()), ("SELECT 1", 0));
+}
+
+#[test]
+fn test_sqlx_render_sql() {
+    use filtrum::sqlx::{render_sql, render_sql_with, ApplyOptions};
+
+    let query = "name[sw]=Ali&age[gte]=18&active=true&limit=10&skip=5&order_by[desc]=age";
+    let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+
+    let (sql, binds) = render_sql("SELECT * FROM users WHERE 1=1", &filter);
+    assert_eq!(
+        sql,
+        "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND age >= ? AND active = ? ORDER BY age DESC LIMIT ? OFFSET ?"
+    );
+    assert_eq!(
+        binds,
+        vec![
+            "Text(\"Ali%\")",
+            "Integer(18)",
+            "Bool(true)",
+            "BigInt(10)",
+            "BigInt(5)"
+        ]
+    );
+
+    let options = ApplyOptions {
+        quote_identifiers: true,
+        ..Default::default()
+    };
+    let age = NumberFilters::<i32>::from_str("age", "age[ne]=3").unwrap();
+    let (sql, binds) = render_sql_with("", &age, &options);
+    assert_eq!(sql, " AND \"age\" <> ?");
+    assert_eq!(binds, vec!["Integer(3)"]);
+
+    let (sql, binds) = render_sql("SELECT 1", &NumberFilters::<i32>::default());
+    assert_eq!((sql.as_str(), binds.len()), ("SELECT 1", 0));
+}
+
+#[cfg(feature = "sqlite")]
+#[test]
+fn test_sqlx_without_pagination() {
+    use filtrum::sqlx::debug_sql;
+
+    let query = "age[gte]=18&active=true&limit=10&skip=5&order_by[desc]=age";
+    let filter = FromQueryFilter::<UserFilter>::from_str(query)
+        .unwrap()
+        .without_pagination();
+
+    let (sql, binds) = debug_sql("SELECT COUNT(*) FROM users WHERE 1=1", &filter);
+    assert_eq!(
+        sql,
+        "SELECT COUNT(*) FROM users WHERE 1=1 AND age >= ? AND active = ?"
+    );
+    assert_eq!(binds, vec!["Integer(18)", "Bool(true)"]);
+}
+
+#[derive(Default)]
+struct OrderFilter {
+    name: StringFilters,
+}
+
+impl WithFilterId for OrderFilter {
+    fn filter_id() -> Option<&'static str> {
+        None
+    }
+
+    fn default_tiebreak() -> Option<&'static str> {
+        Some("id")
+    }
+}
+
+impl FromStr for OrderFilter {
+    type Err = filtrum::errors::FilterParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(OrderFilter {
+            name: StringFilters::from_str("name", s)?,
+        })
+    }
+}
+
+impl SqlxFilter<Sqlite> for OrderFilter {
+    fn apply<'a>(&self, qb: &mut QueryBuilder<'a, Sqlite>) {
+        self.name.apply(qb);
+    }
+}
+
+fn order_sql(query: &str) -> String {
+    let filter = FromQueryFilter::<OrderFilter>::from_str(query).unwrap();
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+    qb.sql().to_string()
+}
+
+#[test]
+fn test_sqlx_tiebreak_order() {
+    assert_eq!(
+        order_sql("order_by[desc]=age"),
+        "SELECT * FROM users WHERE 1=1 ORDER BY age DESC, id ASC"
+    );
+
+    assert_eq!(
+        order_sql(""),
+        "SELECT * FROM users WHERE 1=1 ORDER BY id ASC"
+    );
+
+    // sorting on the tie-break column itself must not repeat it
+    let sql = order_sql("order_by[desc]=id");
+    assert_eq!(sql, "SELECT * FROM users WHERE 1=1 ORDER BY id DESC");
+    assert_eq!(sql.matches("id").count(), 1);
+}
+
+#[test]
+fn test_sqlx_without_pagination_drops_default_sort() {
+    let filter = FromQueryFilter::<OrderFilter>::from_str("name=ana&order_by=age&limit=5")
+        .unwrap()
+        .without_pagination();
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT COUNT(*) FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+
+    // no `ORDER BY id ASC` from the tie-break
+    assert_eq!(qb.sql(), "SELECT COUNT(*) FROM users WHERE 1=1 AND name = ?");
+}
+
+#[test]
+fn test_sqlx_boxed_filters() {
+    use filtrum::sqlx::{apply_all, ApplyOptions, Conjunction};
+
+    let query = "name[sw]=Al&age[gte]=18&age[lt]=65&active=true";
+    let filters: Vec<Box<dyn SqlxFilter<Sqlite>>> = vec![
+        Box::new(StringFilters::<String>::from_str("name", query).unwrap()),
+        Box::new(NumberFilters::<i32>::from_str("age", query).unwrap()),
+        Box::new(EqualFilter::<bool>::from_str("active", query).unwrap()),
+    ];
+
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    for filter in &filters {
+        filter.apply(&mut qb);
+    }
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND age >= ? AND age < ? AND active = ?"
+    );
+
+    // a boxed filter is a filter too, so the list works with `apply_all`
+    let refs: Vec<&dyn SqlxFilter<Sqlite>> = filters.iter().map(|x| x as _).collect();
+    let options = ApplyOptions {
+        conjunction: Conjunction::Or,
+        ..Default::default()
+    };
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    apply_all(&mut qb, &refs, &options);
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND ((name LIKE ? ESCAPE '\\') OR (age >= ? AND age < ?) OR (active = ?))"
+    );
+}
+
+#[test]
+fn test_sqlx_builder_filter() {
+    let filter = FromQueryFilter::from_inner(UserFilter {
+        age: NumberFilters::from_str("age", "age[gte]=18").unwrap(),
+        ..Default::default()
+    })
+    .with_limit(10)
+    .with_skip(20)
+    .with_order_by(filtrum::OrderBy::Desc("age".to_string().into()));
+
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND age >= ? ORDER BY age DESC LIMIT ? OFFSET ?"
+    );
+}
+
+#[tokio::test]
+async fn test_sqlx_skip_without_limit_sqlite() {
+    use sqlx::{Connection, SqliteConnection};
+
+    let filter = FromQueryFilter::<UserFilter>::from_str("skip=1").unwrap();
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT 1 UNION ALL SELECT 2");
+    filter.apply(&mut qb);
+
+    assert_eq!(qb.sql(), "SELECT 1 UNION ALL SELECT 2 LIMIT -1 OFFSET ?");
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+    assert_eq!(rows.len(), 1);
+}
+
+#[test]
+fn test_sqlx_fluent_builders() {
+    let filters: [&dyn SqlxFilter<Sqlite>; 2] = [
+        &NumberFilters::for_column("age").gte(18).less_than(65),
+        &StringFilters::<String>::for_column("name")
+            .contains("al")
+            .not_equal_to("Alan")
+            .len_gt(2),
+    ];
+
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    for filter in filters {
+        filter.apply(&mut qb);
+    }
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND age >= ? AND age < ? AND name LIKE ? ESCAPE '\\' AND name <> ? AND LENGTH(name) > ?"
+    );
+
+    // the same conditions as parsing the query
+    assert_eq!(
+        NumberFilters::for_column("age").gte(18).less_than(65),
+        NumberFilters::<i32>::from_str("age", "age[gte]=18&age[lt]=65").unwrap()
+    );
+}
+
+fn string_sql<DB>(query: &str) -> String
+where
+    DB: sqlx::Database,
+    StringFilters: SqlxFilter<DB>,
+{
+    let filter = StringFilters::<String>::from_str("name", query).unwrap();
+    let mut qb: QueryBuilder<DB> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+    qb.sql().to_string()
+}
+
+#[test]
+fn test_sqlx_regex_postgres() {
+    assert_eq!(
+        string_sql::<sqlx::Postgres>("name[regex]=^Al&name[iregex]=son$"),
+        "SELECT * FROM users WHERE 1=1 AND name ~ $1 AND name ~* $2"
+    );
+}
+
+#[test]
+fn test_sqlx_regex_mysql() {
+    assert_eq!(
+        string_sql::<sqlx::MySql>("name[regex]=^Al&name[iregex]=son$"),
+        "SELECT * FROM users WHERE 1=1 AND REGEXP_LIKE(name, ?, 'c') AND REGEXP_LIKE(name, ?, 'i')"
+    );
+}
+
+#[test]
+fn test_sqlx_regex_sqlite() {
+    assert_eq!(
+        string_sql::<Sqlite>("name[regex]=^Al&name[iregex]=son$"),
+        "SELECT * FROM users WHERE 1=1 AND name REGEXP ? AND name REGEXP ?"
+    );
+}
+
+fn null_safe_sql<DB>() -> String
+where
+    DB: sqlx::Database,
+    StringFilters: SqlxFilter<DB>,
+    NumberFilters<i32>: SqlxFilter<DB>,
+{
+    let query = "name[nseq]=Al&name[!nseq]=Bo&name[!nseq]=null&age[nseq]=3&age[nseq]=null";
+    let name = StringFilters::<String>::from_str("name", query).unwrap();
+    let age = NumberFilters::<i32>::from_str("age", query).unwrap();
+
+    let mut qb: QueryBuilder<DB> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    name.apply(&mut qb);
+    age.apply(&mut qb);
+    qb.sql().to_string()
+}
+
+#[test]
+fn test_sqlx_null_safe_eq_mysql() {
+    assert_eq!(
+        null_safe_sql::<sqlx::MySql>(),
+        "SELECT * FROM users WHERE 1=1 AND name <=> ? AND NOT (name <=> ?) AND NOT (name IS NULL) AND age <=> ? AND age IS NULL"
+    );
+}
+
+#[test]
+fn test_sqlx_null_safe_eq_postgres() {
+    assert_eq!(
+        null_safe_sql::<sqlx::Postgres>(),
+        "SELECT * FROM users WHERE 1=1 AND name IS NOT DISTINCT FROM $1 AND NOT (name IS NOT DISTINCT FROM $2) AND NOT (name IS NULL) AND age IS NOT DISTINCT FROM $3 AND age IS NULL"
+    );
+}
+
+#[test]
+fn test_sqlx_null_safe_eq_sqlite() {
+    assert_eq!(
+        null_safe_sql::<Sqlite>(),
+        "SELECT * FROM users WHERE 1=1 AND name IS NOT DISTINCT FROM ? AND NOT (name IS NOT DISTINCT FROM ?) AND NOT (name IS NULL) AND age IS NOT DISTINCT FROM ? AND age IS NULL"
+    );
+}
+
+#[test]
+fn test_sqlx_length_sqlite() {
+    assert_eq!(
+        string_sql::<Sqlite>("name[len_gt]=0&name[len_lt]=10&name[len_eq]=5"),
+        "SELECT * FROM users WHERE 1=1 AND LENGTH(name) > ? AND LENGTH(name) < ? AND LENGTH(name) = ?"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_length_postgres() {
+    assert_eq!(
+        string_sql::<sqlx::Postgres>("name[len_gt]=0&name[len_eq]=5"),
+        "SELECT * FROM users WHERE 1=1 AND LENGTH(name) > $1 AND LENGTH(name) = $2"
+    );
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn test_sqlx_length_mysql() {
+    assert_eq!(
+        string_sql::<sqlx::MySql>("name[len_gt]=0&name[len_lt]=10"),
+        "SELECT * FROM users WHERE 1=1 AND CHAR_LENGTH(name) > ? AND CHAR_LENGTH(name) < ?"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_similar_postgres() {
+    let filter = StringFilters::<String>::from_str("name", "name[similar]=jon").unwrap();
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+    filter.apply_similarity_order(&mut qb, &Default::default());
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND name % $1 ORDER BY similarity(name, $2) DESC"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_similarity_order_merges_sort() {
+    // the query's own sort follows the similarity, in the same `ORDER BY`
+    let filter = FromQueryFilter::<UserFilter>::from_str("name[sim]=jon&order_by=-age").unwrap();
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    let name = &filter.inner.name;
+    name.apply(&mut qb);
+    name.apply_similarity_order(&mut qb, &filter.sort());
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND name % $1 ORDER BY similarity(name, $2) DESC, age DESC"
+    );
+
+    // without a `similar` filter only the sort is applied
+    let filter = FromQueryFilter::<UserFilter>::from_str("order_by=-age").unwrap();
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    let name = &filter.inner.name;
+    name.apply_similarity_order(&mut qb, &filter.sort());
+    assert_eq!(qb.sql(), "SELECT * FROM users WHERE 1=1 ORDER BY age DESC");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_similarity_order_with_options() {
+    use filtrum::filter_id::FilterId;
+    use filtrum::sqlx::{ApplyOptions, ColumnMap, TableAliases};
+
+    // the column is rendered as the condition renders it, aliased and quoted
+    let id = FilterId::WithPrefix("users".to_string(), "name".to_string());
+    let filter = StringFilters::<String>::from_str("name", "name[sim]=jon")
+        .unwrap()
+        .with_id(id);
+    let sort = FromQueryFilter::<UserFilter>::from_str("order_by=-age")
+        .unwrap()
+        .sort();
+    let options = ApplyOptions {
+        table_aliases: TableAliases::new().with("users", "u"),
+        quote_identifiers: true,
+        ..Default::default()
+    };
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("");
+    filter.apply_with(&mut qb, &options);
+    filter.apply_similarity_order_with(&mut qb, &sort, &options);
+
+    assert_eq!(
+        qb.sql(),
+        r#" AND "u"."name" % $1 ORDER BY similarity("u"."name", $2) DESC, "age" DESC"#
+    );
+
+    let options = ApplyOptions {
+        columns: ColumnMap::new().with("name", "v.full_name"),
+        ..Default::default()
+    };
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("");
+    filter.apply_similarity_order_with(&mut qb, &Default::default(), &options);
+    assert_eq!(qb.sql(), " ORDER BY similarity(v.full_name, $1) DESC");
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_contains_unaccent_postgres() {
+    assert_eq!(
+        string_sql::<sqlx::Postgres>("name[contains_unaccent]=jose"),
+        "SELECT * FROM users WHERE 1=1 AND unaccent(name) ILIKE unaccent($1) ESCAPE '\\'"
+    );
+}
+
+#[test]
+fn test_sqlx_contains_unaccent_elsewhere() {
+    // no case-insensitive match stands in for it, the database rejects `unaccent`
+    let filter =
+        StringFilters::<String>::new(vec![], "name".to_string().into()).contains_unaccent("jose");
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND unaccent(name) ILIKE unaccent(?) ESCAPE '\\'"
+    );
+}
+
+#[test]
+fn test_sqlx_similar_elsewhere() {
+    // no substring match stands in for it, the database rejects the `pg_trgm` function
+    let filter = StringFilters::<String>::new(vec![], "name".to_string().into()).similar("jon");
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply(&mut qb);
+    filter.apply_similarity_order(&mut qb, &Default::default());
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND similarity(name, ?) >= show_limit()"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_full_text_postgres() {
+    let filter = filtrum::FullTextFilter::from_str("q", "q[fts]=rust web")
+        .unwrap()
+        .on_column("body");
+
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT * FROM posts WHERE 1=1");
+    filter.apply(&mut qb);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM posts WHERE 1=1 AND to_tsvector('english', body) @@ plainto_tsquery('english', $1)"
+    );
+    assert_eq!(qb.sql().matches('$').count(), 1);
+}
+
+#[cfg(feature = "derive")]
+mod joined {
+    use filtrum::{
+        equal_filter::EqualFilter,
+        number_filter::NumberFilters,
+        query_filter::FromQueryFilter,
+        sqlx::{ApplyOptions, SqlxFilter},
+        string_filter::StringFilters,
+        Filterable,
+    };
+    use sqlx::{Connection, QueryBuilder, Row, Sqlite, SqliteConnection};
+    use std::str::FromStr;
+
+    #[derive(Filterable, Debug, Default, Clone)]
+    #[filtrum(table = "users")]
+    struct UserOrderFilter {
+        id: NumberFilters<i64>,
+        name: StringFilters,
+        #[filtrum(table = "orders", alias = "id")]
+        order_id: NumberFilters<i64>,
+        #[filtrum(table = "orders")]
+        total: NumberFilters<i64>,
+    }
+
+    const JOIN: &str = "SELECT users.name, orders.id FROM users JOIN orders ON orders.user_id = users.id WHERE 1=1";
+
+    #[test]
+    fn test_sqlx_qualified_columns() {
+        let filter =
+            UserOrderFilter::from_str("id=1&order_id[gt]=10&total[gte]=100&name=ana").unwrap();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(JOIN);
+        filter.apply(&mut qb);
+
+        assert_eq!(
+            qb.sql(),
+            format!(
+                "{} AND users.id = ? AND users.name = ? AND orders.id > ? AND orders.total >= ?",
+                JOIN
+            )
+        );
+    }
+
+    #[test]
+    fn test_sqlx_sort_on_aliased_field() {
+        // a field filters and sorts on the same column, with its own table and alias
+        for (query, order) in [
+            ("order_id[gt]=10&order_by=-order_id", "orders.id DESC"),
+            ("order_id[gt]=10&order_by=total", "orders.total ASC"),
+            ("order_id[gt]=10&order_by=name", "users.name ASC"),
+        ] {
+            let filter = FromQueryFilter::<UserOrderFilter>::from_str(query).unwrap();
+
+            let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(JOIN);
+            filter.apply(&mut qb);
+
+            assert_eq!(qb.sql(), format!("{JOIN} AND orders.id > ? ORDER BY {order}"));
+        }
+    }
+
+    #[test]
+    fn test_sqlx_column_map() {
+        use filtrum::sqlx::ColumnMap;
+
+        let filter = FromQueryFilter::<UserOrderFilter>::from_str(
+            "name=ana&order_id[gt]=10&total[gte]=100&order_by=-name",
+        )
+        .unwrap();
+
+        let render = |columns: ColumnMap| {
+            let options = ApplyOptions {
+                columns,
+                ..Default::default()
+            };
+            let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM v WHERE 1=1");
+            filter.apply_with(&mut qb, &options);
+            qb.sql().to_string()
+        };
+
+        assert_eq!(
+            render(
+                ColumnMap::new()
+                    .with("name", "v.full_name")
+                    .with("total", "v.amount")
+            ),
+            "SELECT * FROM v WHERE 1=1 AND v.full_name = ? AND orders.id > ? AND v.amount >= ? ORDER BY v.full_name DESC"
+        );
+        assert_eq!(
+            render(
+                ColumnMap::new()
+                    .with("order_id", "o.order_no")
+                    .with("name", "customer")
+            ),
+            "SELECT * FROM v WHERE 1=1 AND customer = ? AND o.order_no > ? AND orders.total >= ? ORDER BY customer DESC"
+        );
+        // unmapped fields keep their default column
+        assert_eq!(
+            render(ColumnMap::new()),
+            "SELECT * FROM v WHERE 1=1 AND users.name = ? AND orders.id > ? AND orders.total >= ? ORDER BY users.name DESC"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_join_column_collision() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total INTEGER);
+             INSERT INTO users VALUES (1, 'ana'), (2, 'bob');
+             INSERT INTO orders VALUES (1, 2, 50), (2, 1, 150), (3, 1, 300);",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        // `id` and `order_id` both target an `id` column, on different tables
+        let query = "id=1&order_id[gt]=2&total[gte]=100&order_by[desc]=name";
+        let filter = FromQueryFilter::<UserOrderFilter>::from_str(query).unwrap();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(JOIN);
+        filter.apply(&mut qb);
+        assert!(qb.sql().ends_with(" ORDER BY users.name DESC"));
+
+        let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+        let rows = rows
+            .iter()
+            .map(|r| (r.get::<String, _>(0), r.get::<i64, _>(1)))
+            .collect::<Vec<_>>();
+
+        assert_eq!(rows, vec![("ana".to_string(), 3)]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_table_aliases() {
+        use filtrum::sqlx::TableAliases;
+
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total INTEGER);
+             INSERT INTO users VALUES (1, 'ana'), (2, 'bob');
+             INSERT INTO orders VALUES (1, 2, 50), (2, 1, 150), (3, 1, 300);",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        let join = "SELECT u.name, o.id FROM users u JOIN orders o ON o.user_id = u.id WHERE 1=1";
+        let query = "name=ana&order_id[gt]=2&total[gte]=100&order_by=-name";
+        let filter = FromQueryFilter::<UserOrderFilter>::from_str(query).unwrap();
+
+        let options = ApplyOptions {
+            table_aliases: TableAliases::new().with("users", "u").with("orders", "o"),
+            ..Default::default()
+        };
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(join);
+        filter.apply_with(&mut qb, &options);
+        assert_eq!(
+            qb.sql(),
+            format!("{join} AND u.name = ? AND o.id > ? AND o.total >= ? ORDER BY u.name DESC")
+        );
+
+        let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+        let rows = rows
+            .iter()
+            .map(|r| (r.get::<String, _>(0), r.get::<i64, _>(1)))
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![("ana".to_string(), 3)]);
+
+        // unmapped tables keep their name, and aliases are quoted like tables
+        let options = ApplyOptions {
+            table_aliases: TableAliases::new().with("orders", "o"),
+            quote_identifiers: true,
+            ..Default::default()
+        };
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("");
+        filter.apply_with(&mut qb, &options);
+        assert_eq!(
+            qb.sql(),
+            r#" AND "users"."name" = ? AND "o"."id" > ? AND "o"."total" >= ? ORDER BY "users"."name" DESC"#
+        );
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "users")]
+    struct UserFlagFilter {
+        active: EqualFilter<bool>,
+        #[filtrum(table = "orders", alias = "paid")]
+        order_paid: EqualFilter<bool>,
+        #[filtrum(table = "orders")]
+        order_status: Option<EqualFilter<String>>,
+    }
+
+    #[test]
+    fn test_sqlx_qualified_equal_filters() {
+        use filtrum::sqlx::TableAliases;
+
+        let query = "active=true&order_paid=false&order_status=shipped";
+        let filter = FromQueryFilter::<UserFlagFilter>::from_str(query).unwrap();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("");
+        filter.apply(&mut qb);
+        assert_eq!(
+            qb.sql(),
+            " AND users.active = ? AND orders.paid = ? AND orders.order_status = ?"
+        );
+
+        let options = ApplyOptions {
+            table_aliases: TableAliases::new().with("users", "u"),
+            ..Default::default()
+        };
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("");
+        filter.inner.apply_with(&mut qb, &options);
+        assert_eq!(
+            qb.sql(),
+            " AND u.active = ? AND orders.paid = ? AND orders.order_status = ?"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_conditions_reused() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT);
+             CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total INTEGER);
+             INSERT INTO users VALUES (1, 'ana'), (2, 'bob');
+             INSERT INTO orders VALUES (1, 2, 50), (2, 1, 150), (3, 1, 300), (4, 2, 200);",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        let query = "total[gte]=100&order_by[desc]=name&limit=1";
+        let filter = FromQueryFilter::<UserOrderFilter>::from_str(query).unwrap();
+        let conditions = filter.conditions::<Sqlite>();
+        let options = ApplyOptions::default();
+
+        let mut count: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT COUNT(*) FROM users JOIN orders ON orders.user_id = users.id WHERE 1=1",
+        );
+        conditions.render(&mut count, &options);
+        assert!(count.sql().ends_with("WHERE 1=1 AND orders.total >= ?"));
+
+        let mut page: QueryBuilder<Sqlite> = QueryBuilder::new(JOIN);
+        conditions.render(&mut page, &options);
+        filter.sort().apply(&mut page);
+        filter.limit.as_ref().unwrap().apply(&mut page);
+        assert_eq!(
+            page.sql(),
+            format!("{JOIN} AND orders.total >= ? ORDER BY users.name DESC LIMIT ?")
+        );
+
+        let total: i64 = count.build().fetch_one(&mut conn).await.unwrap().get(0);
+        assert_eq!(total, 3);
+
+        let rows = page.build().fetch_all(&mut conn).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<i64, _>(1), 4);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlx_shape() {
+        use filtrum::sqlx::debug_sql;
+        use std::collections::HashMap;
+
+        let mut cache: HashMap<String, String> = HashMap::new();
+        let mut render = |query: &str| {
+            let filter = FromQueryFilter::<UserOrderFilter>::from_str(query).unwrap();
+            let (sql, binds) = debug_sql(JOIN, &filter);
+
+            let hit = cache.contains_key(&filter.shape());
+            let cached = cache.entry(filter.shape()).or_insert_with(|| sql.clone());
+            assert_eq!(*cached, sql);
+            (filter.shape(), hit, binds)
+        };
+
+        let (shape, hit, binds) = render("name[sw]=a&total[gte]=100&order_by=-name&limit=5");
+        assert_eq!(
+            shape,
+            "name[starts_with]&total[gte]&order_by=users.name:desc&limit"
+        );
+        assert!(!hit);
+        assert_eq!(binds, ["Text(\"a%\")", "BigInt(100)", "BigInt(5)"]);
+
+        // the same shape, with other values and parameters in another order
+        let (_, hit, binds) = render("limit=20&total[gte]=7&name[sw]=bob&order_by=-name");
+        assert!(hit);
+        assert_eq!(binds, ["Text(\"bob%\")", "BigInt(7)", "BigInt(20)"]);
+
+        // another operator, or no limit, is another shape
+        let (shape, hit, _) = render("name[ew]=a&total[gte]=100&order_by=-name&limit=5");
+        assert!(!hit);
+        assert_eq!(
+            shape,
+            "name[ends_with]&total[gte]&order_by=users.name:desc&limit"
+        );
+
+        let (_, hit, _) = render("name[sw]=a&total[gte]=100&order_by=-name");
+        assert!(!hit);
+
+        // list values count their items, as each one is bound
+        let (shape, _, _) = render("total[between]=1,2");
+        assert_eq!(shape, "total[between;2]");
+
+        // so do the brackets of a range, and a `NULL`, which render other comparisons
+        let (shape, _, _) = render("total[range]=[1,2)");
+        assert_eq!(shape, "total[range;[)]");
+
+        let (shape, hit, _) = render("total[range]=(1,2]");
+        assert_eq!(shape, "total[range;(]]");
+        assert!(!hit);
+
+        let (shape, _, _) = render("total[nseq]=null");
+        assert_eq!(shape, "total[nseq;null]");
+
+        let (shape, hit, _) = render("total[nseq]=5");
+        assert_eq!(shape, "total[nseq]");
+        assert!(!hit);
+    }
+}
+
+mod custom_string {
+    use filtrum::{
+        sqlx::{render_sql, SqlxFilter},
+        string_filter::StringFilters,
+    };
+    use sqlx::{encode::IsNull, error::BoxDynError, Database, Encode, Type};
+    use std::{fmt, str::FromStr};
+
+    /// A string column with its own type, compared case-insensitively.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    struct Email(String);
+
+    impl FromStr for Email {
+        type Err = std::convert::Infallible;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            Ok(Email(s.to_lowercase()))
+        }
+    }
+
+    impl fmt::Display for Email {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+
+    impl<DB: Database> Type<DB> for Email
+    where
+        String: Type<DB>,
+    {
+        fn type_info() -> DB::TypeInfo {
+            <String as Type<DB>>::type_info()
+        }
+    }
+
+    impl<'q, DB: Database> Encode<'q, DB> for Email
+    where
+        String: Encode<'q, DB>,
+    {
+        fn encode_by_ref(
+            &self,
+            buf: &mut <DB as Database>::ArgumentBuffer<'q>,
+        ) -> Result<IsNull, BoxDynError> {
+            self.0.encode_by_ref(buf)
+        }
+    }
+
+    fn assert_sqlx_filter<DB: Database, F: SqlxFilter<DB>>() {}
+
+    #[test]
+    fn test_sqlx_custom_string_type() {
+        let query = "email=Ana@X.io&email[c]=X.io&email[ne]=bob@x.io";
+        let email = StringFilters::<Email>::from_str("email", query).unwrap();
+
+        let (sql, binds) = render_sql("", &email);
+        assert_eq!(
+            sql,
+            " AND email = ? AND email LIKE ? ESCAPE '\\' AND email <> ?"
+        );
+        assert_eq!(
+            binds,
+            vec![
+                r#"Text("ana@x.io")"#,
+                r#"Text("%x.io%")"#,
+                r#"Text("bob@x.io")"#
+            ]
+        );
+
+        assert_sqlx_filter::<sqlx::Sqlite, StringFilters<Email>>();
+        assert_sqlx_filter::<sqlx::Postgres, StringFilters<Email>>();
+        assert_sqlx_filter::<sqlx::MySql, StringFilters<Email>>();
+    }
+}
+
+#[cfg(feature = "derive")]
+mod conjunction {
+    use filtrum::{
+        number_filter::NumberFilters,
+        query_filter::FromQueryFilter,
+        sqlx::{ApplyOptions, Conjunction, SqlxFilter},
+        string_filter::StringFilters,
+        Filterable,
+    };
+    use sqlx::{QueryBuilder, Sqlite};
+
+    #[derive(Filterable, Debug, Default)]
+    struct SearchFilter {
+        name: StringFilters,
+        email: StringFilters,
+        age: NumberFilters<i32>,
+    }
+
+    fn search_sql(query: &str, conjunction: Conjunction) -> String {
+        let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        let options = ApplyOptions {
+            conjunction,
+            ..Default::default()
+        };
+        filter.apply_with(&mut qb, &options);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn test_sqlx_conjunction_and() {
+        let query = "name[contains]=ana&email[ew]=@x.com&age[gte]=18&age[lt]=30&limit=5";
+
+        assert_eq!(
+            search_sql(query, Conjunction::And),
+            "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND email LIKE ? ESCAPE '\\' AND age >= ? AND age < ? LIMIT ?"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_conjunction_or() {
+        let query = "name[contains]=ana&email[ew]=@x.com&age[gte]=18&age[lt]=30&limit=5";
+
+        assert_eq!(
+            search_sql(query, Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 AND ((name LIKE ? ESCAPE '\\') OR (email LIKE ? ESCAPE '\\') OR (age >= ? AND age < ?)) LIMIT ?"
+        );
+
+        // fields without conditions don't leave empty groups behind
+        assert_eq!(
+            search_sql("email[ew]=@x.com", Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 AND ((email LIKE ? ESCAPE '\\'))"
+        );
+        assert_eq!(
+            search_sql("limit=5", Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 LIMIT ?"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_or_of_derived_filters() {
+        use filtrum::sqlx::apply_all;
+        use std::str::FromStr;
+
+        let render = |filters: &[&dyn SqlxFilter<Sqlite>], conjunction: Conjunction| {
+            let options = ApplyOptions {
+                conjunction,
+                ..Default::default()
+            };
+            let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+            apply_all(&mut qb, filters, &options);
+            qb.sql().to_string()
+        };
+
+        let ana = SearchFilter::from_str("name=ana&age[gte]=18").unwrap();
+        let x = SearchFilter::from_str("email[ew]=@x.com").unwrap();
+        let empty = SearchFilter::default();
+
+        assert_eq!(
+            render(&[&ana, &empty, &x], Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 AND (((name = ?) OR (age >= ?)) OR ((email LIKE ? ESCAPE '\\')))"
+        );
+        assert_eq!(
+            render(&[&empty], Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1"
+        );
+
+        // a hand-written filter that only implements `apply` is grouped too
+        let user = super::UserFilter::from_str("name=bob&active=true").unwrap();
+        assert_eq!(
+            render(&[&ana, &user], Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 AND (((name = ?) OR (age >= ?)) OR (1=1 AND name = ? AND active = ?))"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_nested_or() {
+        use filtrum::sqlx::{apply_all, Conditions};
+        use std::str::FromStr;
+
+        let base = "SELECT * FROM users WHERE 1=1";
+        let ana = "name=ana&age[gte]=18&order_by[desc]=age&limit=5";
+
+        let conditions = Conditions::<Sqlite>::Any(vec![
+            Conditions::new(SearchFilter::from_str("name=ana&age[gte]=18").unwrap()),
+            Conditions::All(vec![
+                Conditions::new(SearchFilter::from_str("email[ew]=@x.com").unwrap()),
+                Conditions::new(SearchFilter::default()),
+            ]),
+        ]);
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(base);
+        conditions.apply(&mut qb);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND (((name = ?) AND (age >= ?)) OR (((email LIKE ? ESCAPE '\\'))))"
+        );
+
+        // wrapped filters keep their conditions, and leave the sort and limit out
+        let options = ApplyOptions {
+            conjunction: Conjunction::Or,
+            ..Default::default()
+        };
+        let filter = FromQueryFilter::<SearchFilter>::from_str(ana).unwrap();
+        let other = FromQueryFilter::<SearchFilter>::from_str("email[ew]=@x.com").unwrap();
+        let scoped = other.and_raw(|qb: &mut QueryBuilder<'_, Sqlite>| {
+            qb.push("tenant_id = ").push_bind(7i64);
+        });
+        let negated = filter.negated();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new(base);
+        apply_all(&mut qb, &[&filter, &negated, &scoped], &options);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND (((name = ?) OR (age >= ?)) OR (NOT (1=1 AND ((name = ?) OR (age >= ?)))) OR ((tenant_id = ?) AND ((email LIKE ? ESCAPE '\\'))))"
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlx_condition_order() {
+        use filtrum::{sqlx::debug_sql, Merge};
+        use std::str::FromStr;
+
+        // fields in declaration order, each field's conditions in query order
+        let query = "limit=5&age[lt]=30&email[ew]=@x.com&age[gte]=18&name[ne]=bob&name[sw]=a";
+        let mut filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+
+        let base = "SELECT * FROM users WHERE 1=1";
+        let (sql, binds) = debug_sql(base, &filter);
+        assert_eq!(
+            sql,
+            "SELECT * FROM users WHERE 1=1 AND name <> ? AND name LIKE ? ESCAPE '\\' AND email LIKE ? ESCAPE '\\' AND age < ? AND age >= ? LIMIT ?"
+        );
+        assert_eq!(
+            binds,
+            vec![
+                r#"Text("bob")"#,
+                r#"Text("a%")"#,
+                r#"Text("%@x.com")"#,
+                "Integer(30)",
+                "Integer(18)",
+                "BigInt(5)"
+            ]
+        );
+
+        // the same conditions in any other query order render the same SQL
+        let shuffled = "name[ne]=bob&age[lt]=30&name[sw]=a&age[gte]=18&email[ew]=@x.com&limit=5";
+        let other = FromQueryFilter::<SearchFilter>::from_str(shuffled).unwrap();
+        assert_eq!(debug_sql(base, &other), (sql, binds));
+
+        // a merged condition replaces the old one at the end of its field
+        filter
+            .inner
+            .merge(SearchFilter::from_str("age[lt]=40").unwrap());
+        assert_eq!(
+            debug_sql(base, &filter).0,
+            "SELECT * FROM users WHERE 1=1 AND name <> ? AND name LIKE ? ESCAPE '\\' AND email LIKE ? ESCAPE '\\' AND age >= ? AND age < ? LIMIT ?"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_canonical_order() {
+        use filtrum::sqlx::render_sql_with;
+
+        let base = "SELECT * FROM users WHERE 1=1";
+        let sql = |query: &str, canonical_order: bool| {
+            let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+            let options = ApplyOptions {
+                canonical_order,
+                ..Default::default()
+            };
+            render_sql_with(base, &filter, &options).0
+        };
+
+        let a = "name[sw]=a&age[lt]=30&name[ne]=bob&age[gte]=18";
+        let b = "age[gte]=18&name[ne]=bob&age[lt]=30&name[sw]=a";
+        assert_ne!(sql(a, false), sql(b, false));
+        assert_eq!(sql(a, true), sql(b, true));
+        assert_eq!(
+            sql(a, true),
+            "SELECT * FROM users WHERE 1=1 AND name <> ? AND name LIKE ? ESCAPE '\\' AND age >= ? AND age < ?"
+        );
+
+        // also within the groups of an OR
+        let options = ApplyOptions {
+            conjunction: Conjunction::Or,
+            canonical_order: true,
+            ..Default::default()
+        };
+        let render = |query: &str| {
+            let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+            render_sql_with(base, &filter, &options).0
+        };
+        assert_eq!(render(a), render(b));
+        assert_eq!(
+            render(a),
+            "SELECT * FROM users WHERE 1=1 AND ((name <> ? AND name LIKE ? ESCAPE '\\') OR (age >= ? AND age < ?))"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_disabled_fields() {
+        use filtrum::sqlx::FieldAccess;
+
+        let query = "name[contains]=ana&email[ew]=@x.com&age[gte]=18&limit=5";
+        let render = |filter: &FromQueryFilter<SearchFilter>, fields: FieldAccess| {
+            let options = ApplyOptions {
+                fields,
+                ..Default::default()
+            };
+            let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+            filter.apply_with(&mut qb, &options);
+            qb.sql().to_string()
+        };
+
+        let filter = FromQueryFilter::<SearchFilter>::from_str(query)
+            .unwrap()
+            .disable_field("email")
+            .disable_field("unknown");
+        assert_eq!(filter.inner.email.0, vec![]);
+        assert_eq!(
+            render(&filter, FieldAccess::All),
+            "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND age >= ? LIMIT ?"
+        );
+
+        let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+        assert_eq!(
+            render(&filter, FieldAccess::deny(&["name", "age"])),
+            "SELECT * FROM users WHERE 1=1 AND email LIKE ? ESCAPE '\\' LIMIT ?"
+        );
+        assert_eq!(
+            render(&filter, FieldAccess::allow(&["age"])),
+            "SELECT * FROM users WHERE 1=1 AND age >= ? LIMIT ?"
+        );
+        assert_eq!(
+            render(&filter, FieldAccess::allow(&[])),
+            "SELECT * FROM users WHERE 1=1 LIMIT ?"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_not() {
+        let query = "name[!contains]=spam&email[!len_lt]=3&age[gte]=18";
+
+        assert_eq!(
+            search_sql(query, Conjunction::And),
+            "SELECT * FROM users WHERE 1=1 AND NOT (name LIKE ? ESCAPE '\\') AND NOT (LENGTH(email) < ?) AND age >= ?"
+        );
+
+        // each negation stays inside its own field's group
+        assert_eq!(
+            search_sql(query, Conjunction::Or),
+            "SELECT * FROM users WHERE 1=1 AND ((NOT (name LIKE ? ESCAPE '\\')) OR (NOT (LENGTH(email) < ?)) OR (age >= ?))"
+        );
+    }
+
+    #[test]
+    fn test_sqlx_and_raw() {
+        let query = "name[eq]=ana&age[gte]=18&order_by[desc]=age&limit=5";
+        let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+        let tenant_id = 7i64;
+        let scoped = filter.and_raw(|qb| {
+            qb.push("deleted_at IS NULL AND tenant_id = ")
+                .push_bind(tenant_id);
+        });
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        scoped.apply(&mut qb);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND (deleted_at IS NULL AND tenant_id = ?) AND name = ? AND age >= ? ORDER BY age DESC LIMIT ?"
+        );
+
+        // the raw condition still has to hold when the filters are ORed
+        let options = ApplyOptions {
+            conjunction: Conjunction::Or,
+            ..Default::default()
+        };
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        scoped.apply_with(&mut qb, &options);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND (deleted_at IS NULL AND tenant_id = ?) AND ((name = ?) OR (age >= ?)) ORDER BY age DESC LIMIT ?"
+        );
+    }
+    #[test]
+    fn test_sqlx_negated() {
+        let query = "name[eq]=ana&age[gte]=18&order_by[desc]=age&limit=5&skip=10";
+        let filter = FromQueryFilter::<SearchFilter>::from_str(query).unwrap();
+
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.negated().apply(&mut qb);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND NOT (1=1 AND name = ? AND age >= ?) ORDER BY age DESC LIMIT ? OFFSET ?"
+        );
+
+        let options = ApplyOptions {
+            conjunction: Conjunction::Or,
+            ..Default::default()
+        };
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.negated().apply_with(&mut qb, &options);
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM users WHERE 1=1 AND NOT (1=1 AND ((name = ?) OR (age >= ?))) ORDER BY age DESC LIMIT ? OFFSET ?"
+        );
+
+        // with no conditions there is nothing to negate, and every row matches
+        let filter = FromQueryFilter::<SearchFilter>::from_str("limit=5").unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.negated().apply(&mut qb);
+        assert_eq!(qb.sql(), "SELECT * FROM users WHERE 1=1 LIMIT ?");
+    }
+}
+
+#[cfg(feature = "derive")]
+mod dynamic {
+    use filtrum::{
+        dynamic_filter::DynamicFilters,
+        errors::FilterParseError,
+        query_filter::FromQueryFilter,
+        sqlx::{Dialect, SqlxFilter},
+        string_filter::StringFilters,
+        Filterable,
+    };
+    use sqlx::{Connection, QueryBuilder, Row, Sqlite, SqliteConnection};
+
+    #[derive(Filterable, Debug, Default)]
+    struct ProductFilter {
+        name: StringFilters,
+        attrs: DynamicFilters,
+    }
+
+    fn product_sql<DB>(query: &str) -> String
+    where
+        DB: sqlx::Database,
+        ProductFilter: SqlxFilter<DB>,
+        i64: sqlx::Type<DB> + for<'q> sqlx::Encode<'q, DB>,
+    {
+        let filter = FromQueryFilter::<ProductFilter>::from_str(query).unwrap();
+        let mut qb: QueryBuilder<DB> = QueryBuilder::new("SELECT * FROM products WHERE 1=1");
+        filter.apply(&mut qb);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn test_sqlx_dynamic_attributes_sql() {
+        let query = "attrs.color=red&attrs.size[ne]=XL";
+
+        assert_eq!(
+            product_sql::<Sqlite>(query),
+            "SELECT * FROM products WHERE 1=1 AND json_extract(attrs, '$.color') = ? AND json_extract(attrs, '$.size') <> ?"
+        );
+
+        #[cfg(feature = "postgres")]
+        assert_eq!(
+            product_sql::<sqlx::Postgres>(query),
+            "SELECT * FROM products WHERE 1=1 AND attrs->>'color' = $1 AND attrs->>'size' <> $2"
+        );
+
+        #[cfg(feature = "mysql")]
+        assert_eq!(
+            product_sql::<sqlx::MySql>(query),
+            "SELECT * FROM products WHERE 1=1 AND attrs->>'$.color' = ? AND attrs->>'$.size' <> ?"
+        );
+
+        // the attributes are part of the SQL, so they are part of the shape
+        let shape = |query| {
+            FromQueryFilter::<ProductFilter>::from_str(query)
+                .unwrap()
+                .shape()
+        };
+        assert_eq!(shape(query), "attrs.color[eq]&attrs.size[ne]");
+        assert_ne!(shape("attrs.color=red"), shape("attrs.size=red"));
+
+        // an attribute is written into the SQL, so only word characters are parsed
+        assert!(matches!(
+            FromQueryFilter::<ProductFilter>::from_str(r"attrs.x\'=1"),
+            Err(FilterParseError::FilterStructure)
+        ));
+        assert_eq!(
+            Dialect::MySql.json_text("attrs", r"x\'"),
+            r"attrs->>'$.x\\'''"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_dynamic_attributes_sqlite() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            r#"CREATE TABLE products (name TEXT, attrs TEXT);
+             INSERT INTO products VALUES
+                ('shirt', '{"color": "red", "size": "M"}'),
+                ('jacket', '{"color": "red", "size": "XL"}'),
+                ('scarf', '{"color": "blue"}');"#,
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        let names = |query: &'static str| {
+            let filter = FromQueryFilter::<ProductFilter>::from_str(query).unwrap();
+            let mut qb: QueryBuilder<Sqlite> =
+                QueryBuilder::new("SELECT name FROM products WHERE 1=1");
+            filter.apply(&mut qb);
+            qb.push(" ORDER BY name");
+            qb
+        };
+
+        for (query, expected) in [
+            ("attrs.color=red", vec!["jacket", "shirt"]),
+            ("attrs.color=red&attrs.size[ne]=XL", vec!["shirt"]),
+            ("attrs.size[sw]=X&name[c]=ket", vec!["jacket"]),
+            ("attrs.material=wool", vec![]),
+        ] {
+            let rows = names(query).build().fetch_all(&mut conn).await.unwrap();
+            let rows = rows
+                .iter()
+                .map(|r| r.get::<String, _>(0))
+                .collect::<Vec<_>>();
+            assert_eq!(rows, expected, "{query}");
+        }
+    }
+}
+
+#[cfg(feature = "derive")]
+mod nullable {
+    use filtrum::{nullable_filter::NullableFilter, query_filter::FromQueryFilter, Filterable};
+    use sqlx::{QueryBuilder, Sqlite};
+
+    #[derive(Filterable, Debug, Default)]
+    struct CommentFilter {
+        parent_id: NullableFilter<i64>,
+        #[filtrum(default_value = "!null")]
+        published_at: NullableFilter<String>,
+    }
+
+    fn comment_sql(query: &str) -> String {
+        use filtrum::sqlx::SqlxFilter;
+
+        let filter = FromQueryFilter::<CommentFilter>::from_str(query).unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM comments WHERE 1=1");
+        filter.apply(&mut qb);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn test_sqlx_nullable() {
+        assert_eq!(
+            comment_sql("parent_id=null"),
+            "SELECT * FROM comments WHERE 1=1 AND parent_id IS NULL AND published_at IS NOT NULL"
+        );
+        assert_eq!(
+            comment_sql("parent_id=5"),
+            "SELECT * FROM comments WHERE 1=1 AND parent_id = ? AND published_at IS NOT NULL"
+        );
+
+        // absent: `parent_id` doesn't filter, `published_at` falls back to its default
+        assert_eq!(
+            comment_sql(""),
+            "SELECT * FROM comments WHERE 1=1 AND published_at IS NOT NULL"
+        );
+
+        // the default can be overridden, or cleared
+        assert_eq!(
+            comment_sql("published_at=null"),
+            "SELECT * FROM comments WHERE 1=1 AND published_at IS NULL"
+        );
+        assert_eq!(
+            comment_sql("published_at="),
+            "SELECT * FROM comments WHERE 1=1"
+        );
+    }
+
+    #[test]
+    fn test_nullable_shape() {
+        let shape = |query| {
+            FromQueryFilter::<CommentFilter>::from_str(query)
+                .unwrap()
+                .shape()
+        };
+
+        // `IS NULL`, `IS NOT NULL` and `= ?` each have their own shape
+        assert_eq!(shape("parent_id=null"), "parent_id[eq;null]&published_at[ne;null]");
+        assert_eq!(shape("parent_id=!null"), "parent_id[ne;null]&published_at[ne;null]");
+        assert_eq!(shape("parent_id=5"), "parent_id[eq]&published_at[ne;null]");
+        assert_eq!(shape("parent_id=7"), shape("parent_id=5"));
+    }
+}
+
+#[cfg(feature = "derive")]
+mod search {
+    use filtrum::{
+        number_filter::NumberFilters, query_filter::FromQueryFilter, search_filter::SearchFilter,
+        sqlx::SqlxFilter, Filterable,
+    };
+    use sqlx::{Connection, QueryBuilder, Row, Sqlite, SqliteConnection};
+
+    #[derive(Filterable, Debug, Default)]
+    struct UserFilter {
+        #[filtrum(search(name, email))]
+        q: SearchFilter,
+        age: NumberFilters<i32>,
+    }
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "users")]
+    struct JoinedUserFilter {
+        #[filtrum(search(name, email))]
+        q: SearchFilter,
+    }
+
+    fn user_sql<DB: sqlx::Database>(query: &str) -> String
+    where
+        UserFilter: SqlxFilter<DB>,
+        i64: sqlx::Type<DB> + for<'q> sqlx::Encode<'q, DB>,
+    {
+        let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+        let mut qb: QueryBuilder<DB> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.apply(&mut qb);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn test_sqlx_search_sql() {
+        assert_eq!(
+            user_sql::<Sqlite>("q=ali&age[gte]=18"),
+            "SELECT * FROM users WHERE 1=1 AND (LOWER(name) LIKE LOWER(?) ESCAPE '\\' \
+             OR LOWER(email) LIKE LOWER(?) ESCAPE '\\') AND age >= ?"
+        );
+        assert_eq!(
+            user_sql::<Sqlite>("age[gte]=18"),
+            "SELECT * FROM users WHERE 1=1 AND age >= ?"
+        );
+
+        let filter = FromQueryFilter::<JoinedUserFilter>::from_str("q=ali").unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.apply(&mut qb);
+        assert!(qb.sql().contains("LOWER(users.name) LIKE"), "{}", qb.sql());
+        assert!(qb.sql().contains("LOWER(users.email) LIKE"), "{}", qb.sql());
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_sqlx_search_postgres() {
+        assert_eq!(
+            user_sql::<sqlx::Postgres>("q=ali"),
+            "SELECT * FROM users WHERE 1=1 AND (name ILIKE $1 ESCAPE '\\' OR email ILIKE $2 ESCAPE '\\')"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_search_sqlite() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE users (name TEXT, email TEXT, age INTEGER);
+             INSERT INTO users VALUES
+                ('Alice', 'alice@example.com', 30),
+                ('Bob', 'bob@alice.dev', 40),
+                ('Carol', 'carol_100%@example.com', 50);",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        for (query, expected) in [
+            ("q=ALICE", vec!["Alice", "Bob"]),
+            ("q=alice&age[gt]=30", vec!["Bob"]),
+            // wildcards in the term match literally
+            ("q=_100%", vec!["Carol"]),
+            ("q=%", vec!["Carol"]),
+        ] {
+            let filter = FromQueryFilter::<UserFilter>::from_str(query).unwrap();
+            let mut qb: QueryBuilder<Sqlite> =
+                QueryBuilder::new("SELECT name FROM users WHERE 1=1");
+            filter.apply(&mut qb);
+            qb.push(" ORDER BY name");
+
+            let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+            let rows = rows
+                .iter()
+                .map(|r| r.get::<String, _>(0))
+                .collect::<Vec<_>>();
+            assert_eq!(rows, expected, "{query}");
+        }
+    }
+}
+
+#[cfg(all(feature = "derive", any(feature = "postgres", feature = "sqlite")))]
+mod in_list {
+    use filtrum::{in_filter::InFilters, query_filter::FromQueryFilter, sqlx::SqlxFilter, Filterable};
+    use sqlx::{Database, QueryBuilder};
+    #[cfg(feature = "sqlite")]
+    use sqlx::{Connection, Row, Sqlite, SqliteConnection};
+
+    #[derive(Filterable, Debug, Default)]
+    struct OrderFilter {
+        id: InFilters<i64>,
+        status: InFilters<String>,
+    }
+
+    fn order_sql<DB>(query: &str) -> QueryBuilder<'static, DB>
+    where
+        DB: Database,
+        OrderFilter: SqlxFilter<DB>,
+        i64: sqlx::Type<DB> + for<'q> sqlx::Encode<'q, DB>,
+    {
+        let filter = FromQueryFilter::<OrderFilter>::from_str(query).unwrap();
+        let mut qb = QueryBuilder::new("SELECT * FROM orders WHERE 1=1");
+        filter.apply(&mut qb);
+        qb
+    }
+
+    #[cfg(feature = "postgres")]
+    #[test]
+    fn test_sqlx_in_postgres() {
+        use sqlx::{Arguments, Execute};
+
+        let mut qb = order_sql::<sqlx::Postgres>("id[not_in]=1,2,3,4,5&status=paid,shipped");
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM orders WHERE 1=1 AND id <> ALL($1) AND status = ANY($2)"
+        );
+
+        // each list is bound as one array, however long it is
+        let mut query = qb.build();
+        let args = query.take_arguments().unwrap().unwrap();
+        assert_eq!(args.len(), 2);
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn test_sqlx_in_sqlite() {
+        let qb = order_sql::<Sqlite>("id[not_in]=1,2,3&status=paid,shipped");
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM orders WHERE 1=1 AND id NOT IN (?, ?, ?) AND status IN (?, ?)"
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_sqlx_in_sqlite_rows() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE orders (id INTEGER, status TEXT);
+             INSERT INTO orders VALUES (1, 'paid'), (2, 'shipped'), (3, 'paid'), (4, 'open');",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        for (query, expected) in [
+            ("status[in]=paid,open", vec![1, 3, 4]),
+            ("status[!in]=paid&id[nin]=4", vec![2]),
+            ("id=2,3,9", vec![2, 3]),
+        ] {
+            let mut qb = order_sql::<Sqlite>(query);
+            qb.push(" ORDER BY id");
+
+            let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+            let rows = rows.iter().map(|r| r.get::<i64, _>(0)).collect::<Vec<_>>();
+            assert_eq!(rows, expected, "{query}");
+        }
+    }
+}
+
+#[cfg(all(feature = "postgres", feature = "derive"))]
+mod array {
+    use filtrum::{
+        array_filter::ArrayFilters,
+        query_filter::FromQueryFilter,
+        sqlx::{ApplyOptions, Conjunction, SqlxFilter},
+        Filterable, StringFilters,
+    };
+    use sqlx::{Arguments, Execute, Postgres, QueryBuilder};
+
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "posts")]
+    struct PostFilter {
+        title: StringFilters,
+        tags: ArrayFilters<String>,
+    }
+
+    fn post_sql(query: &str, options: &ApplyOptions) -> QueryBuilder<'static, Postgres> {
+        let filter = FromQueryFilter::<PostFilter>::from_str(query).unwrap();
+        let mut qb = QueryBuilder::new("SELECT * FROM posts WHERE 1=1");
+        filter.apply_with(&mut qb, options);
+        qb
+    }
+
+    #[test]
+    fn test_sqlx_array_contains() {
+        // `contains` is a LIKE on a string field, and containment on an array field
+        let query = "title[contains]=rust&tags[contains]=rust,web&tags[array_contains]=db";
+        let mut qb = post_sql(query, &ApplyOptions::default());
+        assert_eq!(
+            qb.sql(),
+            "SELECT * FROM posts WHERE 1=1 AND posts.title LIKE $1 ESCAPE '\\' AND posts.tags @> $2 AND posts.tags @> $3"
+        );
+
+        // each list is bound as one array
+        let mut query = qb.build();
+        let args = query.take_arguments().unwrap().unwrap();
+        assert_eq!(args.len(), 3);
+
+        let options = ApplyOptions {
+            conjunction: Conjunction::Or,
+            ..Default::default()
+        };
+        assert_eq!(
+            post_sql("title=a&tags=rust", &options).sql(),
+            "SELECT * FROM posts WHERE 1=1 AND ((posts.title = $1) OR (posts.tags @> $2))"
+        );
+    }
+}
+
+#[cfg(feature = "derive")]
+mod transform {
+    use filtrum::{
+        query_filter::FromQueryFilter,
+        sqlx::{ApplyOptions, SqlxFilter, Transform, Transforms},
+        Filterable, StringFilters,
+    };
+    use sqlx::{Connection, QueryBuilder, Row, Sqlite, SqliteConnection};
+
+    #[derive(Filterable, Debug, Default)]
+    struct EventFilter {
+        #[filtrum(transform = "DATE")]
+        created_at: StringFilters,
+        #[filtrum(transform = "lower")]
+        email: StringFilters,
+        name: StringFilters,
+    }
+
+    fn event_sql(query: &str, options: &ApplyOptions) -> QueryBuilder<'static, Sqlite> {
+        let filter = FromQueryFilter::<EventFilter>::from_str(query).unwrap();
+        let mut qb = QueryBuilder::new("SELECT * FROM events WHERE 1=1");
+        filter.apply_with(&mut qb, options);
+        qb
+    }
+
+    #[test]
+    fn test_sqlx_transform() {
+        let query = "created_at=2024-05-01&email=ana@x.io&name=ana&order_by=created_at";
+
+        // `ORDER BY` keeps the plain column
+        assert_eq!(
+            event_sql(query, &ApplyOptions::default()).sql(),
+            "SELECT * FROM events WHERE 1=1 AND DATE(created_at) = ? AND LOWER(email) = ? AND name = ? ORDER BY created_at ASC"
+        );
+
+        // a runtime transform replaces the attribute, and transforms other fields too
+        let options = ApplyOptions {
+            transforms: Transforms::new()
+                .with("email", Transform::Upper)
+                .with("name", Transform::Trim),
+            ..Default::default()
+        };
+        assert_eq!(
+            event_sql(query, &options).sql(),
+            "SELECT * FROM events WHERE 1=1 AND DATE(created_at) = ? AND UPPER(email) = ? AND TRIM(name) = ? ORDER BY created_at ASC"
+        );
+
+        assert_eq!("date".parse::<Transform>().unwrap(), Transform::Date);
+        assert!("DROP TABLE".parse::<Transform>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlx_transform_rows() {
+        let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+        sqlx::raw_sql(
+            "CREATE TABLE events (id INTEGER, created_at TEXT, email TEXT, name TEXT);
+             INSERT INTO events VALUES
+                (1, '2024-05-01 09:30:00', 'Ana@X.io', 'ana'),
+                (2, '2024-05-01 18:00:00', 'bob@x.io', 'bob'),
+                (3, '2024-05-02 09:30:00', 'ana@x.io', 'ana');",
+        )
+        .execute(&mut conn)
+        .await
+        .unwrap();
+
+        for (query, expected) in [
+            ("created_at=2024-05-01", vec![1, 2]),
+            ("email=ana@x.io", vec![1, 3]),
+            ("created_at=2024-05-01&email=ana@x.io", vec![1]),
+        ] {
+            let mut qb = event_sql(query, &ApplyOptions::default());
+            qb.push(" ORDER BY id");
+
+            let rows = qb.build().fetch_all(&mut conn).await.unwrap();
+            let rows = rows.iter().map(|r| r.get::<i64, _>(0)).collect::<Vec<_>>();
+            assert_eq!(rows, expected, "{query}");
+        }
+    }
+}
+
+#[cfg(all(feature = "decimal", feature = "postgres"))]
+#[test]
+fn test_sqlx_decimal_postgres() {
+    use rust_decimal::Decimal;
+
+    let qs = "price[gte]=10.50&price[between]=0.1,99.99";
+    let filter = NumberFilters::<Decimal>::from_str("price", qs).unwrap();
+
+    let mut qb: QueryBuilder<sqlx::Postgres> =
+        QueryBuilder::new("SELECT * FROM products WHERE 1=1");
+    filter.apply(&mut qb);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM products WHERE 1=1 AND price >= $1 AND price BETWEEN $2 AND $3"
+    );
+}
+
+#[test]
+fn test_sqlx_string_between() {
+    assert_eq!(
+        string_sql::<Sqlite>("name[between]=A,M&name[!between]=B,C"),
+        "SELECT * FROM users WHERE 1=1 AND name BETWEEN ? AND ? AND NOT (name BETWEEN ? AND ?)"
+    );
+}
+
+#[tokio::test]
+async fn test_sqlx_date_between_sqlite() {
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::raw_sql(
+        "CREATE TABLE events (id INTEGER, created TEXT);
+         INSERT INTO events VALUES (1, '2023-12-31'), (2, '2024-01-01'), (3, '2024-06-30'), (4, '2024-07-01');",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    // ISO dates order the same lexically as in time
+    let created =
+        StringFilters::<String>::from_str("created", "created[between]=2024-01-01,2024-06-30")
+            .unwrap();
+
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT id FROM events WHERE 1=1");
+    created.apply(&mut qb);
+    qb.push(" ORDER BY id");
+
+    let ids = qb
+        .build()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.get::<i64, _>(0))
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids, vec![2, 3]);
+}
+
+#[test]
+fn test_sqlx_number_range() {
+    let sql = |query: &str| {
+        let filter = NumberFilters::<i32>::from_str("age", query).unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        filter.apply(&mut qb);
+        qb.sql().to_string()
+    };
+
+    assert_eq!(
+        sql("age[range]=[18,65]"),
+        "SELECT * FROM users WHERE 1=1 AND age >= ? AND age <= ?"
+    );
+    assert_eq!(
+        sql("age[range]=[18,65)"),
+        "SELECT * FROM users WHERE 1=1 AND age >= ? AND age < ?"
+    );
+    assert_eq!(
+        sql("age[range]=(18,65]"),
+        "SELECT * FROM users WHERE 1=1 AND age > ? AND age <= ?"
+    );
+    assert_eq!(
+        sql("age[range]=(18,65)"),
+        "SELECT * FROM users WHERE 1=1 AND age > ? AND age < ?"
+    );
+}
+
+#[test]
+fn test_sqlx_equal_list() {
+    use filtrum::sqlx::{render_sql, render_sql_with, ApplyOptions, Conjunction};
+
+    let role = EqualFilter::<String>::from_str("role", "role[in]=admin,editor").unwrap();
+    let (sql, binds) = render_sql("SELECT * FROM users WHERE 1=1", &role);
+    assert_eq!(sql, "SELECT * FROM users WHERE 1=1 AND role IN (?, ?)");
+    assert_eq!(binds, vec![r#"Text("admin")"#, r#"Text("editor")"#]);
+
+    let role = EqualFilter::<String>::from_str("role", "role=admin").unwrap();
+    let (sql, binds) = render_sql("", &role);
+    assert_eq!(sql, " AND role = ?");
+    assert_eq!(binds, vec![r#"Text("admin")"#]);
+
+    let role = EqualFilter::<String>::from_str("role", r"role[in]=a\,b,c").unwrap();
+    let options = ApplyOptions {
+        conjunction: Conjunction::Or,
+        ..Default::default()
+    };
+    let (sql, binds) = render_sql_with("", &Box::new(role), &options);
+    assert_eq!(sql, " AND role IN (?, ?)");
+    assert_eq!(binds, vec![r#"Text("a,b")"#, r#"Text("c")"#]);
+}
+
+#[test]
+fn test_sqlx_bracketed_column() {
+    use filtrum::sqlx::{render_sql_with, ApplyOptions};
+
+    let data =
+        StringFilters::<String>::from_str("data[json]", r"data\[json\][ne]=a&data=b").unwrap();
+    assert_eq!(data.0, vec![filtrum::StringFilter::Ne("a".to_string())]);
+
+    let options = ApplyOptions {
+        quote_identifiers: true,
+        ..Default::default()
+    };
+    let (sql, binds) = render_sql_with("", &data, &options);
+    assert_eq!(sql, r#" AND "data[json]" <> ?"#);
+    assert_eq!(binds, vec![r#"Text("a")"#]);
+}
+
+#[test]
+fn test_sqlx_standalone_pagination() {
+    use filtrum::{limit::Limit, skip::Skip};
+
+    let age = NumberFilters::<i32>::from_str("age", "age[gte]=18").unwrap();
+    let mut qb = QueryBuilder::<Sqlite>::new("SELECT * FROM users WHERE 1=1");
+    age.apply(&mut qb);
+    Limit::new(20).apply(&mut qb);
+    Skip::new(40).apply(&mut qb);
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND age >= ? LIMIT ? OFFSET ?"
+    );
+
+    let (sql, binds) = filtrum::sqlx::render_sql("SELECT * FROM users", &Limit::new(5));
+    assert_eq!(sql, "SELECT * FROM users LIMIT ?");
+    assert_eq!(binds, vec!["BigInt(5)"]);
+}
+
+#[test]
+fn test_sqlx_optional_filters() {
+    use filtrum::sqlx::ApplyOptions;
+
+    let sql = |name: &Option<StringFilters>, age: &Option<NumberFilters<i32>>| {
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+        name.apply(&mut qb);
+        age.apply_with(&mut qb, &ApplyOptions::default());
+        qb.sql().to_string()
+    };
+
+    let name = Some(StringFilters::<String>::from_str("name", "name[sw]=Al").unwrap());
+    let age = Some(NumberFilters::<i32>::from_str("age", "age[gte]=18").unwrap());
+    assert_eq!(
+        sql(&name, &age),
+        "SELECT * FROM users WHERE 1=1 AND name LIKE ? ESCAPE '\\' AND age >= ?"
+    );
+    assert_eq!(
+        sql(&None, &age),
+        "SELECT * FROM users WHERE 1=1 AND age >= ?"
+    );
+    assert_eq!(sql(&None, &None), "SELECT * FROM users WHERE 1=1");
+
+    let active: Option<EqualFilter<bool>> = None;
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("");
+    assert!(!active.push_group(&mut qb, " OR ", &ApplyOptions::default()));
+    assert!(qb.sql().is_empty());
+}
+
+#[tokio::test]
+async fn test_sqlx_number_widths_sqlite() {
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::raw_sql(
+        "CREATE TABLE items (id INTEGER, weight REAL);
+         INSERT INTO items VALUES (1, 0.25), (2, 1.5), (3, 4.0);",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    let weight = NumberFilters::<f32>::from_str("weight", "weight[between]=0.5,4").unwrap();
+    // sqlx can't bind a `u64` on SQLite, so it is narrowed first
+    let id = NumberFilters::<u64>::from_str("id", "id[gt]=1")
+        .unwrap()
+        .try_map(i64::try_from)
+        .unwrap();
+
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT id FROM items WHERE 1=1");
+    weight.apply(&mut qb);
+    id.apply(&mut qb);
+    qb.push(" ORDER BY id");
+
+    let ids = qb
+        .build()
+        .fetch_all(&mut conn)
+        .await
+        .unwrap()
+        .iter()
+        .map(|r| r.get::<i64, _>(0))
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids, vec![2, 3]);
+}
+
+#[test]
+fn test_sqlx_u64_mysql() {
+    let filter = NumberFilters::<u64>::from_str("id", "id[gte]=18446744073709551615").unwrap();
+
+    let mut qb: QueryBuilder<sqlx::MySql> = QueryBuilder::new("SELECT * FROM items WHERE 1=1");
+    filter.apply(&mut qb);
+
+    assert_eq!(qb.sql(), "SELECT * FROM items WHERE 1=1 AND id >= ?");
+}
+
+#[tokio::test]
+async fn test_sqlx_like_escape() {
+    use filtrum::sqlx::ApplyOptions;
+    use sqlx::{Connection, Row, SqliteConnection};
+
+    let mut conn = SqliteConnection::connect("sqlite::memory:").await.unwrap();
+    sqlx::raw_sql(
+        "CREATE TABLE codes (name TEXT);
+         INSERT INTO codes VALUES ('50% off'), ('500 off'), ('a_b'), ('axb'), ('x!y');",
+    )
+    .execute(&mut conn)
+    .await
+    .unwrap();
+
+    let options = ApplyOptions {
+        like_escape: '!',
+        ..Default::default()
+    };
+
+    async fn names(
+        conn: &mut SqliteConnection,
+        query: &str,
+        options: &ApplyOptions,
+    ) -> (String, Vec<String>) {
+        let filter = StringFilters::<String>::from_str("name", query).unwrap();
+        let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT name FROM codes WHERE 1=1");
+        filter.apply_with(&mut qb, options);
+
+        let sql = qb.sql().to_string();
+        let rows = qb.build().fetch_all(conn).await.unwrap();
+        (sql, rows.iter().map(|r| r.get(0)).collect())
+    }
+
+    let (sql, rows) = names(&mut conn, "name[sw]=50%", &options).await;
+    assert_eq!(
+        sql,
+        "SELECT name FROM codes WHERE 1=1 AND name LIKE ? ESCAPE '!'"
+    );
+    assert_eq!(rows, vec!["50% off"]);
+
+    let (_, rows) = names(&mut conn, "name[contains]=_", &options).await;
+    assert_eq!(rows, vec!["a_b"]);
+
+    // the escape character itself is matched literally too
+    let (_, rows) = names(&mut conn, "name[ew]=!y", &options).await;
+    assert_eq!(rows, vec!["x!y"]);
+
+    // the default escape behaves the same
+    let (sql, rows) = names(&mut conn, "name[sw]=50%", &ApplyOptions::default()).await;
+    assert!(sql.ends_with(" ESCAPE '\\'"));
+    assert_eq!(rows, vec!["50% off"]);
+}
+
+#[cfg(all(feature = "mysql", feature = "derive"))]
+mod mysql {
+    use filtrum::{
+        number_filter::NumberFilters,
+        query_filter::FromQueryFilter,
+        sqlx::{ApplyOptions, Dialect, SqlxFilter},
+        string_filter::StringFilters,
+        Filterable,
+    };
+    use sqlx::{MySql, QueryBuilder};
+
+    // `key` and `rank` are reserved words on MySQL
+    #[derive(Filterable, Debug, Default)]
+    #[filtrum(table = "users")]
+    struct ReservedFilter {
+        key: StringFilters,
+        name: StringFilters,
+        rank: NumberFilters<i32>,
+    }
+
+    fn mysql_sql(query: &str, options: &ApplyOptions) -> String {
+        let filter = FromQueryFilter::<ReservedFilter>::from_str(query).unwrap();
+        let mut qb: QueryBuilder<MySql> = QueryBuilder::new("SELECT * FROM `users` WHERE 1=1");
+        filter.apply_with(&mut qb, options);
+        qb.sql().to_string()
+    }
+
+    #[test]
+    fn test_mysql_quoted_identifiers() {
+        let options = ApplyOptions {
+            quote_identifiers: true,
+            ..Default::default()
+        };
+        let query = "key[sw]=a_&name[ilike]=Ana%&rank[between]=1,5&order_by=-rank&limit=10&skip=20";
+
+        assert_eq!(
+            mysql_sql(query, &options),
+            "SELECT * FROM `users` WHERE 1=1 AND `users`.`key` LIKE ? ESCAPE '\\\\' AND LOWER(`users`.`name`) LIKE LOWER(?) AND `users`.`rank` BETWEEN ? AND ? ORDER BY `users`.`rank` DESC LIMIT ? OFFSET ?"
+        );
+
+        assert_eq!(Dialect::MySql.quote_identifier("a`b"), "`a``b`");
+    }
+
+    #[test]
+    fn test_mysql_skip_without_limit() {
+        // MySQL has no bare `OFFSET`, so a lone `skip` gets the largest `LIMIT`
+        assert_eq!(
+            mysql_sql("rank[gt]=1&skip=5", &ApplyOptions::default()),
+            "SELECT * FROM `users` WHERE 1=1 AND users.rank > ? LIMIT 18446744073709551615 OFFSET ?"
+        );
+
+        let sql = mysql_sql("limit=10&skip=5", &ApplyOptions::default());
+        assert!(sql.ends_with(" LIMIT ? OFFSET ?"));
+    }
+
+    #[test]
+    fn test_mysql_unquoted() {
+        assert_eq!(
+            mysql_sql("name[ilike]=ana&rank[gt]=1", &ApplyOptions::default()),
+            "SELECT * FROM `users` WHERE 1=1 AND LOWER(users.name) LIKE LOWER(?) AND users.rank > ?"
+        );
+    }
+}
+
+fn random_sql<DB>() -> String
+where
+    DB: sqlx::Database,
+    filtrum::OrderBy: SqlxFilter<DB>,
+{
+    let order_by = filtrum::OrderBy::from_str("order_by[rand]=1")
+        .unwrap()
+        .unwrap();
+    let mut qb: QueryBuilder<DB> = QueryBuilder::new("SELECT * FROM users");
+    order_by.apply(&mut qb);
+    qb.sql().to_string()
+}
+
+#[test]
+fn test_sqlx_random_sqlite() {
+    assert_eq!(
+        random_sql::<Sqlite>(),
+        "SELECT * FROM users ORDER BY RANDOM()"
+    );
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_random_postgres() {
+    assert_eq!(
+        random_sql::<sqlx::Postgres>(),
+        "SELECT * FROM users ORDER BY RANDOM()"
+    );
+}
+
+#[cfg(feature = "mysql")]
+#[test]
+fn test_sqlx_random_mysql() {
+    assert_eq!(
+        random_sql::<sqlx::MySql>(),
+        "SELECT * FROM users ORDER BY RAND()"
+    );
+}
+
+#[test]
+fn test_sqlx_typed_order_by() {
+    use filtrum::sortable::{Sortable, TypedOrderBy};
+
+    enum UserSort {
+        Name,
+        Age,
+    }
+
+    impl Sortable for UserSort {
+        fn from_name(name: &str) -> Option<Self> {
+            match name {
+                "name" => Some(UserSort::Name),
+                "age" => Some(UserSort::Age),
+                _ => None,
+            }
+        }
+
+        fn column(&self) -> &'static str {
+            match self {
+                UserSort::Name => "display_name",
+                UserSort::Age => "age",
+            }
+        }
+    }
+
+    let order_by = TypedOrderBy::<UserSort>::from_str("order_by=-name")
+        .unwrap()
+        .unwrap();
+    let mut qb: QueryBuilder<Sqlite> = QueryBuilder::new("SELECT * FROM users");
+    order_by.apply(&mut qb);
+    assert_eq!(qb.sql(), "SELECT * FROM users ORDER BY display_name DESC");
+
+    assert!(TypedOrderBy::<UserSort>::from_str("order_by=age").is_ok());
+    assert!(matches!(
+        TypedOrderBy::<UserSort>::from_str("order_by=(SELECT 1)"),
+        Err(filtrum::errors::FilterParseError::UnknownField(_))
+    ));
+}
+
+#[cfg(feature = "postgres")]
+#[test]
+fn test_sqlx_ilike_postgres() {
+    use filtrum::sqlx::ApplyOptions;
+
+    let filter = StringFilters::<String>::from_str("name", "name[ilike]=ana%").unwrap();
+    let options = ApplyOptions {
+        quote_identifiers: true,
+        ..Default::default()
+    };
+
+    let mut qb: QueryBuilder<sqlx::Postgres> = QueryBuilder::new("SELECT * FROM users WHERE 1=1");
+    filter.apply_with(&mut qb, &options);
+
+    assert_eq!(
+        qb.sql(),
+        "SELECT * FROM users WHERE 1=1 AND \"name\" ILIKE $1"
+    );
+}
@@ -0,0 +1,9 @@
+use filtrum::{Filterable, StringFilters};
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    #[filtrum(alias = 3)]
+    name: StringFilters,
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use filtrum::{Filterable, StringFilters};
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    #[filtrum(allowed_ops = "eq, [sw]")]
+    name: StringFilters,
+    #[filtrum(allowed_ops = "eq,,ne")]
+    email: StringFilters,
+    #[filtrum(allowed_ops = "eq, gtee")]
+    age: StringFilters,
+    #[filtrum(allowed_ops = "!sw, GTE")]
+    title: StringFilters,
+    #[filtrum(skip, allowed_ops = "eq")]
+    password: StringFilters,
+}
+
+fn main() {}
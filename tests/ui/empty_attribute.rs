@@ -0,0 +1,12 @@
+use filtrum::{Filterable, NumberFilters, StringFilters};
+
+#[derive(Default, Filterable)]
+#[filtrum(table = "")]
+struct UserFilter {
+    #[filtrum(alias = "")]
+    name: StringFilters,
+    #[filtrum(table = "")]
+    age: NumberFilters<i32>,
+}
+
+fn main() {}
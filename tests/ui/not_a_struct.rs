@@ -0,0 +1,8 @@
+use filtrum::Filterable;
+
+#[derive(Filterable)]
+enum UserFilter {
+    Name,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use filtrum::{Filterable, NumberFilters};
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    age: NumberFilters<i32>,
+    active: bool,
+    parent_id: Option<i64>,
+    #[filtrum(skip)]
+    page: u32,
+}
+
+fn main() {}
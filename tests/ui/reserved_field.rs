@@ -0,0 +1,18 @@
+use filtrum::{Filterable, NumberFilters, StringFilters};
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    name: StringFilters,
+    limit: NumberFilters<i32>,
+    #[filtrum(skip)]
+    skip: NumberFilters<i32>,
+    cursor: StringFilters,
+}
+
+#[derive(Default, Filterable)]
+#[filtrum(allow_reserved)]
+struct QuotaFilter {
+    limit: NumberFilters<i32>,
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+use filtrum::{Filterable, NumberFilters, StringFilters};
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    #[filtrum(skip, alias = "full_name")]
+    name: StringFilters,
+    #[filtrum(skip, table = "orders", default_value = "gte=1")]
+    total: NumberFilters<i32>,
+    age: NumberFilters<i32>,
+}
+
+fn main() {}
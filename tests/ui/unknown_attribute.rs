@@ -0,0 +1,11 @@
+use filtrum::{Filterable, NumberFilters, StringFilters};
+
+#[derive(Default, Filterable)]
+#[filtrum(tabel = "users")]
+struct UserFilter {
+    #[filtrum(skipp)]
+    name: StringFilters,
+    age: NumberFilters<i32>,
+}
+
+fn main() {}
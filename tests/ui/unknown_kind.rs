@@ -0,0 +1,13 @@
+use filtrum::{Filterable, NumberFilters};
+
+type Ages = NumberFilters<i32>;
+
+#[derive(Default, Filterable)]
+struct UserFilter {
+    #[filtrum(kind = "integer")]
+    age: Ages,
+    #[filtrum(skip, kind = "number")]
+    min_age: Ages,
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use filtrum::{Filterable, StringFilters};
+
+#[derive(Default, Filterable)]
+struct EventFilter {
+    #[filtrum(transform = "CONCAT")]
+    created_at: StringFilters,
+    #[filtrum(skip, transform = "DATE")]
+    updated_at: StringFilters,
+}
+
+fn main() {}